@@ -0,0 +1,117 @@
+//! Derive macros for [ascii-dag](https://docs.rs/ascii-dag).
+//!
+//! Use these through the `derive` feature of `ascii-dag`, which re-exports
+//! them next to the traits they implement, rather than depending on this
+//! crate directly.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Member, Type, parse_macro_input};
+
+/// Derive `CycleDetectable` from two marked fields.
+///
+/// `#[dag(id)]` marks the field whose clone is the node ID and
+/// `#[dag(dependencies)]` the collection of IDs the node depends on. The
+/// collection can be anything whose reference iterates over `&Id` (`Vec`,
+/// slice, array, `HashSet`, ...); `dependencies` walks it and clones each ID.
+///
+/// ```ignore
+/// use ascii_dag::cycles::generic::{CycleDetectable, detect_cycle};
+///
+/// #[derive(CycleDetectable)]
+/// struct ErrorNode {
+///     #[dag(id)]
+///     id: usize,
+///     message: &'static str,
+///     #[dag(dependencies)]
+///     caused_by: Vec<usize>,
+/// }
+/// ```
+#[proc_macro_derive(CycleDetectable, attributes(dag))]
+pub fn derive_cycle_detectable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_cycle_detectable(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Which field plays which role.
+struct Roles<'a> {
+    id: (Member, &'a Type),
+    dependencies: Member,
+}
+
+fn expand_cycle_detectable(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Roles {
+        id: (id, id_ty),
+        dependencies,
+    } = find_roles(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::ascii_dag::cycles::generic::CycleDetectable
+            for #name #ty_generics #where_clause
+        {
+            type Id = #id_ty;
+
+            fn id(&self) -> Self::Id {
+                ::core::clone::Clone::clone(&self.#id)
+            }
+
+            fn dependencies(&self) -> impl ::core::iter::Iterator<Item = Self::Id> + '_ {
+                ::core::iter::Iterator::cloned(
+                    ::core::iter::IntoIterator::into_iter(&self.#dependencies),
+                )
+            }
+        }
+    })
+}
+
+fn find_roles(input: &DeriveInput) -> syn::Result<Roles<'_>> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "CycleDetectable can only be derived for structs",
+            ));
+        }
+    };
+
+    let mut id = None;
+    let mut dependencies = None;
+    for (index, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("dag")) {
+            attr.parse_nested_meta(|meta| {
+                let slot = if meta.path.is_ident("id") {
+                    &mut id
+                } else if meta.path.is_ident("dependencies") {
+                    &mut dependencies
+                } else {
+                    return Err(meta.error("expected `id` or `dependencies`"));
+                };
+                if slot.is_some() {
+                    return Err(meta.error("only one field can have this role"));
+                }
+                *slot = Some((member.clone(), &field.ty));
+                Ok(())
+            })?;
+        }
+    }
+
+    let missing = |role: &str| {
+        Error::new(
+            input.ident.span(),
+            format!("missing a `#[dag({role})]` field"),
+        )
+    };
+    Ok(Roles {
+        id: id.ok_or_else(|| missing("id"))?,
+        dependencies: dependencies.ok_or_else(|| missing("dependencies"))?.0,
+    })
+}
@@ -67,6 +67,7 @@ fn example_trait_based_registry() {
     println!("   Using traits for cleaner API\n");
 
     #[derive(Debug, Clone)]
+    #[allow(dead_code)]
     struct ErrorDef {
         id: String,
         message: String,
@@ -176,14 +177,9 @@ fn example_metrics_dashboard() {
     let metrics = GraphMetrics::compute(&files, get_deps);
 
     println!("   📈 Graph Statistics:");
-    println!("      Total files: {}", metrics.node_count());
-    println!("      Total dependencies: {}", metrics.edge_count());
-    println!("      Root files (sources): {}", metrics.root_count());
-    println!("      Leaf files (outputs): {}", metrics.leaf_count());
-    println!("      Max depth: {}", metrics.max_depth());
-    println!("      Max impact: {} files", metrics.max_descendants());
-    println!("      Avg dependencies: {:.2}", metrics.avg_dependencies());
-    println!("      Density: {:.2}%", metrics.density() * 100.0);
+    for line in metrics.to_string().lines() {
+        println!("      {}", line);
+    }
     println!();
 
     println!("   🔍 Graph Properties:");
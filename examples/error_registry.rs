@@ -28,6 +28,7 @@ impl ChainableError {
         self
     }
 
+    #[allow(dead_code)]
     fn with_causes(mut self, causes: Vec<&'static str>) -> Self {
         self.caused_by.extend(causes);
         self
@@ -35,15 +36,14 @@ impl ChainableError {
 }
 
 /// Error registry that validates no circular dependencies
+#[derive(Default)]
 pub struct ErrorRegistry {
     errors: HashMap<&'static str, ChainableError>,
 }
 
 impl ErrorRegistry {
     pub fn new() -> Self {
-        Self {
-            errors: HashMap::new(),
-        }
+        Self::default()
     }
 
     /// Register an error, checking for cycles
@@ -31,8 +31,8 @@ impl CycleDetectable for Error {
         self.id
     }
 
-    fn dependencies(&self) -> Vec<Self::Id> {
-        self.caused_by.clone()
+    fn dependencies(&self) -> impl Iterator<Item = Self::Id> + '_ {
+        self.caused_by.iter().copied()
     }
 }
 
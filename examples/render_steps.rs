@@ -0,0 +1,28 @@
+use ascii_dag::graph::DAG;
+use std::{thread, time::Duration};
+
+fn main() {
+    let dag = DAG::from_edges(
+        &[
+            (1, "Fetch"),
+            (2, "Parse"),
+            (3, "Typecheck"),
+            (4, "Codegen"),
+            (5, "Link"),
+        ],
+        &[(1, 2), (2, 3), (2, 4), (3, 5), (4, 5)],
+    );
+
+    let frames = dag.render_steps().expect("graph is acyclic");
+    let height = frames[0].lines().count();
+
+    for (step, frame) in frames.iter().enumerate() {
+        if step > 0 {
+            // Move the cursor back up over the previous frame and redraw in place
+            print!("\x1b[{}A", height + 1);
+        }
+        println!("Step {}/{}", step, frames.len() - 1);
+        print!("{}", frame);
+        thread::sleep(Duration::from_millis(500));
+    }
+}
@@ -135,7 +135,7 @@ fn example_cycle_detection() {
         Ok(_) => println!("   ✓ No cycles detected"),
         Err(cycle) => {
             println!("   ✗ Cycle detected!");
-            println!("   Circular dependency: {:?}", cycle);
+            println!("   Items that cannot be ordered: {:?}", cycle);
         }
     }
     println!();
@@ -64,6 +64,81 @@ impl<'a> DAG<'a> {
         false
     }
 
+    /// Check whether adding the edge `from → to` would close a cycle, without
+    /// adding it.
+    ///
+    /// That is the case exactly when `to` can already reach `from` (or when
+    /// `from == to`), so this runs one DFS from `to` instead of a full
+    /// [`has_cycle`](Self::has_cycle) pass: cheap enough to guard every
+    /// insertion in an incremental builder. Nodes that don't exist yet can't
+    /// be part of a cycle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C")], &[(1, 2), (2, 3)]);
+    ///
+    /// assert!(dag.would_create_cycle(3, 1));
+    /// assert!(!dag.would_create_cycle(1, 3));
+    /// if !dag.would_create_cycle(3, 4) {
+    ///     dag.add_edge(3, 4);
+    /// }
+    /// assert!(!dag.has_cycle());
+    /// ```
+    pub fn would_create_cycle(&self, from: usize, to: usize) -> bool {
+        if from == to {
+            return true;
+        }
+        let (Some(from_idx), Some(to_idx)) = (self.node_index(from), self.node_index(to)) else {
+            return false;
+        };
+
+        let mut visited = vec![false; self.nodes.len()];
+        let mut stack = vec![to_idx];
+        visited[to_idx] = true;
+        while let Some(idx) = stack.pop() {
+            if idx == from_idx {
+                return true;
+            }
+            for &child in &self.children[idx] {
+                if !visited[child] {
+                    visited[child] = true;
+                    stack.push(child);
+                }
+            }
+        }
+        false
+    }
+
+    /// The edges of the first cycle found, as consecutive `(from, to)` pairs
+    /// ending with the edge that closes the loop.
+    ///
+    /// Follows the same node path the cycle renderer reports; a self-loop is
+    /// a single `(id, id)` pair. `None` if the graph is acyclic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C")], &[(1, 2), (2, 3)]);
+    /// assert_eq!(dag.cycle_edges(), None);
+    ///
+    /// dag.add_edge(3, 1);
+    /// assert_eq!(dag.cycle_edges(), Some(vec![(1, 2), (2, 3), (3, 1)]));
+    /// ```
+    pub fn cycle_edges(&self) -> Option<Vec<(usize, usize)>> {
+        let path = self.find_cycle_path()?;
+        Some(
+            path.iter()
+                .zip(path.iter().cycle().skip(1))
+                .map(|(&from, &to)| (from, to))
+                .collect(),
+        )
+    }
+
     /// Helper function for cycle detection using DFS.
     fn has_cycle_util(&self, idx: usize, visited: &mut [bool], rec_stack: &mut [bool]) -> bool {
         if rec_stack[idx] {
@@ -76,15 +151,10 @@ impl<'a> DAG<'a> {
         visited[idx] = true;
         rec_stack[idx] = true;
 
-        let node_id = self.nodes[idx].0;
-        for &(from, to) in &self.edges {
-            if from == node_id {
-                // O(1) HashMap lookup instead of O(n) scan
-                if let Some(child_idx) = self.node_index(to) {
-                    if self.has_cycle_util(child_idx, visited, rec_stack) {
-                        return true;
-                    }
-                }
+        // Cached adjacency: O(degree) per node, O(V+E) overall
+        for &child_idx in &self.children[idx] {
+            if self.has_cycle_util(child_idx, visited, rec_stack) {
+                return true;
             }
         }
 
@@ -130,15 +200,9 @@ impl<'a> DAG<'a> {
         visited[start_idx] = true;
         path.push(start_idx);
 
-        let node_id = self.nodes[start_idx].0;
-        for &(from, to) in &self.edges {
-            if from == node_id {
-                // O(1) HashMap lookup instead of O(n) scan
-                if let Some(child_idx) = self.node_index(to) {
-                    if let Some(cycle) = self.find_cycle_from(child_idx, visited, path) {
-                        return Some(cycle);
-                    }
-                }
+        for &child_idx in &self.children[start_idx] {
+            if let Some(cycle) = self.find_cycle_from(child_idx, visited, path) {
+                return Some(cycle);
             }
         }
 
@@ -179,4 +243,38 @@ mod tests {
 
         assert!(dag.has_cycle());
     }
+
+    #[test]
+    fn test_would_create_cycle_matches_has_cycle() {
+        let edges = [(1, 2), (2, 3), (1, 4), (4, 3), (3, 5)];
+        let dag = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C"), (4, "D"), (5, "E")], &edges);
+
+        for from in 1..=5 {
+            for to in 1..=5 {
+                let mut extended = dag.clone();
+                extended.add_edge(from, to);
+                assert_eq!(
+                    dag.would_create_cycle(from, to),
+                    extended.has_cycle(),
+                    "edge {} → {}",
+                    from,
+                    to
+                );
+            }
+        }
+        assert!(!dag.would_create_cycle(5, 99));
+        assert!(dag.would_create_cycle(99, 99));
+    }
+
+    #[test]
+    fn test_cycle_edges() {
+        let dag = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C")], &[(1, 2), (2, 3), (3, 2)]);
+        let edges = dag.cycle_edges().unwrap();
+        assert_eq!(edges, vec![(2, 3), (3, 2)]);
+        assert!(edges.iter().all(|&(from, to)| dag.has_edge(from, to)));
+
+        let mut looped = DAG::new();
+        looped.add_edge(5, 5);
+        assert_eq!(looped.cycle_edges(), Some(vec![(5, 5)]));
+    }
 }
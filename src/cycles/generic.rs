@@ -11,15 +11,42 @@ pub mod roots;
 
 #[cfg(not(feature = "std"))]
 use alloc::collections::BTreeMap as HashMap;
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
+use core::borrow::Borrow;
 #[cfg(feature = "std")]
 use std::collections::HashMap;
 
+#[cfg(feature = "derive")]
+pub use ascii_dag_derive::CycleDetectable;
+
+/// Bound on the IDs that the generic algorithms use as lookup keys.
+///
+/// With `std` the lookups are `HashMap`s and this means `Eq + Hash`; without
+/// it they fall back to `BTreeMap`s and it means `Ord` instead. Every type
+/// meeting the bound implements it automatically.
+#[cfg(feature = "std")]
+pub trait IdBound: Eq + core::hash::Hash {}
+#[cfg(feature = "std")]
+impl<T: Eq + core::hash::Hash + ?Sized> IdBound for T {}
+
+/// Bound on the IDs that the generic algorithms use as lookup keys.
+///
+/// With `std` the lookups are `HashMap`s and this means `Eq + Hash`; without
+/// it they fall back to `BTreeMap`s and it means `Ord` instead. Every type
+/// meeting the bound implements it automatically.
+#[cfg(not(feature = "std"))]
+pub trait IdBound: Ord {}
+#[cfg(not(feature = "std"))]
+impl<T: Ord + ?Sized> IdBound for T {}
+
 /// A trait for types that can be checked for cycles.
 ///
 /// Implement this for your custom types (errors, tasks, dependencies, etc.)
 /// to get cycle detection for free.
 ///
+/// With the `derive` feature, `#[derive(CycleDetectable)]` writes the impl
+/// from a `#[dag(id)]` field and a `#[dag(dependencies)]` field.
+///
 /// # Examples
 ///
 /// ```ignore
@@ -35,20 +62,23 @@ use std::collections::HashMap;
 ///         self.id
 ///     }
 ///     
-///     fn dependencies(&self) -> impl Iterator<Item = Self::Id> {
+///     fn dependencies(&self) -> impl Iterator<Item = Self::Id> + '_ {
 ///         self.caused_by.iter().copied()
 ///     }
 /// }
 /// ```
 pub trait CycleDetectable {
     /// The type used to identify nodes (e.g., usize, String, etc.)
-    type Id: Eq + core::hash::Hash + Clone;
+    type Id: IdBound + Clone;
 
     /// Get the unique identifier for this node
     fn id(&self) -> Self::Id;
 
-    /// Get the IDs of all nodes this one depends on
-    fn dependencies(&self) -> Vec<Self::Id>;
+    /// Get the IDs of all nodes this one depends on.
+    ///
+    /// The iterator may borrow from `self`, so IDs stored in a field can be
+    /// yielded without collecting them into a `Vec` first.
+    fn dependencies(&self) -> impl Iterator<Item = Self::Id> + '_;
 }
 
 /// Detect cycles in a collection using a higher-order function approach.
@@ -76,8 +106,33 @@ pub trait CycleDetectable {
 /// ```
 pub fn detect_cycle_fn<Id, F>(all_ids: &[Id], get_dependencies: F) -> Option<Vec<Id>>
 where
-    Id: Eq + core::hash::Hash + Clone,
+    Id: IdBound + Clone,
     F: Fn(&Id) -> Vec<Id>,
+{
+    detect_cycle_iter_fn(all_ids, get_dependencies)
+}
+
+/// [`detect_cycle_fn`] for closures that return any iterable of dependencies.
+///
+/// The closure may yield owned IDs or `&Id`, so adjacency data that already
+/// lives in slices can be handed over without a `Vec` per call.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::cycles::generic::detect_cycle_iter_fn;
+///
+/// // deps[i] = what item i depends on
+/// let deps: Vec<Vec<usize>> = vec![vec![1], vec![2], vec![0]];
+/// let ids = [0, 1, 2];
+/// assert!(detect_cycle_iter_fn(&ids, |&id| &deps[id]).is_some());
+/// ```
+pub fn detect_cycle_iter_fn<Id, F, I>(all_ids: &[Id], get_dependencies: F) -> Option<Vec<Id>>
+where
+    Id: IdBound + Clone,
+    F: Fn(&Id) -> I,
+    I: IntoIterator,
+    I::Item: Borrow<Id>,
 {
     let mut id_to_index: HashMap<Id, usize> = HashMap::new();
     for (idx, id) in all_ids.iter().enumerate() {
@@ -102,7 +157,7 @@ where
     None
 }
 
-fn has_cycle_util_fn<Id, F>(
+fn has_cycle_util_fn<Id, F, I>(
     idx: usize,
     all_ids: &[Id],
     get_dependencies: &F,
@@ -111,8 +166,10 @@ fn has_cycle_util_fn<Id, F>(
     rec_stack: &mut [bool],
 ) -> Option<Vec<Id>>
 where
-    Id: Eq + core::hash::Hash + Clone,
-    F: Fn(&Id) -> Vec<Id>,
+    Id: IdBound + Clone,
+    F: Fn(&Id) -> I,
+    I: IntoIterator,
+    I::Item: Borrow<Id>,
 {
     if rec_stack[idx] {
         // Found a cycle - return the node that completes it
@@ -129,7 +186,7 @@ where
     let deps = get_dependencies(current_id);
 
     for dep_id in deps {
-        if let Some(&dep_idx) = id_to_index.get(&dep_id) {
+        if let Some(&dep_idx) = id_to_index.get(dep_id.borrow()) {
             if let Some(mut cycle) = has_cycle_util_fn(
                 dep_idx,
                 all_ids,
@@ -170,18 +227,18 @@ where
     let all_ids: Vec<T::Id> = items.iter().map(|item| item.id()).collect();
     let id_to_item: HashMap<T::Id, &T> = items.iter().map(|item| (item.id(), item)).collect();
 
-    detect_cycle_fn(&all_ids, |id| {
+    detect_cycle_iter_fn(&all_ids, |id| {
         id_to_item
             .get(id)
-            .map(|item| item.dependencies())
-            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|item| item.dependencies())
     })
 }
 
 /// Just check if a cycle exists (faster than finding the path).
 pub fn has_cycle_fn<Id, F>(all_ids: &[Id], get_dependencies: F) -> bool
 where
-    Id: Eq + core::hash::Hash + Clone,
+    Id: IdBound + Clone,
     F: Fn(&Id) -> Vec<Id>,
 {
     detect_cycle_fn(all_ids, get_dependencies).is_some()
@@ -271,8 +328,8 @@ mod tests {
             self.id
         }
 
-        fn dependencies(&self) -> Vec<Self::Id> {
-            self.caused_by.clone()
+        fn dependencies(&self) -> impl Iterator<Item = Self::Id> + '_ {
+            self.caused_by.iter().copied()
         }
     }
 
@@ -318,4 +375,63 @@ mod tests {
         let cycle = detect_cycle(&errors);
         assert!(cycle.is_none());
     }
+
+    #[test]
+    fn test_iter_closure_borrows_dependencies() {
+        use alloc::string::{String, ToString};
+
+        // Adjacency owned elsewhere; the closure lends it out without cloning
+        let mut caused_by: HashMap<String, Vec<String>> = HashMap::new();
+        caused_by.insert("a".to_string(), vec!["b".to_string()]);
+        caused_by.insert("b".to_string(), vec!["c".to_string()]);
+        caused_by.insert("c".to_string(), vec!["a".to_string()]);
+        let ids: Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+
+        let borrowed = |id: &String| caused_by.get(id).map_or(&[][..], Vec::as_slice);
+        let owned = |id: &String| caused_by.get(id).cloned().unwrap_or_default();
+        assert_eq!(
+            detect_cycle_iter_fn(&ids, borrowed),
+            detect_cycle_fn(&ids, owned)
+        );
+
+        caused_by.insert("c".to_string(), vec![]);
+        let borrowed = |id: &String| caused_by.get(id).map_or(&[][..], Vec::as_slice);
+        assert!(detect_cycle_iter_fn(&ids, borrowed).is_none());
+    }
+
+    // Dependencies computed on the fly, borrowing `self`, rather than stored
+    struct RingNode {
+        id: usize,
+        size: usize,
+        closed: bool,
+    }
+
+    impl CycleDetectable for RingNode {
+        type Id = usize;
+
+        fn id(&self) -> Self::Id {
+            self.id
+        }
+
+        fn dependencies(&self) -> impl Iterator<Item = Self::Id> + '_ {
+            core::iter::once((self.id + 1) % self.size).filter(|&next| self.closed || next != 0)
+        }
+    }
+
+    #[test]
+    fn test_trait_dependencies_without_storage() {
+        let ring = |closed| -> Vec<RingNode> {
+            (0..4)
+                .map(|id| RingNode {
+                    id,
+                    size: 4,
+                    closed,
+                })
+                .collect()
+        };
+        // 0 → 1 → 2 → 3 → 0
+        assert!(detect_cycle(&ring(true)).is_some());
+        // The same chain without the edge back to 0
+        assert!(detect_cycle(&ring(false)).is_none());
+    }
 }
@@ -22,12 +22,20 @@
 //! ```
 
 use alloc::vec::Vec;
-use core::hash::Hash;
+
+use super::IdBound;
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeSet as HashSet;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
 
 /// Find all root nodes (nodes with no dependencies) in a graph.
 ///
 /// Root nodes are nodes that don't depend on anything else - they're the
-/// starting points in a dependency graph.
+/// starting points in a dependency graph. If your closure returns a node's
+/// dependents (children) rather than its dependencies, use
+/// [`find_sources_fn`], which finds the same nodes.
 ///
 /// # Arguments
 /// * `items` - All nodes in the graph
@@ -59,7 +67,7 @@ use core::hash::Hash;
 /// ```
 pub fn find_roots_fn<Id, F>(items: &[Id], get_dependencies: F) -> Vec<Id>
 where
-    Id: Clone + Eq + Hash,
+    Id: Clone + IdBound,
     F: Fn(&Id) -> Vec<Id>,
 {
     items
@@ -72,7 +80,8 @@ where
 /// Find all leaf nodes (nodes that nothing depends on).
 ///
 /// Leaf nodes are the opposite of roots - they're the endpoints in a dependency graph.
-/// No other nodes depend on them.
+/// No other nodes depend on them. With a closure that returns dependents
+/// (children), use [`find_sinks_fn`] instead.
 ///
 /// # Examples
 ///
@@ -92,25 +101,133 @@ where
 /// ```
 pub fn find_leaves_fn<Id, F>(items: &[Id], get_dependencies: F) -> Vec<Id>
 where
-    Id: Clone + Eq + Hash,
+    Id: Clone + IdBound,
     F: Fn(&Id) -> Vec<Id>,
 {
-    let mut leaves = Vec::new();
-
-    'outer: for candidate in items {
-        // Check if any other item depends on this candidate
-        for item in items {
-            let deps = get_dependencies(item);
-            if deps.contains(candidate) {
-                // Someone depends on this candidate, not a leaf
-                continue 'outer;
-            }
+    let depended_on = depended_on(items, &get_dependencies);
+    items
+        .iter()
+        .filter(|item| !depended_on.contains(*item))
+        .cloned()
+        .collect()
+}
+
+/// Find all source nodes from a closure that returns each node's children.
+///
+/// This is [`find_roots_fn`] for the forward direction: `get_children`
+/// returns the nodes that depend on (come after, are caused by) a node, and a
+/// source is a node that is nobody's child. `get_children` is called once per
+/// item.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::cycles::generic::roots::find_sources_fn;
+///
+/// // Edges point from a task to the tasks that need it
+/// let get_children = |task: &&str| match *task {
+///     "compile" => vec!["build", "test"],
+///     "build" => vec!["deploy"],
+///     "test" => vec!["deploy"],
+///     _ => vec![],
+/// };
+///
+/// let tasks = ["deploy", "test", "build", "compile"];
+/// assert_eq!(find_sources_fn(&tasks, get_children), vec!["compile"]);
+/// ```
+pub fn find_sources_fn<Id, F>(items: &[Id], get_children: F) -> Vec<Id>
+where
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> Vec<Id>,
+{
+    let has_parent = depended_on(items, &get_children);
+    items
+        .iter()
+        .filter(|item| !has_parent.contains(*item))
+        .cloned()
+        .collect()
+}
+
+/// Find all sink nodes from a closure that returns each node's children.
+///
+/// This is [`find_leaves_fn`] for the forward direction: a sink has no
+/// children, so nothing comes after it.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::cycles::generic::roots::find_sinks_fn;
+///
+/// let get_children = |task: &&str| match *task {
+///     "compile" => vec!["build"],
+///     "build" => vec!["deploy"],
+///     _ => vec![],
+/// };
+///
+/// let tasks = ["deploy", "build", "compile"];
+/// assert_eq!(find_sinks_fn(&tasks, get_children), vec!["deploy"]);
+/// ```
+pub fn find_sinks_fn<Id, F>(items: &[Id], get_children: F) -> Vec<Id>
+where
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> Vec<Id>,
+{
+    items
+        .iter()
+        .filter(|item| get_children(item).is_empty())
+        .cloned()
+        .collect()
+}
+
+/// Find all isolated nodes: nodes that are both roots and leaves.
+///
+/// An isolated node depends on nothing and nothing depends on it, so it has
+/// no edges at all. `get_dependencies` is called once per item.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::cycles::generic::roots::find_isolated_fn;
+///
+/// let get_deps = |task: &&str| match *task {
+///     "deploy" => vec!["build"],
+///     _ => vec![],
+/// };
+///
+/// let tasks = ["deploy", "build", "docs"];
+/// let isolated = find_isolated_fn(&tasks, get_deps);
+/// assert_eq!(isolated, vec!["docs"]);  // Unconnected to the rest
+/// ```
+pub fn find_isolated_fn<Id, F>(items: &[Id], get_dependencies: F) -> Vec<Id>
+where
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> Vec<Id>,
+{
+    let mut depended_on = HashSet::new();
+    let mut roots = Vec::new();
+    for item in items {
+        let deps = get_dependencies(item);
+        if deps.is_empty() {
+            roots.push(item);
         }
-        // No one depends on this candidate - it's a leaf!
-        leaves.push(candidate.clone());
+        depended_on.extend(deps);
     }
 
-    leaves
+    roots
+        .into_iter()
+        .filter(|item| !depended_on.contains(*item))
+        .cloned()
+        .collect()
+}
+
+/// Every id that some item depends on (or, given a children closure, every id
+/// with a parent), in one pass over `items`.
+fn depended_on<Id, F>(items: &[Id], get_dependencies: &F) -> HashSet<Id>
+where
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> Vec<Id>,
+{
+    items.iter().flat_map(get_dependencies).collect()
 }
 
 /// Trait for types that support root/leaf finding.
@@ -146,7 +263,7 @@ where
 /// ```
 pub trait RootFindable {
     /// The type of identifiers in the graph.
-    type Id: Clone + Eq + Hash;
+    type Id: Clone + IdBound;
 
     /// Get all node IDs in the graph.
     fn get_all_ids(&self) -> Vec<Self::Id>;
@@ -166,6 +283,12 @@ pub trait RootFindable {
         find_leaves_fn(&ids, |id| self.get_dependencies(id))
     }
 
+    /// Find all isolated nodes (no dependencies and no dependents).
+    fn find_isolated(&self) -> Vec<Self::Id> {
+        let ids = self.get_all_ids();
+        find_isolated_fn(&ids, |id| self.get_dependencies(id))
+    }
+
     /// Count the number of root nodes.
     fn root_count(&self) -> usize {
         self.find_roots().len()
@@ -176,6 +299,11 @@ pub trait RootFindable {
         self.find_leaves().len()
     }
 
+    /// Count the number of isolated nodes.
+    fn isolated_count(&self) -> usize {
+        self.find_isolated().len()
+    }
+
     /// Check if this is a single-rooted graph (has exactly one entry point).
     fn is_single_rooted(&self) -> bool {
         self.root_count() == 1
@@ -251,6 +379,21 @@ mod tests {
         assert!(leaves.contains(&3));
     }
 
+    #[test]
+    fn test_find_isolated() {
+        // 1 -> 2, 3 stands alone, 4 depends on a missing id, 5 depends on itself
+        let get_deps = |&id: &usize| match id {
+            2 => vec![1],
+            4 => vec![9],
+            5 => vec![5],
+            _ => vec![],
+        };
+
+        let items = [1, 2, 3, 4, 5];
+        assert_eq!(find_isolated_fn(&items, get_deps), vec![3]);
+        assert_eq!(find_leaves_fn(&items, get_deps), vec![2, 3, 4]);
+    }
+
     #[test]
     fn test_trait_based_root_finding() {
         use alloc::collections::BTreeMap;
@@ -283,6 +426,7 @@ mod tests {
         assert!(graph.is_tree_like());
         assert_eq!(graph.root_count(), 1);
         assert_eq!(graph.leaf_count(), 2);
+        assert_eq!(graph.isolated_count(), 0);
     }
 
     #[test]
@@ -300,4 +444,34 @@ mod tests {
         let roots = find_roots_fn(&items, get_deps);
         assert_eq!(roots.len(), 5);
     }
+
+    #[test]
+    fn test_children_direction_matches_dependencies() {
+        // The same diamond-plus-tail graph, expressed both ways
+        let get_deps = |&id: &usize| match id {
+            2 | 3 => vec![1],
+            4 => vec![2, 3],
+            5 => vec![4],
+            _ => vec![],
+        };
+        let get_children = |&id: &usize| match id {
+            1 => vec![2, 3],
+            2 | 3 => vec![4],
+            4 => vec![5],
+            _ => vec![],
+        };
+        let items = [5, 4, 3, 2, 1, 6];
+
+        // No dependencies = nobody's child; nothing depends on it = no children
+        assert_eq!(find_roots_fn(&items, get_deps), vec![1, 6]);
+        assert_eq!(find_sources_fn(&items, get_children), vec![1, 6]);
+        assert_eq!(find_leaves_fn(&items, get_deps), vec![5, 6]);
+        assert_eq!(find_sinks_fn(&items, get_children), vec![5, 6]);
+
+        // Reading one closure the other way round swaps the roles
+        assert_eq!(
+            find_roots_fn(&items, get_children),
+            find_leaves_fn(&items, get_deps)
+        );
+    }
 }
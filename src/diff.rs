@@ -0,0 +1,146 @@
+//! Structural differences between two DAGs.
+//!
+//! [`DAG::diff`](crate::graph::DAG::diff) reports which nodes and edges were added, removed, or
+//! relabeled between two versions of a graph. [`DAG::render_diff`](crate::graph::DAG::render_diff) draws the
+//! same changes on the combined graph.
+//!
+//! ```
+//! use ascii_dag::graph::DAG;
+//!
+//! let old = DAG::from_edges(&[(1, "App"), (2, "Log")], &[(1, 2)]);
+//! let new = DAG::from_edges(&[(1, "App"), (2, "Tracing")], &[(1, 2)]);
+//!
+//! let diff = old.diff(&new);
+//! assert_eq!(diff.relabeled, vec![(2, "Log", "Tracing")]);
+//! assert!(diff.added_edges.is_empty());
+//! ```
+
+use crate::graph::DAG;
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeSet as HashSet;
+
+/// Changes from one DAG to another, as returned by [`DAG::diff`].
+///
+/// Nodes and edges are listed in the insertion order of the graph they come
+/// from (`other` for additions, `self` for removals), without duplicates.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GraphDiff<'a> {
+    /// Node ids only present in the new graph
+    pub added_nodes: Vec<usize>,
+    /// Node ids only present in the old graph
+    pub removed_nodes: Vec<usize>,
+    /// Edges only present in the new graph
+    pub added_edges: Vec<(usize, usize)>,
+    /// Edges only present in the old graph
+    pub removed_edges: Vec<(usize, usize)>,
+    /// `(id, old_label, new_label)` for nodes in both graphs whose label changed.
+    /// Auto-created placeholders have the empty label.
+    pub relabeled: Vec<(usize, &'a str, &'a str)>,
+}
+
+impl GraphDiff<'_> {
+    /// Returns `true` if the two graphs were structurally identical.
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.relabeled.is_empty()
+    }
+}
+
+impl<'a> DAG<'a> {
+    /// Compare this graph (the old version) with `other` (the new version).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let old = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// let new = DAG::from_edges(&[(1, "A"), (3, "C")], &[(1, 3)]);
+    ///
+    /// let diff = old.diff(&new);
+    /// assert_eq!(diff.added_nodes, vec![3]);
+    /// assert_eq!(diff.removed_nodes, vec![2]);
+    /// assert_eq!(diff.added_edges, vec![(1, 3)]);
+    /// assert_eq!(diff.removed_edges, vec![(1, 2)]);
+    /// ```
+    pub fn diff(&self, other: &DAG<'a>) -> GraphDiff<'a> {
+        let mut diff = GraphDiff::default();
+
+        for &(id, new_label) in &other.nodes {
+            match self.node_index(id) {
+                None => diff.added_nodes.push(id),
+                Some(idx) => {
+                    let old_label = self.nodes[idx].1;
+                    if old_label != new_label {
+                        diff.relabeled.push((id, old_label, new_label));
+                    }
+                }
+            }
+        }
+        for &(id, _) in &self.nodes {
+            if other.node_index(id).is_none() {
+                diff.removed_nodes.push(id);
+            }
+        }
+
+        let old_edges: HashSet<(usize, usize)> = self.edges.iter().copied().collect();
+        let new_edges: HashSet<(usize, usize)> = other.edges.iter().copied().collect();
+        diff.added_edges = Self::edges_missing_from(&other.edges, &old_edges);
+        diff.removed_edges = Self::edges_missing_from(&self.edges, &new_edges);
+
+        diff
+    }
+
+    /// Edges of `edges` not in `present`, deduplicated, in their original order.
+    fn edges_missing_from(
+        edges: &[(usize, usize)],
+        present: &HashSet<(usize, usize)>,
+    ) -> Vec<(usize, usize)> {
+        let mut seen = HashSet::new();
+        edges
+            .iter()
+            .copied()
+            .filter(|edge| !present.contains(edge) && seen.insert(*edge))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::DAG;
+
+    #[test]
+    fn test_identical_graphs() {
+        let dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+        assert!(dag.diff(&dag.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_insertion_order_ignored() {
+        let a = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C")], &[(1, 2), (2, 3)]);
+        let b = DAG::from_edges(&[(3, "C"), (2, "B"), (1, "A")], &[(2, 3), (1, 2), (1, 2)]);
+        assert!(a.diff(&b).is_empty());
+    }
+
+    #[test]
+    fn test_promoted_placeholder_is_relabeled() {
+        let mut old = DAG::new();
+        old.add_node(1, "A");
+        old.add_edge(1, 2);
+
+        let mut new = old.clone();
+        new.add_node(2, "B");
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.relabeled, vec![(2, "", "B")]);
+        assert!(diff.added_nodes.is_empty() && diff.added_edges.is_empty());
+    }
+}
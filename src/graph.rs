@@ -23,7 +23,13 @@
 //! - For untrusted input, consider limiting maximum nodes/edges to prevent resource exhaustion
 //! - Maximum node ID: `usize::MAX` (up to 20 decimal digits)
 
-use alloc::{string::String, vec::Vec};
+mod data;
+
+pub use data::DagWithData;
+
+use crate::render::ascii::{RenderTarget, Theme};
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use core::fmt;
 
 #[cfg(feature = "std")]
 use std::collections::{HashMap, HashSet};
@@ -37,78 +43,1550 @@ pub enum RenderMode {
     /// Render chains vertically (takes more vertical space)
     Vertical,
 
-    /// Render chains horizontally when possible (compact, one-line for simple chains)
+    /// Render chains horizontally when possible (compact, one line per root)
     Horizontal,
 
     /// Auto-detect: horizontal for simple chains, vertical for complex graphs
     Auto,
+
+    /// Indented cause chain (like `anyhow`'s `Caused by:` output), one block per leaf.
+    ///
+    /// No box drawing is used, which makes this mode suitable for plain-text logs.
+    CauseChain,
+
+    /// Left-to-right columns by level (level 0 leftmost), nodes stacked within
+    /// each column and `→` connectors between them. Unlike `Horizontal`, this
+    /// handles branching graphs, e.g. build pipelines.
+    HorizontalLayered,
+
+    /// Like `Vertical`, but a level linked to the next purely 1-to-1 gets a
+    /// single arrow row instead of a line row plus an arrow row. Convergences
+    /// and divergences keep the full treatment; tall chains come out about a
+    /// third shorter.
+    Compact,
 }
 
 impl Default for RenderMode {
     fn default() -> Self {
         RenderMode::Auto
     }
-}
+}
+
+/// Horizontal alignment of levels in vertical renders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    /// Keep the layout's positions: nodes centered under parents and over children (default)
+    #[default]
+    Median,
+
+    /// Center each level against the widest level
+    Center,
+
+    /// Start every level at column 0 (stable diffs for generated docs)
+    Left,
+}
+
+/// What [`DAG::set_heat`] measures to grade nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeatMetric {
+    /// No heat markers (default)
+    #[default]
+    Off,
+
+    /// Number of descendants: how much breaks if the node does
+    Descendants,
+
+    /// Number of ancestors: how much the node is exposed to
+    Ancestors,
+}
+
+/// Which relation [`DAG::describe_with`] lists for each node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DescribeDirection {
+    /// `X depends on: A, B` (parents, default)
+    #[default]
+    Dependencies,
+
+    /// `X is depended on by: C, D` (children)
+    Dependents,
+}
+
+/// Per-node emphasis applied while rendering (e.g. by highlighted renders).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NodeMark {
+    /// Rendered with its usual brackets
+    Normal,
+    /// Rendered as `⟦Label⟧` (placeholders as `⟪ID⟫`)
+    Emphasized,
+    /// Rendered as `(Label)` (placeholders keep `⟨ID⟩`)
+    Dimmed,
+    /// Rendered as `+[Label]` (one column wider)
+    Added,
+    /// Rendered as `-[Label]` (one column wider)
+    Removed,
+    /// Rendered as `✓[Label]` (one column wider)
+    Done,
+    /// Rendered as ` (Label)`, padded to the same width as `Done`
+    Pending,
+}
+
+impl NodeMark {
+    /// Character written before the node's brackets, if any.
+    #[inline]
+    pub(crate) fn prefix(self) -> Option<char> {
+        match self {
+            NodeMark::Added => Some('+'),
+            NodeMark::Removed => Some('-'),
+            NodeMark::Done => Some('✓'),
+            NodeMark::Pending => Some(' '),
+            NodeMark::Normal | NodeMark::Emphasized | NodeMark::Dimmed => None,
+        }
+    }
+}
+
+/// Error returned by [`DAG::try_add_node`] for a label containing a control character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidLabel {
+    /// ID of the rejected node
+    pub id: usize,
+    /// The offending character
+    pub ch: char,
+    /// Character index of `ch` within the label
+    pub position: usize,
+}
+
+impl fmt::Display for InvalidLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "label of node {} contains control character {:?} at position {}",
+            self.id, self.ch, self.position
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidLabel {}
+
+/// Terminal columns taken by `ch`: 2 for East Asian wide and fullwidth
+/// characters and emoji, 0 for combining marks and zero-width characters,
+/// 1 otherwise. A small table rather than full Unicode data, so it works
+/// without `std`; every width the layout and padding compute goes through it.
+pub(crate) fn char_width(ch: char) -> usize {
+    match ch as u32 {
+        0x0300..=0x036F | 0x200B..=0x200F | 0x20D0..=0x20FF | 0xFE00..=0xFE0F | 0xFE20..=0xFE2F => {
+            0
+        }
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// Terminal columns taken by `s`, summing [`char_width`] over its chars.
+pub(crate) fn str_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// Iterator over the displayable characters of a label (see [`DAG::write_label`]).
+struct SanitizedLabel<'s> {
+    chars: core::str::Chars<'s>,
+}
+
+impl<'s> SanitizedLabel<'s> {
+    fn new(label: &'s str) -> Self {
+        Self {
+            chars: label.chars(),
+        }
+    }
+}
+
+impl Iterator for SanitizedLabel<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        loop {
+            match self.chars.next()? {
+                '\n' => return Some('␤'),
+                '\t' => return Some('␉'),
+                '\x1b' => {
+                    // CSI sequence (ESC [ params final) or a two-character escape
+                    if self.chars.next() == Some('[') {
+                        for ch in self.chars.by_ref() {
+                            if ('\x40'..='\x7e').contains(&ch) {
+                                break;
+                            }
+                        }
+                    }
+                }
+                ch if ch.is_control() => {}
+                ch => return Some(ch),
+            }
+        }
+    }
+}
+
+/// A Directed Acyclic Graph (DAG) with ASCII rendering capabilities.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::graph::DAG;
+///
+/// let mut dag = DAG::new();
+/// dag.add_node(1, "Start");
+/// dag.add_node(2, "End");
+/// dag.add_edge(1, 2);
+///
+/// let output = dag.render();
+/// assert!(output.contains("Start"));
+/// assert!(output.contains("End"));
+/// ```
+#[derive(Clone)]
+pub struct DAG<'a> {
+    pub(crate) nodes: Vec<(usize, &'a str)>,
+    pub(crate) edges: Vec<(usize, usize)>,
+    pub(crate) render_mode: RenderMode,
+    pub(crate) auto_created: HashSet<usize>, // Track auto-created nodes for visual distinction (O(1) lookups)
+    pub(crate) id_to_index: HashMap<usize, usize>, // Cache id→index mapping (O(1) lookups)
+    pub(crate) node_widths: Vec<usize>,      // Cached formatted widths
+    pub(crate) children: Vec<Vec<usize>>,    // Adjacency list: children[idx] = child indices
+    pub(crate) parents: Vec<Vec<usize>>,     // Adjacency list: parents[idx] = parent indices
+    pub(crate) node_marks: Vec<NodeMark>,    // Render-time emphasis by index (empty = all Normal)
+    pub(crate) show_degrees: bool,           // Append (in↓out↑) suffix to every node
+    pub(crate) theme: Theme,                 // Line and arrow glyphs used by the renderers
+    pub(crate) level_gap: usize,             // Extra blank rows after each connector block
+    pub(crate) max_render_depth: usize,      // Levels drawn by vertical renders (usize::MAX = all)
+    pub(crate) max_render_nodes: usize, // Nodes drawn before the rest is summarized (usize::MAX = all)
+    pub(crate) max_convergence_span: usize, // Widest single-row fan-in before staircasing
+    pub(crate) alignment: Alignment,    // Level alignment in vertical renders
+    pub(crate) compact_isolated: bool,  // Render edgeless nodes on shared `isolated:` lines
+    pub(crate) placeholder_brackets: (&'a str, &'a str), // Brackets around auto-created node IDs
+    pub(crate) ascii_placeholders: bool, // Draw default-style placeholders as `<id>`
+    pub(crate) node_brackets: HashMap<usize, (&'a str, &'a str)>, // Per-node label bracket overrides
+    pub(crate) groups: HashMap<usize, &'a str>, // Group name per node ID (tag + header row)
+    pub(crate) title: Option<&'a str>,          // Centered, underlined heading of every render
+    pub(crate) heat: HeatMetric,                // Metric graded by heat markers
+    pub(crate) heat_levels: Vec<u8>, // Render-time heat bucket by index (empty = not computed)
+    pub(crate) summary: bool,        // Append a summary of rendering decisions
+    pub(crate) upward_arrows: bool,  // Arrowheads point at parents instead of children
+    pub(crate) component_labels: bool, // `── Component k ──` above each subgraph block
+    pub(crate) level_separators: bool, // `┄┄┄` rule between levels in vertical renders
+    pub(crate) show_order: bool,     // Prefix labels with their topological position
+    pub(crate) order_numbers: Vec<usize>, // Render-time 1-based position by index (empty = not computed)
+    pub(crate) show_ids: bool,            // Prefix labels with their node ID, as in `[1:A]`
+    pub(crate) edge_channels: bool,       // Give every bent edge its own lane row
+    pub(crate) summary_node: Option<usize>, // ID of the `⟨… +N hidden⟩` node in a capped render
+}
+
+impl<'a> Default for DAG<'a> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            render_mode: RenderMode::default(),
+            auto_created: HashSet::new(),
+            id_to_index: HashMap::new(),
+            node_widths: Vec::new(),
+            children: Vec::new(),
+            parents: Vec::new(),
+            node_marks: Vec::new(),
+            show_degrees: false,
+            theme: Theme::default(),
+            level_gap: 0,
+            max_render_depth: usize::MAX,
+            max_render_nodes: usize::MAX,
+            max_convergence_span: usize::MAX,
+            alignment: Alignment::default(),
+            compact_isolated: true,
+            placeholder_brackets: ("⟨", "⟩"),
+            ascii_placeholders: false,
+            node_brackets: HashMap::new(),
+            groups: HashMap::new(),
+            title: None,
+            heat: HeatMetric::Off,
+            heat_levels: Vec::new(),
+            summary: false,
+            upward_arrows: false,
+            component_labels: false,
+            level_separators: false,
+            show_order: false,
+            order_numbers: Vec::new(),
+            show_ids: false,
+            edge_channels: false,
+            summary_node: None,
+        }
+    }
+}
+
+/// Prints the graph's structure without the derived caches: render mode,
+/// then nodes and edges with their counts, one per line in insertion order.
+///
+/// Labels are quoted and escaped; auto-created nodes are marked
+/// `(placeholder)`. The format is stable, so it can be used in snapshots.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::graph::DAG;
+///
+/// let dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2), (2, 3)]);
+/// assert_eq!(
+///     format!("{:?}", dag),
+///     "DAG {\n\
+///     \x20   mode: Auto\n\
+///     \x20   nodes (3):\n\
+///     \x20       1: \"A\"\n\
+///     \x20       2: \"B\"\n\
+///     \x20       3: \"\" (placeholder)\n\
+///     \x20   edges (2):\n\
+///     \x20       1 -> 2\n\
+///     \x20       2 -> 3\n\
+///     }"
+/// );
+/// ```
+impl fmt::Debug for DAG<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "DAG {{")?;
+        writeln!(f, "    mode: {:?}", self.render_mode)?;
+        writeln!(f, "    nodes ({}):", self.nodes.len())?;
+        for &(id, label) in &self.nodes {
+            write!(f, "        {}: {:?}", id, label)?;
+            if self.is_auto_created(id) {
+                write!(f, " (placeholder)")?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "    edges ({}):", self.edges.len())?;
+        for &(from, to) in &self.edges {
+            writeln!(f, "        {} -> {}", from, to)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Structural equality: the same nodes (ID, label, and whether the node is
+/// still an auto-created placeholder) and the same edges, in any order.
+///
+/// Edges are compared as a multiset, so parallel edges must match in number.
+/// Insertion order, promotion history, and display settings (render mode,
+/// brackets, ...) are ignored. An unlabeled placeholder never equals a node
+/// explicitly added with an empty label.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::graph::DAG;
+///
+/// let a = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+///
+/// let mut b = DAG::new();
+/// b.add_edge(1, 2);
+/// b.add_node(2, "B");
+/// b.add_node(1, "A");
+/// assert_eq!(a, b);
+///
+/// b.add_edge(1, 2);
+/// assert_ne!(a, b);
+/// ```
+impl PartialEq for DAG<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.nodes.len() != other.nodes.len() || self.edges.len() != other.edges.len() {
+            return false;
+        }
+
+        let node_key = |dag: &Self| {
+            let mut nodes: Vec<(usize, &str, bool)> = dag
+                .nodes
+                .iter()
+                .map(|&(id, label)| (id, label, dag.is_auto_created(id)))
+                .collect();
+            nodes.sort_unstable();
+            nodes
+        };
+        let edge_key = |dag: &Self| {
+            let mut edges = dag.edges.clone();
+            edges.sort_unstable();
+            edges
+        };
+
+        node_key(self) == node_key(other) && edge_key(self) == edge_key(other)
+    }
+}
+
+impl Eq for DAG<'_> {}
+
+impl<'a> DAG<'a> {
+    /// Create a new empty DAG.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    /// let dag = DAG::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a DAG from pre-defined nodes and edges (batch construction).
+    ///
+    /// This is more efficient than using the builder API for static graphs.
+    /// If an ID appears more than once in `nodes`, the first entry wins and
+    /// later ones are ignored (reported on stderr with the `warnings` feature).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(
+    ///     &[(1, "A"), (2, "B"), (3, "C")],
+    ///     &[(1, 2), (2, 3)]
+    /// );
+    /// ```
+    pub fn from_edges(nodes: &[(usize, &'a str)], edges: &[(usize, usize)]) -> Self {
+        let mut dag = Self {
+            nodes: Vec::with_capacity(nodes.len()),
+            ..Self::default()
+        };
+
+        // Build id_to_index map and widths cache, keeping the first of any duplicates
+        for &(id, label) in nodes {
+            if dag.id_to_index.contains_key(&id) {
+                #[cfg(feature = "warnings")]
+                {
+                    eprintln!(
+                        "[ascii-dag] Warning: Duplicate node {} ({:?}) in from_edges - ignored.",
+                        id, label
+                    );
+                }
+                continue;
+            }
+            dag.id_to_index.insert(id, dag.nodes.len());
+            dag.nodes.push((id, label));
+            let width = dag.compute_node_width(id, label);
+            dag.node_widths.push(width);
+        }
+
+        // Initialize adjacency lists
+        dag.children.resize(dag.nodes.len(), Vec::new());
+        dag.parents.resize(dag.nodes.len(), Vec::new());
+
+        // Add edges (may auto-create missing nodes)
+        for &(from, to) in edges {
+            dag.add_edge(from, to);
+        }
+
+        dag
+    }
+
+    /// Set the rendering mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::{DAG, RenderMode};
+    ///
+    /// let mut dag = DAG::new();
+    /// dag.set_render_mode(RenderMode::Horizontal);
+    /// ```
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        self.render_mode = mode;
+    }
+
+    /// Show each node's fan-in/fan-out counts next to it, e.g. `[Build](2↓3↑)`.
+    ///
+    /// The first number is the parent count (edges coming in), the second the
+    /// child count (edges going out).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// dag.set_show_degrees(true);
+    /// assert!(dag.render().contains("[A](0↓1↑)"));
+    /// ```
+    pub fn set_show_degrees(&mut self, on: bool) {
+        self.show_degrees = on;
+        self.refresh_node_widths();
+    }
+
+    /// Show each labeled node's ID before its label, e.g. `[1:Build]`.
+    ///
+    /// Handy for matching a rendered node back to the ID used in
+    /// [`add_edge`](Self::add_edge). Placeholders already render as `⟨id⟩`
+    /// and are left as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "A")], &[(1, 2)]);
+    /// dag.set_show_ids(true);
+    /// let output = dag.render();
+    /// assert!(output.contains("[1:A]"));
+    /// assert!(output.contains("⟨2⟩"));
+    /// ```
+    pub fn set_show_ids(&mut self, on: bool) {
+        self.show_ids = on;
+        self.refresh_node_widths();
+    }
+
+    /// Insert `extra_blank_rows` blank lines after each level's connector rows
+    /// in vertical renders (0 by default, which keeps the compact output).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::{DAG, RenderMode};
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// dag.set_render_mode(RenderMode::Vertical);
+    /// let compact = dag.render().lines().count();
+    ///
+    /// dag.set_level_gap(2);
+    /// assert_eq!(dag.render().lines().count(), compact + 2);
+    /// ```
+    pub fn set_level_gap(&mut self, extra_blank_rows: usize) {
+        self.level_gap = extra_blank_rows;
+    }
+
+    /// Draw a faint full-width rule (`┄┄┄`, see [`Theme::separator`]) between
+    /// one level's connector rows and the next level's nodes in vertical
+    /// renders (off by default).
+    ///
+    /// The rule comes after any [`level gap`](Self::set_level_gap) rows and
+    /// belongs to the level above it, so it counts toward
+    /// [`rendered_dimensions`](Self::rendered_dimensions) and pages break
+    /// after it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::{DAG, RenderMode};
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// dag.set_render_mode(RenderMode::Vertical);
+    /// dag.set_level_separators(true);
+    /// assert_eq!(dag.render(), "[A]\n │\n ↓\n┄┄┄\n[B]\n");
+    /// ```
+    ///
+    /// [`Theme::separator`]: crate::render::ascii::Theme::separator
+    pub fn set_level_separators(&mut self, on: bool) {
+        self.level_separators = on;
+    }
+
+    /// Route every bent edge on its own lane row in vertical renders (off by
+    /// default).
+    ///
+    /// Normally all of a band's edges share one horizontal row
+    /// (`┌──┬──┐`), which is compact but can leave it unclear which corner
+    /// belongs to which source when many edges meet. With channels on, each
+    /// edge that changes column gets its own right-angle elbow on a separate
+    /// row between the levels, ordered so elbows nest instead of crossing
+    /// where possible. Straight edges stay straight; bands that have no bent
+    /// edges look the same as before. This costs one row per overlapping
+    /// edge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::{DAG, RenderMode};
+    ///
+    /// let mut dag = DAG::from_edges(
+    ///     &[(1, "Root"), (2, "Left"), (3, "Right")],
+    ///     &[(1, 2), (1, 3)]
+    /// );
+    /// dag.set_render_mode(RenderMode::Vertical);
+    /// let shared = dag.render();
+    ///
+    /// dag.set_edge_channels(true);
+    /// let channeled = dag.render();
+    /// assert_eq!(channeled.lines().count(), shared.lines().count() + 1);
+    /// ```
+    pub fn set_edge_channels(&mut self, on: bool) {
+        self.edge_channels = on;
+    }
+
+    /// Number every node with its position in
+    /// [`topological_sort`](Self::topological_sort), as in `[#3 Build]`.
+    ///
+    /// Placeholders are numbered too (`⟨#2 7⟩`). The numbers are assigned
+    /// when rendering, so they follow later edits; cyclic graphs render the
+    /// usual cycle warning without numbers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(
+    ///     &[(1, "Fetch"), (2, "Compile"), (3, "Test"), (4, "Package")],
+    ///     &[(1, 2), (1, 3), (2, 4), (3, 4)]
+    /// );
+    /// dag.set_show_order(true);
+    ///
+    /// let output = dag.render();
+    /// assert!(output.contains("[#1 Fetch]"));
+    /// assert!(output.contains("[#2 Compile]"));
+    /// assert!(output.contains("[#3 Test]"));
+    /// assert!(output.contains("[#4 Package]"));
+    /// ```
+    pub fn set_show_order(&mut self, on: bool) {
+        self.show_order = on;
+    }
+
+    /// Draw only the first `depth` levels in vertical renders, followed by a
+    /// `… (N more levels)` line when deeper levels were cut off.
+    ///
+    /// Keeps previews of large graphs bounded. Disconnected subgraphs are each
+    /// cut at the same depth; subgraphs drawn as a one-line horizontal chain are
+    /// shown in full. At least one level is always drawn; `usize::MAX` (the
+    /// default) draws every level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::{DAG, RenderMode};
+    ///
+    /// let mut dag = DAG::from_edges(
+    ///     &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+    ///     &[(1, 2), (2, 3), (3, 4)]
+    /// );
+    /// dag.set_render_mode(RenderMode::Vertical);
+    /// dag.set_max_render_depth(2);
+    ///
+    /// let output = dag.render();
+    /// assert!(output.contains("[B]"));
+    /// assert!(!output.contains("[C]"));
+    /// assert!(output.ends_with("… (2 more levels)\n"));
+    /// ```
+    pub fn set_max_render_depth(&mut self, depth: usize) {
+        self.max_render_depth = depth.max(1);
+    }
+
+    /// Draw at most `n` nodes: when the graph is larger, only the `n` nodes
+    /// with the most descendants are rendered, plus one `⟨… +M hidden⟩`
+    /// summary node standing in for the rest.
+    ///
+    /// Every ancestor of a kept node has strictly more descendants, so the kept
+    /// set is closed upward and the summary is always a sink: kept nodes with
+    /// hidden children point to it. Ranking costs one traversal per node
+    /// (O(V·(V + E))) and only runs when the cap is exceeded. At least one node
+    /// is always kept; `usize::MAX` (the default) draws every node. Cyclic
+    /// graphs are rendered as cycles, uncapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(
+    ///     &[(1, "App"), (2, "Core"), (3, "Log"), (4, "Fmt")],
+    ///     &[(1, 2), (2, 3), (2, 4)]
+    /// );
+    /// dag.set_max_render_nodes(2);
+    ///
+    /// let output = dag.render();
+    /// assert!(output.contains("[App]") && output.contains("[Core]"));
+    /// assert!(output.contains("⟨… +2 hidden⟩"));
+    /// ```
+    pub fn set_max_render_nodes(&mut self, n: usize) {
+        self.max_render_nodes = n.max(1);
+    }
+
+    /// Route wide fan-ins through several rows: when the sources converging on
+    /// one node span more than `columns` columns, neighbouring sources are
+    /// merged step by step (a staircase) instead of with one long `└──┴──┘`.
+    ///
+    /// Each step joins neighbouring lines at most `columns` apart where it can,
+    /// and always at least two, so the number of lines shrinks every row.
+    /// `usize::MAX` (the default) always draws a single row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::{DAG, RenderMode};
+    ///
+    /// let nodes: Vec<(usize, &str)> = (1..=8).map(|i| (i, "Err")).chain([(9, "Fail")]).collect();
+    /// let edges: Vec<(usize, usize)> = (1..=8).map(|i| (i, 9)).collect();
+    /// let mut dag = DAG::from_edges(&nodes, &edges);
+    /// dag.set_render_mode(RenderMode::Vertical);
+    /// let single = dag.render().lines().count();
+    ///
+    /// dag.set_max_convergence_span(12);
+    /// assert!(dag.render().lines().count() > single);
+    /// ```
+    pub fn set_max_convergence_span(&mut self, columns: usize) {
+        self.max_convergence_span = columns;
+    }
+
+    /// Set how levels are aligned in vertical renders.
+    ///
+    /// [`Alignment::Median`] (the default) places each node under its parents,
+    /// so levels shift when the layout changes; [`Alignment::Center`] centers
+    /// each level against the widest one, and [`Alignment::Left`] keeps each
+    /// level anchored at column 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::{Alignment, DAG};
+    ///
+    /// let mut dag = DAG::from_edges(
+    ///     &[(1, "Top"), (2, "Left"), (3, "Right")],
+    ///     &[(1, 2), (1, 3)]
+    /// );
+    /// dag.set_alignment(Alignment::Left);
+    /// assert!(dag.render().starts_with("[Top]"));
+    /// ```
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment;
+    }
+
+    /// Collect nodes without any edges onto compact `isolated: [A] [B] ...` lines
+    /// after the connected subgraphs (on by default).
+    ///
+    /// Turn this off to render every isolated node as its own subgraph block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C")], &[(1, 2)]);
+    /// assert!(dag.render().ends_with("isolated: [C]\n"));
+    ///
+    /// dag.set_compact_isolated(false);
+    /// assert!(!dag.render().contains("isolated:"));
+    /// ```
+    pub fn set_compact_isolated(&mut self, on: bool) {
+        self.compact_isolated = on;
+    }
+
+    /// Head each disconnected subgraph with a `── Component k ──` line in
+    /// vertical renders of graphs with more than one component.
+    ///
+    /// `k` counts the headed blocks from 1, in the order of
+    /// [`connected_components`](Self::connected_components). Edgeless nodes
+    /// collected on the `isolated:` line get no header and no number, so the
+    /// headers always read 1, 2, 3, …
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(
+    ///     &[(1, "A"), (2, "B"), (3, "X"), (4, "Y")],
+    ///     &[(1, 2), (3, 4)]
+    /// );
+    /// dag.set_component_labels(true);
+    /// let output = dag.render();
+    /// assert!(output.starts_with("── Component 1 ──\n"));
+    /// assert!(output.contains("\n── Component 2 ──\n"));
+    /// ```
+    pub fn set_component_labels(&mut self, on: bool) {
+        self.component_labels = on;
+    }
+
+    /// Set the brackets drawn around auto-created placeholder nodes
+    /// (default `⟨` and `⟩`), e.g. `("<", ">")` for plain-ASCII terminals or
+    /// `("[?", "]")` to make missing labels stand out.
+    ///
+    /// Brackets may be several characters long; layout uses their actual width.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::new();
+    /// dag.add_node(1, "Known");
+    /// dag.add_edge(1, 2);
+    /// dag.set_placeholder_style("[?", "]");
+    /// assert_eq!(dag.render(), "[Known] → [?2]\n");
+    /// ```
+    pub fn set_placeholder_style(&mut self, open: &'a str, close: &'a str) {
+        self.placeholder_brackets = (open, close);
+        self.refresh_node_widths();
+    }
+
+    /// Draw placeholders as `<id>` instead of `⟨id⟩`, leaving every other
+    /// glyph alone.
+    ///
+    /// The angle brackets are the characters most often missing from
+    /// terminal fonts; this swaps just them, without changing the
+    /// [`Theme`]. A style set with
+    /// [`set_placeholder_style`](Self::set_placeholder_style) takes precedence
+    /// in either order, and turning this off leaves it in place. The summary
+    /// node of a capped render follows the same style.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::new();
+    /// dag.add_node(1, "Known");
+    /// dag.add_edge(1, 2);
+    /// dag.set_ascii_placeholders(true);
+    /// assert_eq!(dag.render(), "[Known] → <2>\n");
+    /// ```
+    pub fn set_ascii_placeholders(&mut self, on: bool) {
+        self.ascii_placeholders = on;
+        self.refresh_node_widths();
+    }
+
+    /// Brackets drawn around placeholders: the configured style, with the
+    /// default `⟨`/`⟩` swapped for `<`/`>` when ASCII placeholders are on.
+    pub(crate) fn placeholder_brackets(&self) -> (&'a str, &'a str) {
+        match self.placeholder_brackets {
+            ("⟨", "⟩") if self.ascii_placeholders => ("<", ">"),
+            style => style,
+        }
+    }
+
+    /// Draw a node's label with its own brackets instead of `[` and `]`,
+    /// e.g. `("((", "))")` for root causes or `("{", "}")` for external
+    /// dependencies.
+    ///
+    /// The override takes effect once the node has a label: auto-created
+    /// placeholders always use the placeholder style, so they can't be mistaken
+    /// for labeled nodes. Emphasized and dimmed renders also keep their own
+    /// brackets. Layout uses the override's actual width.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "Disk"), (2, "Crash")], &[(1, 2)]);
+    /// dag.set_node_brackets(1, "((", "))");
+    /// assert_eq!(dag.render(), "((Disk)) → [Crash]\n");
+    /// ```
+    pub fn set_node_brackets(&mut self, id: usize, open: &'a str, close: &'a str) {
+        self.node_brackets.insert(id, (open, close));
+        if let Some(idx) = self.node_index(id) {
+            self.refresh_node_width(idx);
+        }
+    }
+
+    /// Put nodes into a named group, e.g. by subsystem.
+    ///
+    /// Members are tagged with the group name (`frontend:[App]`), and vertical
+    /// renders draw a `── frontend ──` header row above the first level that
+    /// contains members, starting at the leftmost member on that level. Headers
+    /// of several groups on one row are shifted right so they don't overlap.
+    /// Other render modes show the tags only. A node belongs to at most one
+    /// group; an empty name removes the nodes from their group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "App"), (2, "Api")], &[(1, 2)]);
+    /// dag.set_group(&[1], "ui");
+    /// assert_eq!(dag.render(), "ui:[App] → [Api]\n");
+    /// ```
+    pub fn set_group(&mut self, ids: &[usize], name: &'a str) {
+        for &id in ids {
+            if name.is_empty() {
+                self.groups.remove(&id);
+            } else {
+                self.groups.insert(id, name);
+            }
+            if let Some(idx) = self.node_index(id) {
+                self.refresh_node_width(idx);
+            }
+        }
+    }
+
+    /// Grade every node by a metric and mark the hottest ones, so the riskiest
+    /// nodes to touch stand out.
+    ///
+    /// Counts are split into five buckets: nodes with none get no marker, the
+    /// rest get `*` to `****` by quarter of the largest count, and a legend
+    /// line after the graph maps markers to count ranges. Five buckets means
+    /// four marker widths, one more than a `*`/`**`/`***` scale, so the top
+    /// quarter stands apart. Nodes already styled by a highlighting or diff
+    /// render keep that style without a marker. Exact counts keep a bitset per
+    /// node, so memory grows quadratically with the node count; above 4096
+    /// nodes a linear estimate that counts shared descendants once per path is
+    /// used instead. Cyclic graphs render their usual warning.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::{DAG, HeatMetric};
+    ///
+    /// let mut dag = DAG::from_edges(
+    ///     &[(1, "Core"), (2, "Api"), (3, "Web"), (4, "Cli")],
+    ///     &[(1, 2), (2, 3), (2, 4)],
+    /// );
+    /// dag.set_heat(HeatMetric::Descendants);
+    ///
+    /// let output = dag.render();
+    /// assert!(output.contains("[Core]****"));
+    /// assert!(output.contains("[Api]***"));
+    /// assert!(output.contains("[Web]\n") || output.contains("[Web] "));
+    /// assert!(output.ends_with("heat (descendants): ** 1, *** 2, **** 3\n"));
+    /// ```
+    pub fn set_heat(&mut self, metric: HeatMetric) {
+        self.heat = metric;
+    }
+
+    /// Append a summary of what the rendering drew: the line glyphs used,
+    /// junction counts, anything left out by the depth limit, node cap, or
+    /// layout, and the number of placeholders.
+    ///
+    /// The same figures are available as a [`RenderReport`] from
+    /// [`render_with_report`](Self::render_with_report).
+    ///
+    /// [`RenderReport`]: crate::render::ascii::RenderReport
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// dag.add_edge(2, 3);
+    /// dag.set_summary(true);
+    /// assert!(dag.render().ends_with("placeholders: 1\n"));
+    /// ```
+    pub fn set_summary(&mut self, on: bool) {
+        self.summary = on;
+    }
+
+    /// Give the graph a title, rendered as the first line of every render
+    /// mode: centered over the drawing and underlined. Cycle reports and
+    /// stacked subgraphs come after it, and
+    /// [`rendered_dimensions`](Self::rendered_dimensions) counts it. A title
+    /// wider than the drawing starts at column 0 and widens the canvas.
+    ///
+    /// [`render_markdown`](Self::render_markdown) shows it as a bold line
+    /// above the code fence instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// dag.set_title("Deps");
+    /// assert_eq!(dag.render(), "  Deps\n  ────\n[A] → [B]\n");
+    /// assert!(dag.render_markdown().starts_with("**Deps**\n\n"));
+    /// ```
+    pub fn set_title(&mut self, title: &'a str) {
+        self.title = Some(title);
+    }
+
+    /// Create a DAG with a specific render mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::{DAG, RenderMode};
+    ///
+    /// let dag = DAG::with_mode(RenderMode::Horizontal);
+    /// ```
+    pub fn with_mode(mode: RenderMode) -> Self {
+        Self {
+            render_mode: mode,
+            ..Self::default()
+        }
+    }
+
+    /// Add a node to the DAG.
+    ///
+    /// If the node was previously auto-created by `add_edge`, this will promote it
+    /// by setting its label and removing the auto-created flag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::new();
+    /// dag.add_node(1, "MyNode");
+    /// ```
+    pub fn add_node(&mut self, id: usize, label: &'a str) {
+        // Check if node already exists (could be auto-created) - O(1) with HashMap
+        if let Some(&idx) = self.id_to_index.get(&id) {
+            // Promote auto-created node to explicit node
+            self.nodes[idx] = (id, label);
+            // Remove from auto_created set - O(1)
+            self.auto_created.remove(&id);
+            // Update cached width
+            let width = self.compute_node_width(id, label);
+            self.node_widths[idx] = width;
+        } else {
+            // Brand new node
+            let idx = self.nodes.len();
+            self.nodes.push((id, label));
+            self.id_to_index.insert(id, idx);
+            let width = self.compute_node_width(id, label);
+            self.node_widths.push(width);
+            // Extend adjacency lists
+            self.children.push(Vec::new());
+            self.parents.push(Vec::new());
+        }
+    }
+
+    /// Add a node, rejecting labels that contain control characters.
+    ///
+    /// [`add_node`](Self::add_node) accepts any label and neutralizes newlines,
+    /// tabs, and escape sequences when rendering. Use this instead when such a
+    /// label indicates bad input that should be reported rather than drawn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::new();
+    /// assert!(dag.try_add_node(1, "Fine").is_ok());
+    ///
+    /// let err = dag.try_add_node(2, "two\nlines").unwrap_err();
+    /// assert_eq!((err.id, err.ch, err.position), (2, '\n', 3));
+    /// assert!(!dag.render().contains("two"));
+    /// ```
+    pub fn try_add_node(&mut self, id: usize, label: &'a str) -> Result<(), InvalidLabel> {
+        if let Some((position, ch)) = label.chars().enumerate().find(|(_, ch)| ch.is_control()) {
+            return Err(InvalidLabel { id, ch, position });
+        }
+        self.add_node(id, label);
+        Ok(())
+    }
+
+    /// Add an edge from one node to another.
+    ///
+    /// If either node doesn't exist, it will be auto-created as a placeholder.
+    /// You can later call `add_node` to provide a label for auto-created nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::new();
+    /// dag.add_node(1, "A");
+    /// dag.add_node(2, "B");
+    /// dag.add_edge(1, 2);  // A -> B
+    /// ```
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        self.ensure_node_exists(from);
+        self.ensure_node_exists(to);
+        self.edges.push((from, to));
+
+        // Update adjacency lists (O(1) lookups)
+        if let (Some(&from_idx), Some(&to_idx)) =
+            (self.id_to_index.get(&from), self.id_to_index.get(&to))
+        {
+            self.children[from_idx].push(to_idx);
+            self.parents[to_idx].push(from_idx);
+
+            // Degree suffixes grow with the edge count
+            if self.show_degrees {
+                self.refresh_node_width(from_idx);
+                self.refresh_node_width(to_idx);
+            }
+        }
+    }
+
+    /// Reorder nodes by ID and edges by `(from, to)`, rebuilding the caches.
+    ///
+    /// Graphs that compare equal render identically once both are
+    /// canonicalized, whatever order they were built in. Display settings and
+    /// node marks are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut a = DAG::from_edges(&[(2, "B"), (1, "A"), (3, "C")], &[(1, 3), (1, 2)]);
+    /// let mut b = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C")], &[(1, 2), (1, 3)]);
+    /// assert_eq!(a, b);
+    ///
+    /// a.canonicalize();
+    /// b.canonicalize();
+    /// assert_eq!(a.render(), b.render());
+    /// ```
+    pub fn canonicalize(&mut self) {
+        let mut order: Vec<usize> = (0..self.nodes.len()).collect();
+        order.sort_unstable_by_key(|&idx| self.nodes[idx].0);
+
+        self.nodes = order.iter().map(|&idx| self.nodes[idx]).collect();
+        if !self.node_marks.is_empty() {
+            self.node_marks = order
+                .iter()
+                .map(|&idx| {
+                    self.node_marks
+                        .get(idx)
+                        .copied()
+                        .unwrap_or(NodeMark::Normal)
+                })
+                .collect();
+        }
+        self.edges.sort_unstable();
+        self.rebuild_caches();
+    }
+
+    /// Collapse a group of nodes into a single node.
+    ///
+    /// The nodes in `ids` are removed and `new_id` (labeled `new_label`) takes
+    /// the place of the first of them. Edges between the group and the rest of
+    /// the graph are rewired onto `new_id`, with the resulting parallel edges
+    /// merged; edges inside the group are dropped. IDs not in the graph are
+    /// ignored, and if `new_id` already names a node outside the group, that
+    /// node is merged in as well.
+    ///
+    /// Contraction can create a cycle: if the group reaches an outside node
+    /// that leads back into the group, the new node ends up on both sides of
+    /// it. This is allowed and reported by the usual cycle rendering and
+    /// [`validate`](Self::validate).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(
+    ///     &[(1, "main"), (2, "util/fs"), (3, "util/str"), (4, "io")],
+    ///     &[(1, 2), (1, 3), (2, 3), (2, 4), (3, 4)],
+    /// );
+    /// dag.contract(&[2, 3], 10, "util");
+    ///
+    /// assert!(dag.has_edge(1, 10));
+    /// assert!(dag.has_edge(10, 4));
+    /// assert_eq!(dag.descendants(1), vec![10, 4]);
+    /// ```
+    pub fn contract(&mut self, ids: &[usize], new_id: usize, new_label: &'a str) {
+        let mut group: HashSet<usize> = ids
+            .iter()
+            .copied()
+            .filter(|id| self.id_to_index.contains_key(id))
+            .collect();
+        let Some(slot) = self.nodes.iter().position(|(id, _)| group.contains(id)) else {
+            return;
+        };
+        group.insert(new_id);
+
+        let marks = core::mem::take(&mut self.node_marks);
+        let mut nodes = Vec::with_capacity(self.nodes.len());
+        let mut node_marks = Vec::new();
+        for (idx, &(id, label)) in self.nodes.iter().enumerate() {
+            let node = if idx == slot {
+                (new_id, new_label)
+            } else if group.contains(&id) {
+                continue;
+            } else {
+                (id, label)
+            };
+            nodes.push(node);
+            if !marks.is_empty() {
+                let mark = if idx == slot {
+                    NodeMark::Normal
+                } else {
+                    marks.get(idx).copied().unwrap_or(NodeMark::Normal)
+                };
+                node_marks.push(mark);
+            }
+        }
+        self.nodes = nodes;
+        self.node_marks = node_marks;
+
+        let mut rewired: HashSet<(usize, usize)> = HashSet::new();
+        let edges = core::mem::take(&mut self.edges);
+        for (from, to) in edges {
+            let (from_in, to_in) = (group.contains(&from), group.contains(&to));
+            match (from_in, to_in) {
+                (true, true) => {}
+                (false, false) => self.edges.push((from, to)),
+                _ => {
+                    let edge = if from_in {
+                        (new_id, to)
+                    } else {
+                        (from, new_id)
+                    };
+                    if rewired.insert(edge) {
+                        self.edges.push(edge);
+                    }
+                }
+            }
+        }
+
+        for id in &group {
+            self.auto_created.remove(id);
+            if *id != new_id {
+                self.node_brackets.remove(id);
+                self.groups.remove(id);
+            }
+        }
+        self.rebuild_caches();
+    }
+
+    /// A copy of the graph with every node ID passed through `f`.
+    ///
+    /// Labels, edges, placeholders, brackets, groups, and render options carry
+    /// over unchanged, so this is handy for shifting one graph's IDs out of
+    /// another's way before combining them.
+    ///
+    /// `f` must be injective over the graph's IDs. If two nodes map to the same
+    /// ID, nothing is built and that ID is returned as the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// let shifted = dag.map_ids(|id| id + 1000).unwrap();
+    ///
+    /// assert!(shifted.has_edge(1001, 1002));
+    /// assert_eq!(shifted.roots(), vec![1001]);
+    ///
+    /// // Not injective: both nodes land on 0
+    /// assert_eq!(dag.map_ids(|_| 0).unwrap_err(), 0);
+    /// ```
+    pub fn map_ids(&self, f: impl Fn(usize) -> usize) -> Result<DAG<'a>, usize> {
+        let mut seen = HashSet::new();
+        let mut nodes = Vec::with_capacity(self.nodes.len());
+        for &(id, label) in &self.nodes {
+            let new_id = f(id);
+            if !seen.insert(new_id) {
+                return Err(new_id);
+            }
+            nodes.push((new_id, label));
+        }
+
+        let mut mapped = self.clone();
+        mapped.nodes = nodes;
+        mapped.edges = self.edges.iter().map(|&(a, b)| (f(a), f(b))).collect();
+        mapped.auto_created = self.auto_created.iter().map(|&id| f(id)).collect();
+        mapped.node_brackets = self
+            .node_brackets
+            .iter()
+            .map(|(&id, &brackets)| (f(id), brackets))
+            .collect();
+        mapped.groups = self
+            .groups
+            .iter()
+            .map(|(&id, &group)| (f(id), group))
+            .collect();
+        mapped.rebuild_caches();
+        Ok(mapped)
+    }
+
+    /// Check whether the edge `from → to` exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// assert!(dag.has_edge(1, 2));
+    /// assert!(!dag.has_edge(2, 1));
+    /// ```
+    pub fn has_edge(&self, from: usize, to: usize) -> bool {
+        match (self.node_index(from), self.node_index(to)) {
+            (Some(from_idx), Some(to_idx)) => self.children[from_idx].contains(&to_idx),
+            _ => false,
+        }
+    }
+
+    /// Position of node `id` in insertion order, or `None` for unknown IDs.
+    ///
+    /// This is the index the internal algorithms work with and the row or
+    /// column of `id` in [`render_matrix`](Self::render_matrix). Indices are
+    /// stable while nodes are only added; [`canonicalize`](Self::canonicalize)
+    /// and [`contract`](Self::contract) renumber them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(&[(10, "A"), (20, "B"), (30, "C")], &[(10, 30)]);
+    /// assert_eq!(dag.index_of(30), Some(2));
+    /// assert_eq!(dag.id_at(2), Some(30));
+    /// assert_eq!(dag.index_of(40), None);
+    /// assert_eq!(dag.id_at(3), None);
+    /// ```
+    pub fn index_of(&self, id: usize) -> Option<usize> {
+        self.node_index(id)
+    }
+
+    /// ID of the node at `index` in insertion order, the inverse of
+    /// [`index_of`](Self::index_of). Returns `None` past the last node.
+    pub fn id_at(&self, index: usize) -> Option<usize> {
+        self.nodes.get(index).map(|&(id, _)| id)
+    }
+
+    /// IDs of nodes without parents, in insertion order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(
+    ///     &[
+    ///         (1, "ConfigMissing"),
+    ///         (2, "DBConnFail"),
+    ///         (3, "AuthFail"),
+    ///         (4, "InitError"),
+    ///         (5, "StartupFail"),
+    ///     ],
+    ///     &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)]
+    /// );
+    /// assert_eq!(dag.roots(), vec![1]);
+    /// assert!(dag.is_root(1));
+    /// assert!(!dag.is_root(4));
+    /// ```
+    pub fn roots(&self) -> Vec<usize> {
+        self.nodes
+            .iter()
+            .zip(&self.parents)
+            .filter(|(_, parents)| parents.is_empty())
+            .map(|(&(id, _), _)| id)
+            .collect()
+    }
+
+    /// IDs of nodes without children, in insertion order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(
+    ///     &[
+    ///         (1, "ConfigMissing"),
+    ///         (2, "DBConnFail"),
+    ///         (3, "AuthFail"),
+    ///         (4, "InitError"),
+    ///         (5, "StartupFail"),
+    ///     ],
+    ///     &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)]
+    /// );
+    /// assert_eq!(dag.leaves(), vec![5]);
+    /// assert!(dag.is_leaf(5));
+    /// assert!(!dag.is_leaf(1));
+    /// ```
+    pub fn leaves(&self) -> Vec<usize> {
+        self.nodes
+            .iter()
+            .zip(&self.children)
+            .filter(|(_, children)| children.is_empty())
+            .map(|(&(id, _), _)| id)
+            .collect()
+    }
+
+    /// Check whether `id` is a node without parents (`false` for unknown IDs).
+    pub fn is_root(&self, id: usize) -> bool {
+        self.node_index(id)
+            .is_some_and(|idx| self.parents[idx].is_empty())
+    }
+
+    /// Check whether `id` is a node without children (`false` for unknown IDs).
+    pub fn is_leaf(&self, id: usize) -> bool {
+        self.node_index(id)
+            .is_some_and(|idx| self.children[idx].is_empty())
+    }
+
+    /// Number of nodes without parents, without collecting their IDs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// // Two trees: 1 → 2, 1 → 3 and 4 → 5
+    /// let dag = DAG::from_edges(
+    ///     &[(1, "A"), (2, "B"), (3, "C"), (4, "D"), (5, "E")],
+    ///     &[(1, 2), (1, 3), (4, 5)]
+    /// );
+    /// assert_eq!(dag.root_count(), 2);
+    /// assert_eq!(dag.leaf_count(), 3);
+    /// assert!(!dag.is_single_rooted());
+    /// ```
+    pub fn root_count(&self) -> usize {
+        self.parents.iter().filter(|p| p.is_empty()).count()
+    }
+
+    /// Number of nodes without children, without collecting their IDs.
+    pub fn leaf_count(&self) -> usize {
+        self.children.iter().filter(|c| c.is_empty()).count()
+    }
+
+    /// Check whether exactly one node has no parents.
+    ///
+    /// Like `RootFindable::is_single_rooted`, but read from the cached
+    /// adjacency lists, so it needs no closure or `generic` feature.
+    /// A cycle with no way in has no root at all, so it is not single-rooted.
+    pub fn is_single_rooted(&self) -> bool {
+        self.root_count() == 1
+    }
+
+    /// IDs of every node reachable from `id` by following edges forward,
+    /// excluding `id` itself, nearest first (BFS order).
+    ///
+    /// Runs in O(V + E) over the cached adjacency lists and needs no `generic`
+    /// feature. Unknown IDs have no descendants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(
+    ///     &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+    ///     &[(1, 2), (1, 3), (2, 4), (3, 4)]
+    /// );
+    /// assert_eq!(dag.descendants(1), vec![2, 3, 4]);
+    /// assert!(dag.descendants(4).is_empty());
+    /// ```
+    pub fn descendants(&self, id: usize) -> Vec<usize> {
+        self.reachable_ids(id, false)
+    }
+
+    /// IDs of every node that can reach `id` (its transitive dependencies),
+    /// excluding `id` itself, nearest first (BFS order).
+    ///
+    /// Runs in O(V + E) over the cached adjacency lists and needs no `generic`
+    /// feature. Unknown IDs have no ancestors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(
+    ///     &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+    ///     &[(1, 2), (1, 3), (2, 4), (3, 4)]
+    /// );
+    /// assert_eq!(dag.ancestors(4), vec![2, 3, 1]);
+    /// assert!(dag.ancestors(1).is_empty());
+    /// ```
+    pub fn ancestors(&self, id: usize) -> Vec<usize> {
+        self.reachable_ids(id, true)
+    }
+
+    /// Everything affected if `id` changes: its [`descendants`](Self::descendants).
+    ///
+    /// The closure-free counterpart of
+    /// `layout::generic::impact::compute_descendants_fn`, reading edges as
+    /// "`from` is needed by `to`".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// // core is needed by lib-a and lib-b, which are needed by app
+    /// let dag = DAG::from_edges(
+    ///     &[(1, "core"), (2, "lib-a"), (3, "lib-b"), (4, "app")],
+    ///     &[(1, 2), (1, 3), (2, 4), (3, 4)]
+    /// );
+    /// assert_eq!(dag.impacted_by(1), vec![2, 3, 4]);
+    /// assert_eq!(dag.required_for(4), vec![2, 3, 1]);
+    /// assert_eq!(dag.blast_radius(2), (vec![1], vec![4]));
+    /// ```
+    pub fn impacted_by(&self, id: usize) -> Vec<usize> {
+        self.descendants(id)
+    }
 
-/// A Directed Acyclic Graph (DAG) with ASCII rendering capabilities.
-///
-/// # Examples
-///
-/// ```
-/// use ascii_dag::graph::DAG;
-///
-/// let mut dag = DAG::new();
-/// dag.add_node(1, "Start");
-/// dag.add_node(2, "End");
-/// dag.add_edge(1, 2);
-///
-/// let output = dag.render();
-/// assert!(output.contains("Start"));
-/// assert!(output.contains("End"));
-/// ```
-#[derive(Clone)]
-pub struct DAG<'a> {
-    pub(crate) nodes: Vec<(usize, &'a str)>,
-    pub(crate) edges: Vec<(usize, usize)>,
-    pub(crate) render_mode: RenderMode,
-    pub(crate) auto_created: HashSet<usize>, // Track auto-created nodes for visual distinction (O(1) lookups)
-    pub(crate) id_to_index: HashMap<usize, usize>, // Cache id→index mapping (O(1) lookups)
-    pub(crate) node_widths: Vec<usize>,      // Cached formatted widths
-    pub(crate) children: Vec<Vec<usize>>,    // Adjacency list: children[idx] = child indices
-    pub(crate) parents: Vec<Vec<usize>>,     // Adjacency list: parents[idx] = parent indices
-}
+    /// Everything `id` needs first: its [`ancestors`](Self::ancestors), the
+    /// closure-free counterpart of `compute_ancestors_fn`.
+    pub fn required_for(&self, id: usize) -> Vec<usize> {
+        self.ancestors(id)
+    }
 
-impl<'a> Default for DAG<'a> {
-    fn default() -> Self {
-        Self {
-            nodes: Vec::new(),
-            edges: Vec::new(),
-            render_mode: RenderMode::default(),
-            auto_created: HashSet::new(),
-            id_to_index: HashMap::new(),
-            node_widths: Vec::new(),
-            children: Vec::new(),
-            parents: Vec::new(),
-        }
+    /// `(required_for(id), impacted_by(id))`: what `id` depends on and what
+    /// depends on it, in the same order as `compute_blast_radius_fn`.
+    pub fn blast_radius(&self, id: usize) -> (Vec<usize>, Vec<usize>) {
+        (self.ancestors(id), self.descendants(id))
     }
-}
 
-impl<'a> DAG<'a> {
-    /// Create a new empty DAG.
+    /// A shortest path from `from` to `to` following edges forward, both
+    /// endpoints included.
+    ///
+    /// BFS over the cached adjacency lists with a predecessor map. Returns
+    /// `None` when `to` is unreachable or either ID is unknown; `from == to`
+    /// yields the one-node path.
     ///
     /// # Examples
     ///
     /// ```
     /// use ascii_dag::graph::DAG;
-    /// let dag = DAG::new();
+    ///
+    /// let dag = DAG::from_edges(
+    ///     &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+    ///     &[(1, 2), (1, 3), (2, 4), (3, 4)]
+    /// );
+    /// assert_eq!(dag.path_between(1, 4), Some(vec![1, 2, 4]));
+    /// assert_eq!(dag.path_between(4, 1), None);
     /// ```
-    pub fn new() -> Self {
-        Self::default()
+    pub fn path_between(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let from_idx = self.node_index(from)?;
+        let to_idx = self.node_index(to)?;
+
+        let mut predecessor: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut seen = vec![false; self.nodes.len()];
+        let mut queue = VecDeque::new();
+        seen[from_idx] = true;
+        queue.push_back(from_idx);
+
+        while let Some(idx) = queue.pop_front() {
+            if idx == to_idx {
+                // Walk the predecessor chain back to the start
+                let mut path = vec![self.nodes[idx].0];
+                let mut current = idx;
+                while let Some(prev) = predecessor[current] {
+                    path.push(self.nodes[prev].0);
+                    current = prev;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &child in &self.children[idx] {
+                if !seen[child] {
+                    seen[child] = true;
+                    predecessor[child] = Some(idx);
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        None
     }
 
-    /// Create a DAG from pre-defined nodes and edges (batch construction).
+    /// Node IDs of one longest path (the critical chain), root first.
     ///
-    /// This is more efficient than using the builder API for static graphs.
+    /// Dynamic programming over the topological order; among equally long
+    /// paths the one ending at the earliest-inserted node wins. Empty for empty
+    /// or cyclic graphs (a cycle has no longest path).
     ///
     /// # Examples
     ///
@@ -116,117 +1594,109 @@ impl<'a> DAG<'a> {
     /// use ascii_dag::graph::DAG;
     ///
     /// let dag = DAG::from_edges(
-    ///     &[(1, "A"), (2, "B"), (3, "C")],
-    ///     &[(1, 2), (2, 3)]
+    ///     &[(1, "Fetch"), (2, "Parse"), (3, "Lint"), (4, "Build")],
+    ///     &[(1, 2), (1, 3), (2, 4)]
     /// );
+    /// assert_eq!(dag.longest_path(), vec![1, 2, 4]);
     /// ```
-    pub fn from_edges(nodes: &[(usize, &'a str)], edges: &[(usize, usize)]) -> Self {
-        let mut dag = Self {
-            nodes: nodes.to_vec(),
-            edges: Vec::new(),
-            render_mode: RenderMode::default(),
-            auto_created: HashSet::new(),
-            id_to_index: HashMap::new(),
-            node_widths: Vec::new(),
-            children: Vec::new(),
-            parents: Vec::new(),
+    pub fn longest_path(&self) -> Vec<usize> {
+        let Some(order) = self.topological_order() else {
+            return Vec::new();
         };
 
-        // Build id_to_index map and widths cache
-        for (idx, &(id, label)) in dag.nodes.iter().enumerate() {
-            dag.id_to_index.insert(id, idx);
-            let width = dag.compute_node_width(id, label);
-            dag.node_widths.push(width);
+        // length[idx]: nodes on the longest path ending at idx
+        let mut length = vec![1usize; self.nodes.len()];
+        let mut predecessor: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        for &idx in &order {
+            for &child in &self.children[idx] {
+                if length[idx] + 1 > length[child] {
+                    length[child] = length[idx] + 1;
+                    predecessor[child] = Some(idx);
+                }
+            }
         }
 
-        // Initialize adjacency lists
-        dag.children.resize(dag.nodes.len(), Vec::new());
-        dag.parents.resize(dag.nodes.len(), Vec::new());
-
-        // Add edges (may auto-create missing nodes)
-        for &(from, to) in edges {
-            dag.add_edge(from, to);
+        let Some(mut end) = (0..self.nodes.len()).rev().max_by_key(|&idx| length[idx]) else {
+            return Vec::new();
+        };
+        let mut path = vec![self.nodes[end].0];
+        while let Some(prev) = predecessor[end] {
+            path.push(self.nodes[prev].0);
+            end = prev;
         }
-
-        dag
+        path.reverse();
+        path
     }
 
-    /// Set the rendering mode.
+    /// Number of levels in the layout (longest path length in nodes).
+    ///
+    /// 0 for empty or cyclic graphs, matching [`longest_path`](Self::longest_path).
     ///
     /// # Examples
     ///
     /// ```
-    /// use ascii_dag::graph::{DAG, RenderMode};
+    /// use ascii_dag::graph::DAG;
     ///
-    /// let mut dag = DAG::new();
-    /// dag.set_render_mode(RenderMode::Horizontal);
+    /// let dag = DAG::from_edges(
+    ///     &[(1, "A"), (2, "B"), (3, "C")],
+    ///     &[(1, 2), (2, 3), (1, 3)]
+    /// );
+    /// assert_eq!(dag.depth(), 3);
     /// ```
-    pub fn set_render_mode(&mut self, mode: RenderMode) {
-        self.render_mode = mode;
+    pub fn depth(&self) -> usize {
+        self.longest_path().len()
     }
 
-    /// Create a DAG with a specific render mode.
+    /// Node IDs in dependency-first order (every node after all of its parents).
+    ///
+    /// Runs Kahn's algorithm over the cached adjacency lists; ties are broken by
+    /// insertion order, so the result is deterministic. Returns `Err` with a
+    /// cycle path if the graph is not acyclic.
     ///
     /// # Examples
     ///
     /// ```
-    /// use ascii_dag::graph::{DAG, RenderMode};
+    /// use ascii_dag::graph::DAG;
     ///
-    /// let dag = DAG::with_mode(RenderMode::Horizontal);
+    /// let dag = DAG::from_edges(
+    ///     &[(3, "Link"), (1, "Parse"), (2, "Compile")],
+    ///     &[(1, 2), (2, 3)]
+    /// );
+    /// assert_eq!(dag.topological_sort(), Ok(vec![1, 2, 3]));
+    ///
+    /// let mut cyclic = DAG::new();
+    /// cyclic.add_edge(1, 2);
+    /// cyclic.add_edge(2, 1);
+    /// assert!(cyclic.topological_sort().is_err());
     /// ```
-    pub fn with_mode(mode: RenderMode) -> Self {
-        Self {
-            nodes: Vec::new(),
-            edges: Vec::new(),
-            render_mode: mode,
-            auto_created: HashSet::new(),
-            id_to_index: HashMap::new(),
-            node_widths: Vec::new(),
-            children: Vec::new(),
-            parents: Vec::new(),
+    pub fn topological_sort(&self) -> Result<Vec<usize>, Vec<usize>> {
+        match self.topological_order() {
+            Some(order) => Ok(order.into_iter().map(|idx| self.nodes[idx].0).collect()),
+            None => Err(self.find_cycle_path().unwrap_or_default()),
         }
     }
 
-    /// Add a node to the DAG.
-    ///
-    /// If the node was previously auto-created by `add_edge`, this will promote it
-    /// by setting its label and removing the auto-created flag.
+    /// Like [`topological_sort`](Self::topological_sort), but returns
+    /// `(id, label)` pairs (placeholders have an empty label).
     ///
     /// # Examples
     ///
     /// ```
     /// use ascii_dag::graph::DAG;
     ///
-    /// let mut dag = DAG::new();
-    /// dag.add_node(1, "MyNode");
+    /// let dag = DAG::from_edges(&[(2, "Build"), (1, "Fetch")], &[(1, 2)]);
+    /// assert_eq!(dag.topological_labels(), Ok(vec![(1, "Fetch"), (2, "Build")]));
     /// ```
-    pub fn add_node(&mut self, id: usize, label: &'a str) {
-        // Check if node already exists (could be auto-created) - O(1) with HashMap
-        if let Some(&idx) = self.id_to_index.get(&id) {
-            // Promote auto-created node to explicit node
-            self.nodes[idx] = (id, label);
-            // Remove from auto_created set - O(1)
-            self.auto_created.remove(&id);
-            // Update cached width
-            let width = self.compute_node_width(id, label);
-            self.node_widths[idx] = width;
-        } else {
-            // Brand new node
-            let idx = self.nodes.len();
-            self.nodes.push((id, label));
-            self.id_to_index.insert(id, idx);
-            let width = self.compute_node_width(id, label);
-            self.node_widths.push(width);
-            // Extend adjacency lists
-            self.children.push(Vec::new());
-            self.parents.push(Vec::new());
+    pub fn topological_labels(&self) -> Result<Vec<(usize, &'a str)>, Vec<usize>> {
+        match self.topological_order() {
+            Some(order) => Ok(order.into_iter().map(|idx| self.nodes[idx]).collect()),
+            None => Err(self.find_cycle_path().unwrap_or_default()),
         }
     }
 
-    /// Add an edge from one node to another.
+    /// Reserve capacity for at least `additional` more nodes.
     ///
-    /// If either node doesn't exist, it will be auto-created as a placeholder.
-    /// You can later call `add_node` to provide a label for auto-created nodes.
+    /// Avoids reallocation when growing an existing graph in bulk.
     ///
     /// # Examples
     ///
@@ -234,28 +1704,41 @@ impl<'a> DAG<'a> {
     /// use ascii_dag::graph::DAG;
     ///
     /// let mut dag = DAG::new();
-    /// dag.add_node(1, "A");
-    /// dag.add_node(2, "B");
-    /// dag.add_edge(1, 2);  // A -> B
+    /// dag.reserve_nodes(100);
+    /// for id in 0..100 {
+    ///     dag.add_node(id, "N");
+    /// }
     /// ```
-    pub fn add_edge(&mut self, from: usize, to: usize) {
-        self.ensure_node_exists(from);
-        self.ensure_node_exists(to);
-        self.edges.push((from, to));
+    pub fn reserve_nodes(&mut self, additional: usize) {
+        self.nodes.reserve(additional);
+        self.node_widths.reserve(additional);
+        self.children.reserve(additional);
+        self.parents.reserve(additional);
+        // BTreeMap (no_std) has no capacity to reserve
+        #[cfg(feature = "std")]
+        self.id_to_index.reserve(additional);
+    }
 
-        // Update adjacency lists (O(1) lookups)
-        if let (Some(&from_idx), Some(&to_idx)) =
-            (self.id_to_index.get(&from), self.id_to_index.get(&to))
-        {
-            self.children[from_idx].push(to_idx);
-            self.parents[to_idx].push(from_idx);
-        }
+    /// Reserve capacity for at least `additional` more edges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C")], &[]);
+    /// dag.reserve_edges(2);
+    /// dag.add_edge(1, 2);
+    /// dag.add_edge(2, 3);
+    /// ```
+    pub fn reserve_edges(&mut self, additional: usize) {
+        self.edges.reserve(additional);
     }
 
     /// Ensure a node exists, auto-creating if missing.
     /// Auto-created nodes will be visually distinct (rendered with ⟨⟩ instead of [])
     /// until explicitly defined with add_node.
-    fn ensure_node_exists(&mut self, id: usize) {
+    pub(crate) fn ensure_node_exists(&mut self, id: usize) {
         // O(1) lookup with HashMap
         if !self.id_to_index.contains_key(&id) {
             #[cfg(feature = "warnings")]
@@ -288,12 +1771,15 @@ impl<'a> DAG<'a> {
     /// Write an unsigned integer to a string buffer without allocation.
     /// This avoids format! bloat in no_std builds.
     #[inline]
-    pub(crate) fn write_usize(buf: &mut String, mut n: usize) {
+    pub(crate) fn write_usize(buf: &mut impl RenderTarget, mut n: usize) {
+        // Decimal digits of usize::MAX, whatever the pointer width
+        const MAX_DIGITS: usize = usize::MAX.ilog10() as usize + 1;
+
         if n == 0 {
             buf.push('0');
             return;
         }
-        let mut digits = [0u8; 20]; // Max digits for u64
+        let mut digits = [0u8; MAX_DIGITS];
         let mut i = 0;
         while n > 0 {
             digits[i] = (n % 10) as u8 + b'0';
@@ -309,7 +1795,7 @@ impl<'a> DAG<'a> {
 
     /// Count digits in a number (for width calculation)
     #[inline]
-    fn count_digits(mut n: usize) -> usize {
+    pub(crate) fn count_digits(mut n: usize) -> usize {
         if n == 0 {
             return 1;
         }
@@ -323,27 +1809,295 @@ impl<'a> DAG<'a> {
 
     /// Compute the formatted width of a node
     pub(crate) fn compute_node_width(&self, id: usize, label: &str) -> usize {
-        if label.is_empty() || self.is_auto_created(id) {
-            // ⟨ID⟩ format
-            2 + Self::count_digits(id) // ⟨ + digits + ⟩
+        let (open, close) = self.brackets(id);
+        let text = if self.is_auto_created(id) {
+            Self::count_digits(id) // ⟨ID⟩ format
+        } else {
+            Self::label_width(label) // [Label] format
+        };
+        let base = str_width(open)
+            + text
+            + str_width(close)
+            + usize::from(self.node_mark(id).prefix().is_some())
+            + self.groups.get(&id).map_or(0, |group| str_width(group) + 1);
+
+        let base = base
+            + usize::from(self.heat_level(id))
+            + self
+                .order_number(id)
+                .map_or(0, |n| Self::count_digits(n) + 2) // "#n "
+            + if self.shows_id(id) {
+                Self::count_digits(id) + 1 // "id:"
+            } else {
+                0
+            };
+        if self.show_degrees {
+            let (fan_in, fan_out) = self.degrees(id);
+            // ( + in + ↓ + out + ↑ + )
+            base + 4 + Self::count_digits(fan_in) + Self::count_digits(fan_out)
+        } else {
+            base
+        }
+    }
+
+    /// Recompute the cached width of one node.
+    pub(crate) fn refresh_node_width(&mut self, idx: usize) {
+        let (id, label) = self.nodes[idx];
+        self.node_widths[idx] = self.compute_node_width(id, label);
+    }
+
+    /// Rebuild the ID index, adjacency lists, and widths from `nodes` and `edges`.
+    pub(crate) fn rebuild_caches(&mut self) {
+        self.id_to_index.clear();
+        for (idx, &(id, _)) in self.nodes.iter().enumerate() {
+            self.id_to_index.insert(id, idx);
+        }
+        self.children = vec![Vec::new(); self.nodes.len()];
+        self.parents = vec![Vec::new(); self.nodes.len()];
+        for &(from, to) in &self.edges {
+            if let (Some(&from_idx), Some(&to_idx)) =
+                (self.id_to_index.get(&from), self.id_to_index.get(&to))
+            {
+                self.children[from_idx].push(to_idx);
+                self.parents[to_idx].push(from_idx);
+            }
+        }
+        self.node_widths = vec![0; self.nodes.len()];
+        self.refresh_node_widths();
+    }
+
+    /// Recompute all cached widths (after a formatting option changes).
+    pub(crate) fn refresh_node_widths(&mut self) {
+        for idx in 0..self.nodes.len() {
+            self.refresh_node_width(idx);
+        }
+    }
+
+    /// Get (parent count, child count) for a node. Safe during node insertion,
+    /// before the adjacency lists have been extended.
+    #[inline]
+    fn degrees(&self, id: usize) -> (usize, usize) {
+        match self.node_index(id) {
+            Some(idx) => (
+                self.parents.get(idx).map_or(0, Vec::len),
+                self.children.get(idx).map_or(0, Vec::len),
+            ),
+            None => (0, 0),
+        }
+    }
+
+    /// Get the render-time mark for a node (Normal unless marks are set).
+    #[inline]
+    pub(crate) fn node_mark(&self, id: usize) -> NodeMark {
+        self.node_index(id)
+            .and_then(|idx| self.node_marks.get(idx).copied())
+            .unwrap_or(NodeMark::Normal)
+    }
+
+    /// Get the render-time heat bucket for a node (0 unless heat is being rendered).
+    #[inline]
+    pub(crate) fn heat_level(&self, id: usize) -> u8 {
+        if self.heat_levels.is_empty() {
+            return 0;
+        }
+        self.node_index(id)
+            .and_then(|idx| self.heat_levels.get(idx).copied())
+            .unwrap_or(0)
+    }
+
+    /// Whether `id` is written before the label (`[id:label]`). Placeholders
+    /// already show their ID, and the summary node of a capped render has no
+    /// ID of its own.
+    #[inline]
+    pub(crate) fn shows_id(&self, id: usize) -> bool {
+        self.show_ids && !self.is_auto_created(id) && self.summary_node != Some(id)
+    }
+
+    /// Render-time topological position of a node, if numbering is active.
+    #[inline]
+    pub(crate) fn order_number(&self, id: usize) -> Option<usize> {
+        if self.order_numbers.is_empty() {
+            return None;
+        }
+        self.node_index(id)
+            .and_then(|idx| self.order_numbers.get(idx).copied())
+    }
+
+    /// Write a label with control characters made harmless: `\n` becomes `␤`,
+    /// `\t` becomes `␉`, ANSI escape sequences and other control characters are
+    /// dropped. Keeps every label on one row and its width predictable.
+    pub(crate) fn write_label(output: &mut impl RenderTarget, label: &str) {
+        if label.contains(char::is_control) {
+            for ch in SanitizedLabel::new(label) {
+                output.push(ch);
+            }
+        } else {
+            output.push_str(label);
+        }
+    }
+
+    /// Rendered width of a label, after sanitizing (see [`write_label`](Self::write_label)).
+    pub(crate) fn label_width(label: &str) -> usize {
+        if label.contains(char::is_control) {
+            SanitizedLabel::new(label).map(char_width).sum()
+        } else {
+            str_width(label)
+        }
+    }
+
+    /// Opening and closing brackets for a node, given its current mark.
+    /// Shared by `write_node` and `compute_node_width` so widths always match.
+    fn brackets(&self, id: usize) -> (&'a str, &'a str) {
+        let mark = self.node_mark(id);
+        if self.is_auto_created(id) {
+            match mark {
+                NodeMark::Emphasized => ("⟪", "⟫"),
+                _ => self.placeholder_brackets(),
+            }
         } else {
-            // [Label] format
-            2 + label.chars().count() // [ + label + ]
+            match mark {
+                NodeMark::Emphasized => ("⟦", "⟧"),
+                NodeMark::Dimmed | NodeMark::Pending => ("(", ")"),
+                _ => self.node_brackets.get(&id).copied().unwrap_or(("[", "]")),
+            }
         }
     }
 
     /// Write a formatted node directly to output buffer (avoids intermediate String allocation)
     #[inline]
-    pub(crate) fn write_node(&self, output: &mut String, id: usize, label: &str) {
-        if label.is_empty() || self.is_auto_created(id) {
-            output.push('⟨');
+    pub(crate) fn write_node(&self, output: &mut impl RenderTarget, id: usize, label: &str) {
+        output.begin_node(id);
+        if let Some(group) = self.groups.get(&id) {
+            output.push_str(group);
+            output.push(':');
+        }
+        let mark = self.node_mark(id);
+        if let Some(prefix) = mark.prefix() {
+            output.push(prefix);
+        }
+        let (open, close) = self.brackets(id);
+        output.push_str(open);
+        if let Some(n) = self.order_number(id) {
+            output.push('#');
+            Self::write_usize(output, n);
+            output.push(' ');
+        }
+        if self.shows_id(id) {
+            Self::write_usize(output, id);
+            output.push(':');
+        }
+        if self.is_auto_created(id) {
             Self::write_usize(output, id);
-            output.push('⟩');
         } else {
-            output.push('[');
-            output.push_str(label);
-            output.push(']');
+            Self::write_label(output, label);
+        }
+        output.push_str(close);
+
+        if self.show_degrees {
+            let (fan_in, fan_out) = self.degrees(id);
+            output.push('(');
+            Self::write_usize(output, fan_in);
+            output.push('↓');
+            Self::write_usize(output, fan_out);
+            output.push_str("↑)");
+        }
+        for _ in 0..self.heat_level(id) {
+            output.push('*');
+        }
+        output.end_node();
+    }
+
+    /// Mark every node reachable from `start_idx` (following parents when `upward`,
+    /// children otherwise), including `start_idx` itself. BFS over cached adjacency.
+    pub(crate) fn reachable_mask(&self, start_idx: usize, upward: bool) -> Vec<bool> {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut queue = VecDeque::new();
+        seen[start_idx] = true;
+        queue.push_back(start_idx);
+
+        while let Some(idx) = queue.pop_front() {
+            let next = if upward {
+                &self.parents[idx]
+            } else {
+                &self.children[idx]
+            };
+            for &n in next {
+                if !seen[n] {
+                    seen[n] = true;
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        seen
+    }
+
+    /// IDs reachable from `id` (following parents when `upward`, children
+    /// otherwise), excluding `id`, in BFS order. Empty for unknown IDs.
+    fn reachable_ids(&self, id: usize, upward: bool) -> Vec<usize> {
+        let Some(start_idx) = self.node_index(id) else {
+            return Vec::new();
+        };
+
+        let mut seen = vec![false; self.nodes.len()];
+        let mut queue = VecDeque::new();
+        let mut found = Vec::new();
+        seen[start_idx] = true;
+        queue.push_back(start_idx);
+
+        while let Some(idx) = queue.pop_front() {
+            let next = if upward {
+                &self.parents[idx]
+            } else {
+                &self.children[idx]
+            };
+            for &n in next {
+                if !seen[n] {
+                    seen[n] = true;
+                    found.push(self.nodes[n].0);
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        found
+    }
+
+    /// Node indices in topological order (Kahn's algorithm, ties broken by
+    /// insertion order), or `None` if the graph has a cycle.
+    pub(crate) fn topological_order(&self) -> Option<Vec<usize>> {
+        let mut in_degree: Vec<usize> = self.parents.iter().map(Vec::len).collect();
+        let mut queue: VecDeque<usize> = (0..self.nodes.len())
+            .filter(|&idx| in_degree[idx] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+
+        while let Some(idx) = queue.pop_front() {
+            order.push(idx);
+            for &child in &self.children[idx] {
+                in_degree[child] -= 1;
+                if in_degree[child] == 0 {
+                    queue.push_back(child);
+                }
+            }
         }
+
+        (order.len() == self.nodes.len()).then_some(order)
+    }
+
+    /// Number of parallel `from_idx → to_idx` edges (duplicate edges are kept).
+    pub(crate) fn edge_multiplicity(&self, from_idx: usize, to_idx: usize) -> usize {
+        self.children[from_idx]
+            .iter()
+            .filter(|&&child| child == to_idx)
+            .count()
+    }
+
+    /// Whether a node has at most one distinct parent and one distinct child
+    /// (parallel edges to the same neighbour are not branching).
+    pub(crate) fn is_chain_link(&self, idx: usize) -> bool {
+        let single = |neighbours: &[usize]| neighbours.iter().all(|&n| n == neighbours[0]);
+        single(&self.parents[idx]) && single(&self.children[idx])
     }
 
     /// Get children of a node (returns IDs, not indices).
@@ -409,6 +2163,10 @@ impl<'a> DAG<'a> {
     /// Estimate the buffer size needed for rendering.
     ///
     /// Use this to pre-allocate a buffer for [`render_to`](Self::render_to).
+    /// The estimate sizes the canvas from the cached node widths per level
+    /// (one O(V+E) pass, no layout): each level is a node row plus up to three
+    /// connector rows as wide as the widest level, plus room for multi-byte
+    /// line glyphs on every edge.
     ///
     /// # Examples
     ///
@@ -425,7 +2183,36 @@ impl<'a> DAG<'a> {
     /// dag.render_to(&mut buffer);
     /// ```
     pub fn estimate_size(&self) -> usize {
-        // Rough estimate: nodes * avg_label_size + edges * connection_chars + box
-        self.nodes.len() * 25 + self.edges.len() * 15 + 200
+        // Longest-path levels via Kahn's algorithm, summing widths per level
+        let n = self.nodes.len();
+        let mut in_degree: Vec<usize> = self.parents.iter().map(Vec::len).collect();
+        let mut level = vec![0usize; n];
+        let mut level_widths: Vec<usize> = Vec::new();
+        let mut stack: Vec<usize> = (0..n).filter(|&idx| in_degree[idx] == 0).collect();
+        let mut ordered = 0;
+
+        while let Some(idx) = stack.pop() {
+            ordered += 1;
+            if level_widths.len() <= level[idx] {
+                level_widths.resize(level[idx] + 1, 0);
+            }
+            level_widths[level[idx]] += self.get_node_width(idx) + 3;
+            for &child in &self.children[idx] {
+                level[child] = level[child].max(level[idx] + 1);
+                in_degree[child] -= 1;
+                if in_degree[child] == 0 {
+                    stack.push(child);
+                }
+            }
+        }
+
+        let glyphs = self.edges.len() * 12; // Arrows and lines take 3 bytes each
+        if ordered < n {
+            // Cyclic: the warning lists node labels along one path
+            let labels: usize = self.node_widths.iter().sum();
+            return labels + n * 4 + glyphs + 200;
+        }
+        let canvas_width = level_widths.iter().copied().max().unwrap_or(0);
+        level_widths.len() * 4 * (canvas_width + 1) + glyphs + 64
     }
 }
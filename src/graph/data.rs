@@ -0,0 +1,129 @@
+//! Per-node payload storage alongside a [`DAG`].
+
+use super::DAG;
+use core::ops::{Deref, DerefMut};
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as HashMap;
+
+/// A [`DAG`] whose nodes can each carry a value of type `T`.
+///
+/// Dereferences to the inner `DAG`, so building, querying, and rendering work
+/// exactly as on a plain graph; rendering ignores the payloads. Payloads are
+/// keyed by node ID and live independently of the node list, so data can be
+/// attached before or after the node itself is added.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::graph::DagWithData;
+///
+/// let mut dag = DagWithData::new();
+/// dag.add_node_with_data(1, "Fetch", 120u32);
+/// dag.add_node_with_data(2, "Build", 3400);
+/// dag.add_edge(1, 2);
+///
+/// assert_eq!(dag.data(2), Some(&3400));
+/// *dag.data_mut(1).unwrap() += 5;
+/// assert_eq!(dag.data(1), Some(&125));
+/// assert_eq!(dag.render(), "[Fetch] → [Build]\n");
+/// ```
+#[derive(Clone)]
+pub struct DagWithData<'a, T> {
+    dag: DAG<'a>,
+    data: HashMap<usize, T>,
+}
+
+impl<'a, T> DagWithData<'a, T> {
+    /// Create an empty graph without any payloads.
+    pub fn new() -> Self {
+        Self::from(DAG::new())
+    }
+
+    /// Add a node together with its payload (replacing any previous payload).
+    pub fn add_node_with_data(&mut self, id: usize, label: &'a str, data: T) {
+        self.dag.add_node(id, label);
+        self.data.insert(id, data);
+    }
+
+    /// Attach `data` to node `id`, returning the payload it replaces.
+    pub fn set_data(&mut self, id: usize, data: T) -> Option<T> {
+        self.data.insert(id, data)
+    }
+
+    /// The payload attached to node `id`, if any.
+    pub fn data(&self, id: usize) -> Option<&T> {
+        self.data.get(&id)
+    }
+
+    /// Mutable access to the payload attached to node `id`, if any.
+    pub fn data_mut(&mut self, id: usize) -> Option<&mut T> {
+        self.data.get_mut(&id)
+    }
+
+    /// Detach and return the payload of node `id`. The node itself stays.
+    pub fn take_data(&mut self, id: usize) -> Option<T> {
+        self.data.remove(&id)
+    }
+
+    /// Drop all payloads and return the plain graph.
+    pub fn into_dag(self) -> DAG<'a> {
+        self.dag
+    }
+}
+
+impl<T> Default for DagWithData<'_, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T> From<DAG<'a>> for DagWithData<'a, T> {
+    fn from(dag: DAG<'a>) -> Self {
+        Self {
+            dag,
+            data: HashMap::new(),
+        }
+    }
+}
+
+impl<'a, T> Deref for DagWithData<'a, T> {
+    type Target = DAG<'a>;
+
+    fn deref(&self) -> &DAG<'a> {
+        &self.dag
+    }
+}
+
+impl<'a, T> DerefMut for DagWithData<'a, T> {
+    fn deref_mut(&mut self) -> &mut DAG<'a> {
+        &mut self.dag
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DagWithData;
+    use crate::graph::DAG;
+
+    #[test]
+    fn test_payload_lifecycle() {
+        let dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+        let mut dag: DagWithData<&str> = dag.into();
+        assert_eq!(dag.data(1), None);
+
+        assert_eq!(dag.set_data(1, "first"), None);
+        assert_eq!(dag.set_data(1, "second"), Some("first"));
+        assert_eq!(dag.take_data(1), Some("second"));
+        assert_eq!(dag.data(1), None);
+
+        // Data for a node that is only added later
+        dag.set_data(3, "pending");
+        dag.add_edge(2, 3);
+        assert_eq!(dag.data(3), Some(&"pending"));
+        assert_eq!(dag.render(), "[A] → [B] → ⟨3⟩\n");
+    }
+}
@@ -13,7 +13,91 @@ pub mod generic;
 use crate::graph::DAG;
 use alloc::{vec, vec::Vec};
 
+/// Where the Sugiyama layout puts one node (see [`DAG::layout`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutNode {
+    /// Node ID
+    pub id: usize,
+    /// Level (0 for roots), i.e. the node's row of levels top to bottom
+    pub level: usize,
+    /// Leftmost column of the node's box (0-based, in chars)
+    pub x: usize,
+    /// Width of the node's box in columns, brackets included
+    pub width: usize,
+}
+
+/// Node positions computed by the vertical layout, independent of ASCII output.
+///
+/// Columns are the same character units the ASCII renderer uses, so the
+/// positions can be scaled directly onto an SVG or canvas grid.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Layout {
+    /// Every node, level by level, left to right within a level
+    pub nodes: Vec<LayoutNode>,
+    /// Canvas width in columns (right edge of the rightmost node)
+    pub width: usize,
+    /// Number of levels
+    pub levels: usize,
+}
+
+impl Layout {
+    /// Position of node `id`, if it is part of the layout.
+    pub fn node(&self, id: usize) -> Option<&LayoutNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+}
+
 impl<'a> DAG<'a> {
+    /// Run the vertical layout without rendering: every node's level and
+    /// column, plus the canvas size.
+    ///
+    /// This is the layout of a connected graph in [`Alignment::Median`](crate::graph::Alignment::Median)
+    /// (crossing reduction, then median alignment). Disconnected components
+    /// are laid out side by side here, whereas the ASCII renderer stacks them.
+    /// Returns an empty layout for empty or cyclic graphs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(
+    ///     &[(1, "Root"), (2, "Left"), (3, "Right")],
+    ///     &[(1, 2), (1, 3)]
+    /// );
+    /// let layout = dag.layout();
+    ///
+    /// assert_eq!(layout.levels, 2);
+    /// let root = layout.node(1).unwrap();
+    /// assert_eq!((root.level, root.width), (0, 6));
+    /// assert!(layout.nodes.iter().all(|n| n.x + n.width <= layout.width));
+    /// ```
+    pub fn layout(&self) -> Layout {
+        if self.nodes.is_empty() || self.has_cycle() {
+            return Layout::default();
+        }
+
+        let (levels, x_coords) = self.layout_levels();
+        let mut layout = Layout {
+            nodes: Vec::with_capacity(self.nodes.len()),
+            width: 0,
+            levels: levels.len(),
+        };
+        for (level, level_nodes) in levels.iter().enumerate() {
+            for &idx in level_nodes {
+                let node = LayoutNode {
+                    id: self.nodes[idx].0,
+                    level,
+                    x: x_coords[idx],
+                    width: self.get_node_width(idx),
+                };
+                layout.width = layout.width.max(node.x + node.width);
+                layout.nodes.push(node);
+            }
+        }
+        layout
+    }
+
     /// Calculate hierarchical levels for all nodes in the graph.
     ///
     /// Uses a fixed-point algorithm to assign each node to a level,
@@ -41,6 +125,263 @@ impl<'a> DAG<'a> {
         levels.into_iter().enumerate().collect()
     }
 
+    /// Run the level assignment, crossing reduction (PASS 1) and coordinate
+    /// assignment (PASS 2) of the vertical layout.
+    ///
+    /// Returns node indices grouped by level, each level ordered left to right,
+    /// and every node's x-coordinate. The graph must be acyclic and non-empty.
+    pub(crate) fn layout_levels(&self) -> (Vec<Vec<usize>>, Vec<usize>) {
+        let mut levels = self.initial_levels();
+        let max_level = levels.len() - 1;
+
+        // === PASS 1: Crossing Reduction (Median Heuristic) ===
+        self.reduce_crossings(&mut levels, max_level);
+
+        // === PASS 2: Character-Level Coordinate Assignment ===
+        let x_coords = self.assign_x_coordinates(&levels, max_level);
+
+        (levels, x_coords)
+    }
+
+    /// Node indices grouped by level, in insertion order within each level
+    /// (the ordering crossing reduction starts from). Never empty.
+    pub(crate) fn initial_levels(&self) -> Vec<Vec<usize>> {
+        let level_data = self.calculate_levels();
+        let max_level = level_data.iter().map(|(_, l)| *l).max().unwrap_or(0);
+
+        let mut levels: Vec<Vec<usize>> = vec![Vec::new(); max_level + 1];
+        for (idx, level) in level_data {
+            levels[level].push(idx);
+        }
+        levels
+    }
+
+    /// Node IDs grouped by level: roots first, then every node one level below
+    /// its deepest parent (longest-path layering).
+    ///
+    /// Within a level, nodes are in the left-to-right order of the vertical
+    /// render of a connected graph, i.e. after crossing reduction and coordinate
+    /// assignment. Disconnected components are layered together (each starts
+    /// at level 0), whereas the renderer stacks them separately. Returns an
+    /// empty list if the graph has a cycle.
+    ///
+    /// Every node's parents sit on earlier levels, so the levels double as
+    /// execution batches: run them in order, the nodes within one in parallel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(
+    ///     &[(1, "Fetch"), (2, "Lint"), (3, "Test"), (4, "Ship")],
+    ///     &[(1, 2), (1, 3), (2, 4), (3, 4)]
+    /// );
+    /// assert_eq!(dag.levels(), vec![vec![1], vec![2, 3], vec![4]]);
+    /// ```
+    pub fn levels(&self) -> Vec<Vec<usize>> {
+        if self.nodes.is_empty() || self.has_cycle() {
+            return Vec::new();
+        }
+
+        let (levels, _) = self.layout_levels();
+        levels
+            .into_iter()
+            .map(|level| level.into_iter().map(|idx| self.nodes[idx].0).collect())
+            .collect()
+    }
+
+    /// Level of node `id` (0 for roots), as in [`levels`](Self::levels).
+    ///
+    /// Returns `None` for an unknown node or a cyclic graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C")], &[(1, 2), (2, 3), (1, 3)]);
+    /// assert_eq!(dag.level_of(3), Some(2));
+    /// assert_eq!(dag.level_of(9), None);
+    /// ```
+    pub fn level_of(&self, id: usize) -> Option<usize> {
+        let idx = self.node_index(id)?;
+        if self.has_cycle() {
+            return None;
+        }
+        self.calculate_levels()
+            .into_iter()
+            .find(|&(node_idx, _)| node_idx == idx)
+            .map(|(_, level)| level)
+    }
+
+    /// Number of pairwise edge crossings in the vertical layout.
+    ///
+    /// Uses the same level assignment and crossing reduction as
+    /// [`levels`](Self::levels), then counts, for every pair of adjacent
+    /// levels, the edges between them that cross. Edges spanning more than one
+    /// level are not counted, and neither are edges sharing an endpoint.
+    /// Returns 0 for empty or cyclic graphs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// // A complete bipartite K(2,2) needs one crossing however it is drawn
+    /// let dag = DAG::from_edges(
+    ///     &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+    ///     &[(1, 3), (1, 4), (2, 3), (2, 4)]
+    /// );
+    /// assert_eq!(dag.count_crossings(), 1);
+    ///
+    /// let diamond = DAG::from_edges(
+    ///     &[(1, "Top"), (2, "Left"), (3, "Right"), (4, "Bottom")],
+    ///     &[(1, 2), (1, 3), (2, 4), (3, 4)]
+    /// );
+    /// assert_eq!(diamond.count_crossings(), 0);
+    /// ```
+    pub fn count_crossings(&self) -> usize {
+        if self.nodes.is_empty() || self.has_cycle() {
+            return 0;
+        }
+
+        let (levels, _) = self.layout_levels();
+        self.crossings_in(&levels)
+    }
+
+    /// Crossings between adjacent levels for the given left-to-right order.
+    pub(crate) fn crossings_in(&self, levels: &[Vec<usize>]) -> usize {
+        let mut position = vec![0; self.nodes.len()];
+        for level_nodes in levels {
+            for (pos, &idx) in level_nodes.iter().enumerate() {
+                position[idx] = pos;
+            }
+        }
+
+        let mut total = 0;
+        for pair in levels.windows(2) {
+            let (upper, lower) = (&pair[0], &pair[1]);
+            let mut in_lower = vec![false; self.nodes.len()];
+            for &idx in lower {
+                in_lower[idx] = true;
+            }
+
+            // Edges as (upper position, lower position), sorted left to right
+            // along the upper level; every later edge ending strictly left of
+            // an earlier one crosses it
+            let mut edges: Vec<(usize, usize)> = upper
+                .iter()
+                .flat_map(|&from| {
+                    self.children[from]
+                        .iter()
+                        .filter(|&&to| in_lower[to])
+                        .map(move |&to| (from, to))
+                })
+                .map(|(from, to)| (position[from], position[to]))
+                .collect();
+            edges.sort_unstable();
+            let mut ends: Vec<usize> = edges.into_iter().map(|(_, to)| to).collect();
+            total += Self::count_inversions(&mut ends);
+        }
+        total
+    }
+
+    /// Pairs `i < j` with `values[i] > values[j]`, by merge sort (sorts `values`).
+    fn count_inversions(values: &mut [usize]) -> usize {
+        if values.len() < 2 {
+            return 0;
+        }
+        let mid = values.len() / 2;
+        let mut count =
+            Self::count_inversions(&mut values[..mid]) + Self::count_inversions(&mut values[mid..]);
+
+        let mut merged = Vec::with_capacity(values.len());
+        let (mut i, mut j) = (0, mid);
+        while i < mid && j < values.len() {
+            if values[j] < values[i] {
+                // Everything left in the first half is greater than values[j]
+                count += mid - i;
+                merged.push(values[j]);
+                j += 1;
+            } else {
+                merged.push(values[i]);
+                i += 1;
+            }
+        }
+        merged.extend_from_slice(&values[i..mid]);
+        merged.extend_from_slice(&values[j..]);
+        values.copy_from_slice(&merged);
+        count
+    }
+
+    /// Nodes without parents in the connected component containing `node`,
+    /// in insertion order: the component's entry points.
+    ///
+    /// Returns an empty `Vec` for an unknown node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(
+    ///     &[(1, "A"), (2, "B"), (3, "C"), (4, "X"), (5, "Y")],
+    ///     &[(1, 3), (2, 3), (4, 5)]
+    /// );
+    /// assert_eq!(dag.subgraph_roots(3), vec![1, 2]);
+    /// assert_eq!(dag.subgraph_roots(5), vec![4]);
+    /// assert!(dag.subgraph_roots(9).is_empty());
+    /// ```
+    pub fn subgraph_roots(&self, node: usize) -> Vec<usize> {
+        let Some(start_idx) = self.node_index(node) else {
+            return Vec::new();
+        };
+
+        let mut visited = vec![false; self.nodes.len()];
+        let mut component = Vec::new();
+        self.collect_connected(start_idx, &mut visited, &mut component);
+        component.sort_unstable();
+
+        self.component_roots(&component)
+            .into_iter()
+            .map(|idx| self.nodes[idx].0)
+            .collect()
+    }
+
+    /// Node IDs of each connected component, ignoring edge direction.
+    ///
+    /// Components come in render order (by smallest node ID), each in insertion
+    /// order; with [`set_component_labels`](Self::set_component_labels) the
+    /// block headed `Component k` is entry `k - 1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(
+    ///     &[(4, "X"), (5, "Y"), (1, "A"), (2, "B"), (3, "C")],
+    ///     &[(1, 3), (2, 3), (4, 5)]
+    /// );
+    /// assert_eq!(dag.connected_components(), vec![vec![1, 2, 3], vec![4, 5]]);
+    /// ```
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        self.find_subgraphs()
+            .into_iter()
+            .map(|subgraph| subgraph.into_iter().map(|idx| self.nodes[idx].0).collect())
+            .collect()
+    }
+
+    /// Indices in `subgraph_indices` without parents, in the given order.
+    pub(crate) fn component_roots(&self, subgraph_indices: &[usize]) -> Vec<usize> {
+        subgraph_indices
+            .iter()
+            .copied()
+            .filter(|&idx| self.parents[idx].is_empty())
+            .collect()
+    }
+
     /// Calculate levels for a specific subgraph.
     pub(crate) fn calculate_levels_for_subgraph(
         &self,
@@ -138,7 +479,7 @@ impl<'a> DAG<'a> {
         }
 
         // Sort by median
-        node_medians.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        node_medians.sort_by(|a, b| a.1.total_cmp(&b.1));
         *level_nodes = node_medians.iter().map(|(idx, _)| *idx).collect();
     }
 
@@ -173,90 +514,158 @@ impl<'a> DAG<'a> {
             }
         }
 
-        node_medians.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        node_medians.sort_by(|a, b| a.1.total_cmp(&b.1));
         *level_nodes = node_medians.iter().map(|(idx, _)| *idx).collect();
     }
 
     /// PASS 2: Assign x-coordinates to each node (character-level positioning).
     ///
-    /// Positions nodes horizontally to minimize edge length while
-    /// maintaining the ordering from crossing reduction.
+    /// Keeps the order from crossing reduction and aligns each node with the
+    /// median of its neighbours (Brandes–Köpf style): alternating top-down and
+    /// bottom-up sweeps pull nodes toward their parents' and children's centers,
+    /// and a final top-down sweep puts single-parent children directly below
+    /// their parent, so connectors run straight down wherever the order allows.
     pub(crate) fn assign_x_coordinates(
         &self,
-        levels: &mut [Vec<usize>],
+        levels: &[Vec<usize>],
         max_level: usize,
     ) -> Vec<usize> {
         let mut x_coords = vec![0usize; self.nodes.len()];
+        let mut level_of = vec![0usize; self.nodes.len()];
 
         // Start with left-to-right layout within each level, preserving crossing reduction order
-        for level_nodes in levels.iter() {
+        for (level_idx, level_nodes) in levels.iter().enumerate() {
             let mut x = 0;
             for &idx in level_nodes.iter() {
+                level_of[idx] = level_idx;
                 x_coords[idx] = x;
                 let width = self.get_node_width(idx);
                 x += width + 3;
             }
         }
 
-        // Refine positions using median of connected nodes to center under parents/over children
-        // But maintain relative order within levels
-        for _ in 0..2 {
-            // Top-down: center under parents where possible
-            for level_idx in 1..=max_level {
-                for &idx in &levels[level_idx] {
-                    let node_id = self.nodes[idx].0;
-                    let parents = self.get_parents(node_id);
-
-                    if !parents.is_empty() {
-                        let mut parent_centers: Vec<usize> = Vec::new();
-                        for &p_id in &parents {
-                            // O(1) HashMap lookup instead of O(n) scan
-                            if let Some(p_idx) = self.node_index(p_id) {
-                                let width = self.get_node_width(p_idx); // Use cached width
-                                parent_centers.push(x_coords[p_idx] + width / 2);
-                            }
-                        }
-
-                        if !parent_centers.is_empty() {
-                            parent_centers.sort_unstable();
-                            let median = parent_centers[parent_centers.len() / 2];
-                            let width = self.get_node_width(idx);
-                            // Shift toward median but don't reorder
-                            let target = median.saturating_sub(width / 2);
-                            x_coords[idx] = (x_coords[idx] + target) / 2;
-                        }
-                    }
+        if max_level > 0 {
+            for _ in 0..4 {
+                // Top-down: align under parents
+                for level_idx in 1..=max_level {
+                    self.align_level(&mut x_coords, &level_of, &levels[level_idx], true);
+                }
+                // Bottom-up: align over children
+                for level_idx in (0..max_level).rev() {
+                    self.align_level(&mut x_coords, &level_of, &levels[level_idx], false);
                 }
-
-                // Re-compact this level to remove overlaps and reorder to match x-coords
-                self.compact_level(&mut x_coords, &mut levels[level_idx]);
+            }
+            for level_idx in 1..=max_level {
+                self.align_level(&mut x_coords, &level_of, &levels[level_idx], true);
             }
         }
 
+        // Alignment may push nodes left of zero; shift everything back
+        let min_x = x_coords.iter().copied().min().unwrap_or(0);
+        for x in &mut x_coords {
+            *x -= min_x;
+        }
+
         x_coords
     }
 
-    /// Compact a level to remove overlaps and reorder nodes left-to-right by x-coordinate.
-    pub(crate) fn compact_level(&self, x_coords: &mut [usize], level_nodes: &mut Vec<usize>) {
-        if level_nodes.is_empty() {
+    /// Place one level as close as possible to the median center of each
+    /// node's parents (`toward_parents`) or children on the adjacent level,
+    /// without overlaps or reordering. Edges spanning several levels have no
+    /// connector in the vertical renderer, so they don't pull.
+    ///
+    /// Works relative to the current leftmost position of the level; the
+    /// closest order-preserving placement is found by pooling adjacent
+    /// violators (isotonic regression on the desired offsets).
+    fn align_level(
+        &self,
+        x_coords: &mut [usize],
+        level_of: &[usize],
+        level_nodes: &[usize],
+        toward_parents: bool,
+    ) {
+        let Some(&first) = level_nodes.first() else {
             return;
+        };
+        let neighbor_level = if toward_parents {
+            level_of[first] - 1
+        } else {
+            level_of[first] + 1
+        };
+
+        // Work in signed coordinates so targets may lie left of the current origin
+        let mut desired: Vec<i64> = Vec::with_capacity(level_nodes.len());
+        for &idx in level_nodes {
+            let neighbors = if toward_parents {
+                self.get_parents_indices(idx)
+            } else {
+                self.get_children_indices(idx)
+            };
+            let mut centers: Vec<i64> = neighbors
+                .iter()
+                .filter(|&&n| level_of[n] == neighbor_level)
+                .map(|&n| (x_coords[n] + self.get_node_width(n) / 2) as i64)
+                .collect();
+
+            let half = (self.get_node_width(idx) / 2) as i64;
+            let target = if centers.is_empty() {
+                x_coords[idx] as i64
+            } else {
+                centers.sort_unstable();
+                let mid = centers.len() / 2;
+                let median = if centers.len() % 2 == 1 {
+                    centers[mid]
+                } else {
+                    (centers[mid - 1] + centers[mid]).div_euclid(2)
+                };
+                median - half
+            };
+            desired.push(target);
         }
 
-        // Sort nodes by their current x position
-        let mut sorted: Vec<_> = level_nodes
-            .iter()
-            .map(|&idx| (x_coords[idx], idx))
-            .collect();
-        sorted.sort_by_key(|(x, _)| *x);
+        // Minimum offset of each node from the level start: x_i >= x_{i-1} + w_{i-1} + 3
+        // becomes a monotonicity constraint on x_i - offset_i.
+        let mut offsets: Vec<i64> = Vec::with_capacity(level_nodes.len());
+        let mut offset = 0i64;
+        for &idx in level_nodes {
+            offsets.push(offset);
+            offset += (self.get_node_width(idx) + 3) as i64;
+        }
+
+        // Pool adjacent violators: blocks of (sum, count), each placed at its mean
+        let mut blocks: Vec<(i64, i64)> = Vec::with_capacity(level_nodes.len());
+        for (target, offset) in desired.iter().zip(&offsets) {
+            blocks.push((target - offset, 1));
+            while blocks.len() > 1 {
+                let (sum, count) = blocks[blocks.len() - 1];
+                let (prev_sum, prev_count) = blocks[blocks.len() - 2];
+                if prev_sum.div_euclid(prev_count) <= sum.div_euclid(count) {
+                    break;
+                }
+                blocks.pop();
+                let last = blocks.len() - 1;
+                blocks[last] = (prev_sum + sum, prev_count + count);
+            }
+        }
 
-        // Reassign x-coords to remove overlaps and update level_nodes order
-        level_nodes.clear();
-        let mut x = 0;
-        for (_, idx) in sorted {
-            level_nodes.push(idx);
-            x_coords[idx] = x;
-            let width = self.get_node_width(idx);
-            x += width + 3;
+        let mut positions: Vec<i64> = Vec::with_capacity(level_nodes.len());
+        for &(sum, count) in &blocks {
+            let base = sum.div_euclid(count);
+            for _ in 0..count {
+                positions.push(base + offsets[positions.len()]);
+            }
+        }
+
+        // Keep coordinates unsigned: if the level would start left of zero,
+        // shift the whole layout right (assign_x_coordinates normalizes at the end)
+        let shift = -positions.iter().copied().min().unwrap_or(0).min(0);
+        if shift > 0 {
+            for x in x_coords.iter_mut() {
+                *x += shift as usize;
+            }
+        }
+        for (&idx, &pos) in level_nodes.iter().zip(&positions) {
+            x_coords[idx] = (pos + shift) as usize;
         }
     }
 
@@ -282,12 +691,12 @@ impl<'a> DAG<'a> {
                 .map(|&idx| x_coords[idx])
                 .min()
                 .unwrap_or(0);
-            let max_node_idx = level_nodes
+            let level_width = level_nodes
                 .iter()
-                .max_by_key(|&&idx| x_coords[idx])
-                .unwrap();
-            let width = self.get_node_width(*max_node_idx);
-            let level_width = (x_coords[*max_node_idx] - min_x) + width;
+                .map(|&idx| x_coords[idx] + self.get_node_width(idx))
+                .max()
+                .unwrap_or(0)
+                - min_x;
 
             level_widths.push(level_width);
             max_width = max_width.max(level_width);
@@ -297,6 +706,10 @@ impl<'a> DAG<'a> {
     }
 
     /// Find disconnected subgraphs in the DAG.
+    ///
+    /// Subgraphs are ordered by their smallest node ID, and each lists its node
+    /// indices in insertion order. Multi-subgraph renders stack them in this
+    /// order, so the output doesn't depend on how edges were added.
     pub(crate) fn find_subgraphs(&self) -> Vec<Vec<usize>> {
         let mut visited = vec![false; self.nodes.len()];
         let mut subgraphs = Vec::new();
@@ -305,36 +718,34 @@ impl<'a> DAG<'a> {
             if !visited[i] {
                 let mut subgraph = Vec::new();
                 self.collect_connected(i, &mut visited, &mut subgraph);
+                subgraph.sort_unstable();
                 subgraphs.push(subgraph);
             }
         }
 
+        // Pinned order: renderers stack subgraphs in this order
+        subgraphs
+            .sort_by_cached_key(|subgraph| subgraph.iter().map(|&idx| self.nodes[idx].0).min());
         subgraphs
     }
 
     /// Collect all nodes connected to the given node (helper for find_subgraphs).
+    ///
+    /// Iterative DFS over the cached adjacency lists in both directions, so
+    /// long chains can't overflow the stack and each node costs O(degree).
     fn collect_connected(&self, start_idx: usize, visited: &mut [bool], subgraph: &mut Vec<usize>) {
         if visited[start_idx] {
             return;
         }
-
         visited[start_idx] = true;
-        subgraph.push(start_idx);
-
-        let node_id = self.nodes[start_idx].0;
-
-        // Follow edges in both directions
-        for &(from, to) in &self.edges {
-            if from == node_id {
-                // O(1) HashMap lookup instead of O(n) scan
-                if let Some(child_idx) = self.node_index(to) {
-                    self.collect_connected(child_idx, visited, subgraph);
-                }
-            }
-            if to == node_id {
-                // O(1) HashMap lookup instead of O(n) scan
-                if let Some(parent_idx) = self.node_index(from) {
-                    self.collect_connected(parent_idx, visited, subgraph);
+        let mut stack = vec![start_idx];
+
+        while let Some(idx) = stack.pop() {
+            subgraph.push(idx);
+            for &next in self.children[idx].iter().chain(&self.parents[idx]) {
+                if !visited[next] {
+                    visited[next] = true;
+                    stack.push(next);
                 }
             }
         }
@@ -342,16 +753,7 @@ impl<'a> DAG<'a> {
 
     /// Check if a subgraph is a simple chain (no branching).
     pub(crate) fn is_subgraph_simple_chain(&self, subgraph_indices: &[usize]) -> bool {
-        for &idx in subgraph_indices {
-            let node_id = self.nodes[idx].0;
-            let parents = self.get_parents(node_id);
-            let children = self.get_children(node_id);
-
-            if parents.len() > 1 || children.len() > 1 {
-                return false;
-            }
-        }
-        true
+        subgraph_indices.iter().all(|&idx| self.is_chain_link(idx))
     }
 }
 
@@ -376,6 +778,58 @@ mod tests {
         assert_eq!(level_map[&3], 2);
     }
 
+    #[test]
+    fn test_public_levels() {
+        let dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        let mut levels = dag.levels();
+        for level in &mut levels {
+            level.sort_unstable();
+        }
+        assert_eq!(levels, vec![vec![1], vec![2, 3], vec![4]]);
+        assert_eq!(dag.level_of(1), Some(0));
+        assert_eq!(dag.level_of(4), Some(2));
+
+        let mut cyclic = DAG::new();
+        cyclic.add_edge(1, 2);
+        cyclic.add_edge(2, 1);
+        assert!(cyclic.levels().is_empty());
+        assert_eq!(cyclic.level_of(1), None);
+    }
+
+    #[test]
+    fn test_levels_follow_layout_order() {
+        // Children inserted against their parents' order get swapped by
+        // crossing reduction; `levels` must report the reduced order
+        let dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D"), (5, "E")],
+            &[(1, 4), (2, 3), (3, 5), (4, 5)],
+        );
+        let levels = dag.levels();
+        let layout = dag.layout();
+        for (level, ids) in levels.iter().enumerate() {
+            let laid_out: Vec<usize> = layout
+                .nodes
+                .iter()
+                .filter(|node| node.level == level)
+                .map(|node| node.id)
+                .collect();
+            assert_eq!(*ids, laid_out);
+        }
+
+        // Batches: every parent is on an earlier level
+        for (level, ids) in levels.iter().enumerate() {
+            for &id in ids {
+                for parent in dag.get_parents(id) {
+                    assert!(dag.level_of(parent).unwrap() < level);
+                }
+            }
+        }
+        assert_eq!(dag.levels(), dag.levels());
+    }
+
     #[test]
     fn test_diamond_layout() {
         let dag = DAG::from_edges(
@@ -394,4 +848,108 @@ mod tests {
         assert_eq!(level_map[&3], 1);
         assert_eq!(level_map[&4], 2); // Bottom
     }
+
+    #[test]
+    fn test_count_inversions() {
+        assert_eq!(DAG::count_inversions(&mut []), 0);
+        assert_eq!(DAG::count_inversions(&mut [1, 2, 3]), 0);
+        assert_eq!(DAG::count_inversions(&mut [3, 2, 1]), 3);
+        // Equal ends share a node and do not cross
+        assert_eq!(DAG::count_inversions(&mut [2, 0, 2, 1]), 3);
+    }
+
+    #[test]
+    fn test_reduce_crossings_never_worse() {
+        let corpus: &[&[(usize, usize)]] = &[
+            // Diamond
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+            // Children inserted against their parents' order
+            &[(1, 4), (2, 3), (1, 5), (2, 6)],
+            // Twisted ladder
+            &[(1, 6), (2, 5), (3, 4), (4, 9), (5, 8), (6, 7)],
+            // K(3,3)
+            &[
+                (1, 4),
+                (1, 5),
+                (1, 6),
+                (2, 4),
+                (2, 5),
+                (2, 6),
+                (3, 4),
+                (3, 5),
+                (3, 6),
+            ],
+            // Shared leaves across two roots
+            &[
+                (1, 3),
+                (1, 5),
+                (2, 4),
+                (2, 3),
+                (3, 6),
+                (4, 6),
+                (5, 7),
+                (4, 7),
+            ],
+            // Build graph with a long edge
+            &[
+                (1, 2),
+                (1, 3),
+                (2, 4),
+                (3, 4),
+                (1, 4),
+                (4, 5),
+                (2, 5),
+                (3, 6),
+            ],
+            // Wide fan-in and fan-out
+            &[
+                (1, 5),
+                (2, 5),
+                (3, 5),
+                (4, 5),
+                (5, 9),
+                (5, 8),
+                (5, 7),
+                (5, 6),
+                (1, 9),
+                (4, 6),
+            ],
+        ];
+
+        for edges in corpus {
+            let mut dag = DAG::new();
+            for &(from, to) in *edges {
+                dag.add_edge(from, to);
+            }
+            let mut levels = dag.initial_levels();
+            let before = dag.crossings_in(&levels);
+            let max_level = levels.len() - 1;
+            dag.reduce_crossings(&mut levels, max_level);
+            let after = dag.crossings_in(&levels);
+            assert!(after <= before, "{:?}: {} -> {}", edges, before, after);
+            assert_eq!(dag.count_crossings(), after);
+        }
+    }
+
+    #[test]
+    fn test_layout_matches_render() {
+        let mut dag = DAG::from_edges(
+            &[(1, "Root"), (2, "Left"), (3, "RightSide"), (4, "C")],
+            &[(1, 2), (1, 3), (3, 4)],
+        );
+        dag.set_render_mode(crate::graph::RenderMode::Vertical);
+
+        let layout = dag.layout();
+        let (_, positions) = dag.render_with_positions();
+        assert_eq!(layout.nodes.len(), positions.len());
+        for pos in &positions {
+            let node = layout.node(pos.id).unwrap();
+            assert_eq!((node.x, node.width), (pos.col, pos.width));
+        }
+
+        let mut cyclic = DAG::new();
+        cyclic.add_edge(1, 2);
+        cyclic.add_edge(2, 1);
+        assert_eq!(cyclic.layout(), crate::layout::Layout::default());
+    }
 }
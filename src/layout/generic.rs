@@ -33,9 +33,97 @@
 pub mod impact;
 pub mod metrics;
 
-use alloc::collections::BTreeMap;
+use alloc::collections::{BinaryHeap, VecDeque};
+use alloc::vec;
 use alloc::vec::Vec;
-use core::hash::Hash;
+use core::borrow::Borrow;
+use core::cmp::{Ordering, Reverse};
+
+use crate::cycles::generic::IdBound;
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as HashMap;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// Index-based view of a dependency closure.
+///
+/// Built with exactly one `get_dependencies` call per item, so the algorithms
+/// using it run in O(V+E) instead of re-querying the closure in nested loops.
+pub(crate) struct DependencyIndex<Id> {
+    /// Dependency id → positions of the items that depend on it.
+    /// Keys may include ids that are not in `items`.
+    pub(crate) dependents: HashMap<Id, Vec<usize>>,
+    /// `deps[i]` = positions of the items that `items[i]` depends on (within `items`)
+    pub(crate) deps: Vec<Vec<usize>>,
+    /// Number of dependencies each item declares (including ids outside `items`)
+    pub(crate) dep_counts: Vec<usize>,
+    /// Whether the id at each position already appeared earlier in `items`
+    pub(crate) repeated: Vec<bool>,
+}
+
+impl<Id: Clone + IdBound> DependencyIndex<Id> {
+    pub(crate) fn build<F, I>(items: &[Id], get_dependencies: F) -> Self
+    where
+        F: Fn(&Id) -> I,
+        I: IntoIterator,
+        I::Item: Borrow<Id>,
+    {
+        // A repeated id resolves to its first position
+        let mut index: HashMap<Id, usize> = HashMap::new();
+        let mut repeated = Vec::with_capacity(items.len());
+        for (idx, id) in items.iter().enumerate() {
+            repeated.push(*index.entry(id.clone()).or_insert(idx) != idx);
+        }
+
+        let mut dependents: HashMap<Id, Vec<usize>> = HashMap::new();
+        let mut deps = Vec::with_capacity(items.len());
+        let mut dep_counts = Vec::with_capacity(items.len());
+
+        for (idx, item) in items.iter().enumerate() {
+            let mut count = 0;
+            let mut item_deps = Vec::new();
+            for dep in get_dependencies(item) {
+                let dep = dep.borrow();
+                count += 1;
+                if let Some(&dep_idx) = index.get(dep) {
+                    item_deps.push(dep_idx);
+                }
+                match dependents.get_mut(dep) {
+                    Some(positions) => positions.push(idx),
+                    None => {
+                        dependents.insert(dep.clone(), vec![idx]);
+                    }
+                }
+            }
+            dep_counts.push(count);
+            deps.push(item_deps);
+        }
+
+        Self {
+            dependents,
+            deps,
+            dep_counts,
+            repeated,
+        }
+    }
+
+    /// Positions of the items that depend on `id`.
+    pub(crate) fn dependents_of(&self, id: &Id) -> &[usize] {
+        self.dependents.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Positions of the items that depend on `items[idx]`, for releasing them
+    /// in Kahn's algorithm. A repeated id releases its dependents only from its
+    /// first position, so no in-degree is counted down twice.
+    pub(crate) fn dependents_at(&self, items: &[Id], idx: usize) -> &[usize] {
+        if self.repeated[idx] {
+            &[]
+        } else {
+            self.dependents_of(&items[idx])
+        }
+    }
+}
 
 /// Performs topological sorting on a collection of items using a dependency function.
 ///
@@ -45,7 +133,15 @@ use core::hash::Hash;
 ///
 /// # Returns
 /// * `Ok(Vec<Id>)` - Items in topological order (items with no dependencies first)
-/// * `Err(Vec<Id>)` - A cycle was detected, returns one of the cycles found
+/// * `Err(Vec<Id>)` - The items that could not be ordered, in input order: those
+///   on a cycle, those depending on one (directly or transitively), and those
+///   depending on an id missing from `items`
+///
+/// The order is deterministic without comparing ids: whenever several items
+/// are ready, the one listed first in `items` goes next. With `std`, ids need
+/// only `Eq + Hash`; without it they still need `Ord` (see [`IdBound`]).
+/// An id listed twice appears twice in the output; its dependents wait only
+/// for the first copy.
 ///
 /// # Examples
 ///
@@ -62,65 +158,416 @@ use core::hash::Hash;
 /// let items = [1, 2, 3];
 /// let sorted = topological_sort_fn(&items, get_deps).unwrap();
 /// assert_eq!(sorted, vec![1, 2, 3]);
+///
+/// // 2 <-> 3 form a cycle and 4 waits on it; 1 can still be ordered
+/// let get_deps = |&id: &usize| match id {
+///     2 => vec![3],
+///     3 => vec![2],
+///     4 => vec![3],
+///     _ => vec![],
+/// };
+/// assert_eq!(topological_sort_fn(&[1, 2, 3, 4], get_deps), Err(vec![2, 3, 4]));
 /// ```
 pub fn topological_sort_fn<Id, F>(items: &[Id], get_dependencies: F) -> Result<Vec<Id>, Vec<Id>>
 where
-    Id: Clone + Eq + Hash + Ord,
+    Id: Clone + IdBound,
     F: Fn(&Id) -> Vec<Id>,
 {
-    use crate::cycles::generic::detect_cycle_fn;
+    topological_sort_iter_fn(items, get_dependencies)
+}
 
-    // First check for cycles
-    if let Some(cycle) = detect_cycle_fn(items, &get_dependencies) {
-        return Err(cycle);
+/// [`topological_sort_fn`] for closures that return any iterable of
+/// dependencies, owned or borrowed.
+///
+/// Use this when the dependency lists already exist (say, a `Vec<Vec<Id>>`
+/// indexed by item) to avoid building a `Vec` per closure call. Not to be
+/// confused with [`topological_iter_fn`], which yields its *output* lazily.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::layout::generic::topological_sort_iter_fn;
+///
+/// // deps[i] = what item i depends on
+/// let deps: Vec<Vec<usize>> = vec![vec![2], vec![0], vec![]];
+/// let sorted = topological_sort_iter_fn(&[0, 1, 2], |&id| &deps[id]);
+/// assert_eq!(sorted, Ok(vec![2, 0, 1]));
+/// ```
+pub fn topological_sort_iter_fn<Id, F, I>(
+    items: &[Id],
+    get_dependencies: F,
+) -> Result<Vec<Id>, Vec<Id>>
+where
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> I,
+    I: IntoIterator,
+    I::Item: Borrow<Id>,
+{
+    // Kahn's algorithm over a reverse index: one closure call per item, O(V+E).
+    // Dependencies outside `items` are never released, so their dependents stall.
+    let index = DependencyIndex::build(items, get_dependencies);
+    let mut in_degree = index.dep_counts.clone();
+    let mut result = Vec::with_capacity(items.len());
+
+    // Ready items by input position, earliest first
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..items.len())
+        .filter(|&idx| in_degree[idx] == 0)
+        .map(Reverse)
+        .collect();
+
+    while let Some(Reverse(idx)) = ready.pop() {
+        result.push(items[idx].clone());
+
+        for &dependent in index.dependents_at(items, idx) {
+            in_degree[dependent] -= 1;
+            if in_degree[dependent] == 0 {
+                ready.push(Reverse(dependent));
+            }
+        }
     }
 
-    // Kahn's algorithm with BTreeMap for deterministic ordering
-    let mut in_degree: BTreeMap<Id, usize> = BTreeMap::new();
-    let mut result = Vec::new();
+    finish_kahn(items, result, &in_degree)
+}
 
-    // Initialize in-degrees
-    for item in items {
-        in_degree.entry(item.clone()).or_insert(0);
+/// Topological sort that picks the next item with a custom comparator.
+///
+/// Whenever several items are ready, the smallest under `cmp` goes next;
+/// items that compare equal keep their input order. Cycles and missing
+/// dependencies are reported exactly as by [`topological_sort_fn`].
+///
+/// The ready set is kept sorted by binary insertion: O(V log V) comparator
+/// calls, plus O(V·R) element moves where R is the largest number of items
+/// ready at once. `get_dependencies` is called exactly once per item.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::layout::generic::topological_sort_with_fn;
+///
+/// let get_deps = |task: &&str| match *task {
+///     "test" | "lint" | "typecheck" => vec!["build"],
+///     "deploy" => vec!["test", "lint", "typecheck"],
+///     _ => vec![],
+/// };
+///
+/// // Shortest task name first among the ready ones
+/// let tasks = ["deploy", "typecheck", "test", "lint", "build"];
+/// let sorted = topological_sort_with_fn(&tasks, get_deps, |a, b| a.len().cmp(&b.len()));
+/// assert_eq!(sorted, Ok(vec!["build", "test", "lint", "typecheck", "deploy"]));
+/// ```
+pub fn topological_sort_with_fn<Id, F, C>(
+    items: &[Id],
+    get_dependencies: F,
+    mut cmp: C,
+) -> Result<Vec<Id>, Vec<Id>>
+where
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> Vec<Id>,
+    C: FnMut(&Id, &Id) -> Ordering,
+{
+    let index = DependencyIndex::build(items, get_dependencies);
+    let mut in_degree = index.dep_counts.clone();
+    let mut result = Vec::with_capacity(items.len());
+
+    // Ready items, next one last: descending under `cmp`, ties by input position
+    let mut ready: Vec<usize> = Vec::new();
+    let mut make_ready = |ready: &mut Vec<usize>, idx: usize| {
+        let at = ready.partition_point(|&r| match cmp(&items[r], &items[idx]) {
+            Ordering::Equal => r > idx,
+            order => order == Ordering::Greater,
+        });
+        ready.insert(at, idx);
+    };
+    for idx in 0..items.len() {
+        if in_degree[idx] == 0 {
+            make_ready(&mut ready, idx);
+        }
     }
 
-    // Calculate in-degrees: if item depends on dep, then item has incoming edge from dep
-    for item in items {
-        let deps_count = get_dependencies(item).len();
-        *in_degree.entry(item.clone()).or_insert(0) += deps_count;
+    while let Some(idx) = ready.pop() {
+        result.push(items[idx].clone());
+
+        for &dependent in index.dependents_at(items, idx) {
+            in_degree[dependent] -= 1;
+            if in_degree[dependent] == 0 {
+                make_ready(&mut ready, dependent);
+            }
+        }
     }
 
-    // Find all items with no dependencies (in_degree == 0)
-    let mut queue: Vec<Id> = in_degree
-        .iter()
-        .filter(|&(_, &degree)| degree == 0)
-        .map(|(id, _)| id.clone())
+    finish_kahn(items, result, &in_degree)
+}
+
+/// `Ok(order)` if Kahn's algorithm placed every item. Otherwise it stalled:
+/// whatever still has unresolved dependencies is tangled in (or stuck behind)
+/// a cycle, and is returned in input order.
+fn finish_kahn<Id: Clone>(
+    items: &[Id],
+    order: Vec<Id>,
+    in_degree: &[usize],
+) -> Result<Vec<Id>, Vec<Id>> {
+    if order.len() == items.len() {
+        Ok(order)
+    } else {
+        Err(items
+            .iter()
+            .zip(in_degree)
+            .filter(|&(_, &degree)| degree > 0)
+            .map(|(item, _)| item.clone())
+            .collect())
+    }
+}
+
+/// Lazily yields items in topological order, one ready item per `next()`.
+///
+/// Returned by [`topological_iter_fn`]. Runs Kahn's algorithm incrementally:
+/// each `next()` pops a ready item and releases its dependents. Items become
+/// ready in input order, so the output is deterministic.
+///
+/// If the graph has a cycle, the iterator stops early once no item is ready;
+/// the items on (or behind) the cycle are never yielded. Compare the number of
+/// yielded items with the input length to detect this.
+pub struct TopologicalIter<Id> {
+    items: Vec<Id>,
+    /// `dependents[i]` = positions of the items that depend on `items[i]`
+    dependents: Vec<Vec<usize>>,
+    /// Unresolved dependencies left per item
+    in_degree: Vec<usize>,
+    ready: VecDeque<usize>,
+    remaining: usize,
+}
+
+impl<Id: Clone> Iterator for TopologicalIter<Id> {
+    type Item = Id;
+
+    fn next(&mut self) -> Option<Id> {
+        let idx = self.ready.pop_front()?;
+        self.remaining -= 1;
+        for &dependent in &self.dependents[idx] {
+            self.in_degree[dependent] -= 1;
+            if self.in_degree[dependent] == 0 {
+                self.ready.push_back(dependent);
+            }
+        }
+        Some(self.items[idx].clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.ready.len(), Some(self.remaining))
+    }
+}
+
+/// Topological order as a lazy iterator (see [`TopologicalIter`]).
+///
+/// Like [`topological_sort_fn`], a dependency that is not in `items` is never
+/// satisfied, so the items waiting on it are not yielded. `get_dependencies`
+/// is called exactly once per item, up front.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::layout::generic::topological_iter_fn;
+///
+/// let get_deps = |&id: &usize| match id {
+///     2 => vec![1],
+///     3 => vec![2],
+///     _ => vec![],
+/// };
+///
+/// let mut order = topological_iter_fn(&[3, 2, 1], get_deps);
+/// assert_eq!(order.next(), Some(1)); // available before the rest is resolved
+/// assert_eq!(order.collect::<Vec<_>>(), vec![2, 3]);
+///
+/// // With a cycle, iteration stops short
+/// let cyclic = |&id: &usize| match id {
+///     1 => vec![2],
+///     2 => vec![1],
+///     _ => vec![],
+/// };
+/// let partial: Vec<_> = topological_iter_fn(&[0, 1, 2], cyclic).collect();
+/// assert_eq!(partial, vec![0]);
+///
+/// // So does it when a dependency is missing from `items`
+/// let partial: Vec<_> = topological_iter_fn(&[2, 3], get_deps).collect();
+/// assert!(partial.is_empty());
+/// ```
+pub fn topological_iter_fn<Id, F>(items: &[Id], get_dependencies: F) -> TopologicalIter<Id>
+where
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> Vec<Id>,
+{
+    let index = DependencyIndex::build(items, get_dependencies);
+    let in_degree = index.dep_counts.clone();
+    let ready = (0..items.len())
+        .filter(|&idx| in_degree[idx] == 0)
+        .collect();
+    let dependents = (0..items.len())
+        .map(|idx| index.dependents_at(items, idx).to_vec())
         .collect();
 
-    // Process queue
-    while let Some(item) = queue.pop() {
-        result.push(item.clone());
-
-        // Find all items that depend on the current item
-        for candidate in items {
-            let deps = get_dependencies(candidate);
-            if deps.contains(&item) {
-                if let Some(degree) = in_degree.get_mut(candidate) {
-                    *degree -= 1;
-                    if *degree == 0 {
-                        queue.push(candidate.clone());
-                    }
-                }
+    TopologicalIter {
+        items: items.to_vec(),
+        dependents,
+        in_degree,
+        ready,
+        remaining: items.len(),
+    }
+}
+
+/// Every valid topological order, up to `limit` of them.
+///
+/// **The number of orderings grows exponentially**: `n` independent items
+/// have `n!` of them. Keep `limit` small, or call
+/// [`count_topological_sorts_fn`] first.
+///
+/// Orderings are produced by backtracking over Kahn's algorithm, trying ready
+/// items in input order, so they come out sorted by input position and the
+/// first one matches [`topological_sort_fn`]. Graphs with a cycle, or with a
+/// dependency missing from `items`, have no orderings. `get_dependencies` is
+/// called exactly once per item.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::layout::generic::all_topological_sorts_fn;
+///
+/// // The diamond: fetch first, ship last, lint and test either way round
+/// let get_deps = |task: &&str| match *task {
+///     "lint" | "test" => vec!["fetch"],
+///     "ship" => vec!["lint", "test"],
+///     _ => vec![],
+/// };
+///
+/// let tasks = ["fetch", "lint", "test", "ship"];
+/// let orders = all_topological_sorts_fn(&tasks, get_deps, 10);
+/// assert_eq!(orders, vec![
+///     vec!["fetch", "lint", "test", "ship"],
+///     vec!["fetch", "test", "lint", "ship"],
+/// ]);
+///
+/// // Stop early
+/// assert_eq!(all_topological_sorts_fn(&tasks, get_deps, 1).len(), 1);
+/// ```
+pub fn all_topological_sorts_fn<Id, F>(
+    items: &[Id],
+    get_dependencies: F,
+    limit: usize,
+) -> Vec<Vec<Id>>
+where
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> Vec<Id>,
+{
+    let mut orders = Vec::new();
+    if limit == 0 {
+        return orders;
+    }
+    for_each_topological_order(items, get_dependencies, |order| {
+        orders.push(order.iter().map(|&idx| items[idx].clone()).collect());
+        orders.len() < limit
+    });
+    orders
+}
+
+/// Number of valid topological orders, counting no further than `cap`.
+///
+/// Like [`all_topological_sorts_fn`] without materializing the orderings, but
+/// the work still grows with the count: `cap` bounds the running time, and a
+/// result equal to `cap` means "at least `cap`".
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::layout::generic::count_topological_sorts_fn;
+///
+/// // Four independent items: 4! = 24 orderings
+/// let no_deps = |_: &usize| vec![];
+/// assert_eq!(count_topological_sorts_fn(&[1, 2, 3, 4], no_deps, 1000), 24);
+/// assert_eq!(count_topological_sorts_fn(&[1, 2, 3, 4], no_deps, 5), 5);
+/// ```
+pub fn count_topological_sorts_fn<Id, F>(items: &[Id], get_dependencies: F, cap: usize) -> usize
+where
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> Vec<Id>,
+{
+    let mut count = 0;
+    if cap == 0 {
+        return count;
+    }
+    for_each_topological_order(items, get_dependencies, |_| {
+        count += 1;
+        count < cap
+    });
+    count
+}
+
+/// Backtrack over every topological order of `items`, calling `visit` with
+/// each one (as positions into `items`) until it returns `false`.
+///
+/// Iterative, so long chains do not exhaust the stack.
+fn for_each_topological_order<Id, F>(
+    items: &[Id],
+    get_dependencies: F,
+    mut visit: impl FnMut(&[usize]) -> bool,
+) where
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> Vec<Id>,
+{
+    let index = DependencyIndex::build(items, get_dependencies);
+    let n = items.len();
+
+    // One Kahn pass first: if it stalls there is no full order, and the
+    // backtracking below would walk every partial one without ever visiting
+    let mut in_degree = index.dep_counts.clone();
+    let mut ready: Vec<usize> = (0..n).filter(|&idx| in_degree[idx] == 0).collect();
+    let mut released = 0;
+    while let Some(idx) = ready.pop() {
+        released += 1;
+        for &dependent in index.dependents_at(items, idx) {
+            in_degree[dependent] -= 1;
+            if in_degree[dependent] == 0 {
+                ready.push(dependent);
             }
         }
     }
+    if released < n {
+        return;
+    }
 
-    // If we processed all items, we have a valid topological order
-    if result.len() == items.len() {
-        Ok(result)
-    } else {
-        // This shouldn't happen since we checked for cycles, but handle it anyway
-        Err(vec![])
+    let mut in_degree = index.dep_counts.clone();
+    let mut placed = vec![false; n];
+    let mut order: Vec<usize> = Vec::with_capacity(n);
+    // cursor[d] = first position still to try at depth d
+    let mut cursor: Vec<usize> = vec![0];
+
+    loop {
+        let depth = order.len();
+        if depth == n {
+            if !visit(&order) {
+                return;
+            }
+        } else {
+            let next = (cursor[depth]..n).find(|&idx| !placed[idx] && in_degree[idx] == 0);
+            if let Some(idx) = next {
+                cursor[depth] = idx + 1;
+                placed[idx] = true;
+                for &dependent in index.dependents_at(items, idx) {
+                    in_degree[dependent] -= 1;
+                }
+                order.push(idx);
+                cursor.push(0);
+                continue;
+            }
+        }
+
+        // Exhausted this depth: undo the last choice and try the next one
+        cursor.pop();
+        let Some(idx) = order.pop() else {
+            return;
+        };
+        placed[idx] = false;
+        for &dependent in index.dependents_at(items, idx) {
+            in_degree[dependent] += 1;
+        }
     }
 }
 
@@ -155,8 +602,9 @@ where
 /// // let sorted = task_graph.topological_sort().unwrap();
 /// ```
 pub trait TopologicallySortable {
-    /// The type of identifiers in the graph.
-    type Id: Clone + Eq + Hash + Ord;
+    /// The type of identifiers in the graph (`Eq + Hash` with `std`, `Ord`
+    /// without; see [`IdBound`]).
+    type Id: Clone + IdBound;
 
     /// Get all item IDs in the collection.
     fn get_all_ids(&self) -> Vec<Self::Id>;
@@ -168,7 +616,7 @@ pub trait TopologicallySortable {
     ///
     /// # Returns
     /// * `Ok(Vec<Id>)` - Items in dependency order
-    /// * `Err(Vec<Id>)` - A cycle was detected
+    /// * `Err(Vec<Id>)` - The items that could not be ordered (see [`topological_sort_fn`])
     fn topological_sort(&self) -> Result<Vec<Self::Id>, Vec<Self::Id>> {
         let ids = self.get_all_ids();
         topological_sort_fn(&ids, |id| self.get_dependencies(id))
@@ -233,6 +681,123 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_cycle_remainder() {
+        // 2 -> 3 -> 4 -> 2 is a cycle; 5 depends on it; 1 and 6 are fine
+        let get_deps = |&id: &usize| match id {
+            2 => vec![4, 1],
+            3 => vec![2],
+            4 => vec![3],
+            5 => vec![4],
+            6 => vec![1],
+            _ => vec![],
+        };
+
+        let items = [6, 5, 4, 3, 2, 1];
+        let result = topological_sort_fn(&items, get_deps);
+        assert_eq!(result, Err(vec![5, 4, 3, 2]));
+    }
+
+    #[test]
+    fn test_sort_calls_closure_once_per_item() {
+        use core::cell::Cell;
+
+        // A long chain, given in reverse: 0 <- 1 <- ... <- 199
+        let calls = Cell::new(0);
+        let get_deps = |&id: &usize| {
+            calls.set(calls.get() + 1);
+            if id == 0 { vec![] } else { vec![id - 1] }
+        };
+        let items: Vec<usize> = (0..200).rev().collect();
+
+        let sorted = topological_sort_fn(&items, get_deps).unwrap();
+        assert_eq!(sorted, (0..200).collect::<Vec<_>>());
+        assert_eq!(calls.get(), items.len());
+    }
+
+    #[test]
+    fn test_missing_dependency_is_unresolved() {
+        let get_deps = |&id: &usize| match id {
+            2 => vec![1, 9], // 9 is not an item
+            3 => vec![2],
+            _ => vec![],
+        };
+        assert_eq!(topological_sort_fn(&[1, 2, 3], get_deps), Err(vec![2, 3]));
+    }
+
+    #[test]
+    fn test_ties_follow_input_order() {
+        // 1 and 2 are independent roots, 3 needs both, 4 needs only 1
+        let get_deps = |&id: &usize| match id {
+            3 => vec![1, 2],
+            4 => vec![1],
+            _ => vec![],
+        };
+
+        let shuffles = [[1, 2, 3, 4], [4, 3, 2, 1], [2, 4, 1, 3], [3, 1, 4, 2]];
+        for items in &shuffles {
+            let sorted = topological_sort_fn(items, get_deps).unwrap();
+            assert_eq!(sorted, topological_sort_fn(items, get_deps).unwrap());
+            for id in &sorted {
+                let pos = |x: &usize| sorted.iter().position(|s| s == x).unwrap();
+                assert!(get_deps(id).iter().all(|dep| pos(dep) < pos(id)));
+            }
+            // The same as the first of all orderings: earliest ready item next
+            let first = all_topological_sorts_fn(items, get_deps, 1);
+            assert_eq!(first, vec![sorted]);
+        }
+        assert_eq!(
+            topological_sort_fn(&[4, 3, 2, 1], get_deps),
+            Ok(vec![2, 1, 4, 3])
+        );
+    }
+
+    #[test]
+    fn test_sort_with_comparator() {
+        // 1 -> {2, 3, 4}; 5 is independent
+        let get_deps = |&id: &usize| match id {
+            2..=4 => vec![1],
+            _ => vec![],
+        };
+        let items = [1, 2, 3, 4, 5];
+
+        // Largest ready id first
+        let sorted = topological_sort_with_fn(&items, get_deps, |a, b| b.cmp(a));
+        assert_eq!(sorted, Ok(vec![5, 1, 4, 3, 2]));
+
+        // Everything equal: input order, like topological_sort_fn
+        let sorted = topological_sort_with_fn(&items, get_deps, |_, _| Ordering::Equal);
+        assert_eq!(sorted, topological_sort_fn(&items, get_deps));
+
+        // Same cycle report as topological_sort_fn
+        let cyclic = |&id: &usize| match id {
+            2 => vec![3],
+            3 => vec![2],
+            4 => vec![3],
+            _ => vec![],
+        };
+        assert_eq!(
+            topological_sort_with_fn(&items, cyclic, |a, b| a.cmp(b)),
+            Err(vec![2, 3, 4])
+        );
+    }
+
+    // Without `std` the index is a BTreeMap and ids need `Ord` after all
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_sort_without_ord() {
+        // Eq + Hash only
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        struct Handle(u32);
+
+        let get_deps = |h: &Handle| match h.0 {
+            2 => vec![Handle(1)],
+            _ => vec![],
+        };
+        let sorted = topological_sort_fn(&[Handle(2), Handle(1)], get_deps).unwrap();
+        assert_eq!(sorted, vec![Handle(1), Handle(2)]);
+    }
+
     #[test]
     fn test_multiple_roots() {
         let get_deps = |&id: &usize| match id {
@@ -286,4 +851,133 @@ mod tests {
         assert_eq!(sorted, vec![1, 2, 3]);
         assert!(graph.has_valid_ordering());
     }
+
+    #[test]
+    fn test_all_topological_sorts() {
+        // Antichain: both orders
+        let no_deps = |_: &usize| vec![];
+        assert_eq!(
+            all_topological_sorts_fn(&[1, 2], no_deps, 10),
+            vec![vec![1, 2], vec![2, 1]]
+        );
+
+        // Chain: exactly one
+        let chain = |&id: &usize| if id > 1 { vec![id - 1] } else { vec![] };
+        assert_eq!(
+            all_topological_sorts_fn(&[3, 1, 2], chain, 10),
+            vec![vec![1, 2, 3]]
+        );
+        assert_eq!(count_topological_sorts_fn(&[3, 1, 2], chain, 10), 1);
+
+        // Diamond: two
+        let diamond = |&id: &usize| match id {
+            2 | 3 => vec![1],
+            4 => vec![2, 3],
+            _ => vec![],
+        };
+        assert_eq!(count_topological_sorts_fn(&[1, 2, 3, 4], diamond, 10), 2);
+
+        // Cycles and missing dependencies leave nothing to order
+        let cyclic = |&id: &usize| match id {
+            1 => vec![2],
+            2 => vec![1],
+            _ => vec![],
+        };
+        assert!(all_topological_sorts_fn(&[1, 2, 3], cyclic, 10).is_empty());
+        let missing = |&id: &usize| if id == 1 { vec![9] } else { vec![] };
+        assert_eq!(count_topological_sorts_fn(&[1, 2], missing, 10), 0);
+
+        // Zero limits
+        assert!(all_topological_sorts_fn(&[1, 2], no_deps, 0).is_empty());
+        assert_eq!(count_topological_sorts_fn(&[1, 2], no_deps, 0), 0);
+    }
+
+    #[test]
+    fn test_count_topological_sorts_long_chain() {
+        let chain = |&id: &usize| if id > 0 { vec![id - 1] } else { vec![] };
+        let items: Vec<usize> = (0..3_000).collect();
+        assert_eq!(count_topological_sorts_fn(&items, chain, 5), 1);
+    }
+
+    #[test]
+    fn test_count_topological_sorts_cycle_beside_free_items() {
+        // 20 free items and a 2-cycle: backtracking alone would try 20! prefixes
+        let cyclic = |&id: &usize| match id {
+            100 => vec![101],
+            101 => vec![100],
+            _ => vec![],
+        };
+        let items: Vec<usize> = (0..20).chain([100, 101]).collect();
+        assert_eq!(count_topological_sorts_fn(&items, cyclic, 1), 0);
+        assert!(all_topological_sorts_fn(&items, cyclic, 1).is_empty());
+    }
+
+    #[test]
+    fn test_sort_with_repeated_item() {
+        // The repeated 1 must not release 2 a second time
+        let get_deps = |&id: &u32| if id == 2 { vec![1] } else { vec![] };
+        assert_eq!(
+            topological_sort_fn(&[1u32, 1, 2], get_deps),
+            Ok(vec![1, 1, 2])
+        );
+        assert_eq!(count_topological_sorts_fn(&[1u32, 1, 2], get_deps, 10), 3);
+    }
+
+    #[test]
+    fn test_topological_iter_with_repeated_item() {
+        let get_deps = |&id: &u32| if id == 2 { vec![1] } else { vec![] };
+        let order: Vec<_> = topological_iter_fn(&[1u32, 1, 2], get_deps).collect();
+        assert_eq!(order, vec![1, 1, 2]);
+    }
+
+    #[test]
+    fn test_topological_iter_matches_order_constraints() {
+        let get_deps = |task: &&str| match *task {
+            "deploy" => vec!["test", "build"],
+            "test" => vec!["build"],
+            "build" => vec!["compile", "fetch"],
+            _ => vec![],
+        };
+        let tasks = ["deploy", "test", "build", "compile", "fetch"];
+
+        let order: Vec<_> = topological_iter_fn(&tasks, get_deps).collect();
+        assert_eq!(order.len(), tasks.len());
+        for task in &tasks {
+            let pos = |t: &&str| order.iter().position(|o| o == t).unwrap();
+            for dep in get_deps(task) {
+                assert!(pos(&dep) < pos(task), "{} must come before {}", dep, task);
+            }
+        }
+    }
+
+    #[test]
+    fn test_topological_iter_stops_at_cycle() {
+        // 1 <-> 2 is a cycle, 3 depends on it, 4 is independent
+        let get_deps = |&id: &usize| match id {
+            1 => vec![2],
+            2 => vec![1],
+            3 => vec![1],
+            _ => vec![],
+        };
+        let order: Vec<_> = topological_iter_fn(&[1, 2, 3, 4], get_deps).collect();
+        assert_eq!(order, vec![4]);
+    }
+
+    #[test]
+    fn test_sort_iter_fn_over_slices() {
+        // deps[i] = what item i depends on; the closure borrows each list
+        let deps: Vec<Vec<usize>> = vec![vec![3], vec![0, 3], vec![1], vec![], vec![9]];
+        let items = [0, 1, 2, 3, 4];
+
+        let sorted = topological_sort_iter_fn(&items, |&id| deps[id].iter());
+        assert_eq!(sorted, topological_sort_fn(&items, |&id| deps[id].clone()));
+        // Item 4 depends on 9, which is not in `items`, so it is reported
+        assert_eq!(sorted, Err(vec![4]));
+
+        let acyclic = |&id: &usize| deps[id].iter().filter(|&&dep| dep != 9);
+        assert_eq!(
+            topological_sort_iter_fn(&items, acyclic),
+            Ok(vec![3, 0, 1, 2, 4])
+        );
+    }
 }
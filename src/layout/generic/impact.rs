@@ -23,18 +23,24 @@
 //! assert!(impacted.contains(&"app.exe"));
 //! ```
 
-use alloc::vec::Vec;
-use core::hash::Hash;
+use alloc::{vec, vec::Vec};
+use core::borrow::Borrow;
+use core::cell::OnceCell;
+
+use super::DependencyIndex;
+use crate::cycles::generic::IdBound;
+use crate::graph::DAG;
 
 #[cfg(not(feature = "std"))]
-use alloc::collections::{BTreeSet as HashSet, VecDeque};
+use alloc::collections::{BTreeMap as HashMap, BTreeSet as HashSet, VecDeque};
 #[cfg(feature = "std")]
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 /// Compute all nodes that (transitively) depend on a given starting node.
 ///
 /// This performs a breadth-first search to find all descendants - nodes that would
-/// be affected if the starting node changed.
+/// be affected if the starting node changed. `get_dependencies` is called once per
+/// item to build a reverse index, so the whole search is O(V+E).
 ///
 /// # Arguments
 /// * `items` - All nodes in the graph
@@ -66,32 +72,128 @@ use std::collections::{HashSet, VecDeque};
 /// ```
 pub fn compute_descendants_fn<Id, F>(items: &[Id], start: &Id, get_dependencies: F) -> Vec<Id>
 where
-    Id: Clone + Eq + Hash,
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> Vec<Id>,
+{
+    compute_descendants_iter_fn(items, start, get_dependencies)
+}
+
+/// [`compute_descendants_fn`] for closures that return any iterable of
+/// dependencies, owned or borrowed.
+///
+/// Use this when the dependency lists already exist to avoid building a `Vec`
+/// per closure call.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::layout::generic::impact::compute_descendants_iter_fn;
+///
+/// // deps[i] = what item i depends on
+/// let deps: Vec<Vec<usize>> = vec![vec![], vec![0], vec![1]];
+/// let impacted = compute_descendants_iter_fn(&[0, 1, 2], &0, |&id| &deps[id]);
+/// assert_eq!(impacted, vec![1, 2]);
+/// ```
+pub fn compute_descendants_iter_fn<Id, F, I>(
+    items: &[Id],
+    start: &Id,
+    get_dependencies: F,
+) -> Vec<Id>
+where
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> I,
+    I: IntoIterator,
+    I::Item: Borrow<Id>,
+{
+    compute_descendants_within_iter_fn(items, start, get_dependencies, usize::MAX)
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect()
+}
+
+/// Compute the nodes that depend on `start` within `max_depth` hops, with their distance.
+///
+/// Direct dependents are at distance 1, their dependents at distance 2, and so on.
+/// Results are in breadth-first order (non-decreasing distance), so the closest
+/// impacts come first. [`compute_descendants_fn`] is the `max_depth = usize::MAX`
+/// case without distances.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::layout::generic::impact::compute_descendants_within_fn;
+///
+/// let get_deps = |pkg: &&str| match *pkg {
+///     "app" => vec!["lib"],
+///     "lib" => vec!["core"],
+///     _ => vec![],
+/// };
+///
+/// let packages = ["app", "lib", "core"];
+///
+/// // Direct dependents of core
+/// let near = compute_descendants_within_fn(&packages, &"core", get_deps, 1);
+/// assert_eq!(near, vec![("lib", 1)]);
+/// ```
+pub fn compute_descendants_within_fn<Id, F>(
+    items: &[Id],
+    start: &Id,
+    get_dependencies: F,
+    max_depth: usize,
+) -> Vec<(Id, usize)>
+where
+    Id: Clone + IdBound,
     F: Fn(&Id) -> Vec<Id>,
+{
+    compute_descendants_within_iter_fn(items, start, get_dependencies, max_depth)
+}
+
+/// [`compute_descendants_within_fn`] for closures that return any iterable of
+/// dependencies, owned or borrowed.
+pub fn compute_descendants_within_iter_fn<Id, F, I>(
+    items: &[Id],
+    start: &Id,
+    get_dependencies: F,
+    max_depth: usize,
+) -> Vec<(Id, usize)>
+where
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> I,
+    I: IntoIterator,
+    I::Item: Borrow<Id>,
 {
     let mut descendants = Vec::new();
-    let mut visited = HashSet::new();
+
+    if max_depth == 0 {
+        return descendants;
+    }
+
+    // One pass over the closure: dependency id → dependents
+    let index = DependencyIndex::build(items, get_dependencies);
+    let mut visited = vec![false; items.len()];
     let mut queue = VecDeque::new();
 
     // Find all items that depend on 'start'
-    for item in items {
-        let deps = get_dependencies(item);
-        if deps.contains(start) && !visited.contains(item) {
-            queue.push_back(item.clone());
-            visited.insert(item.clone());
+    for &idx in index.dependents_of(start) {
+        if !visited[idx] {
+            visited[idx] = true;
+            queue.push_back((idx, 1));
         }
     }
 
     // BFS to find all transitive dependents
-    while let Some(current) = queue.pop_front() {
-        descendants.push(current.clone());
+    while let Some((current, distance)) = queue.pop_front() {
+        descendants.push((items[current].clone(), distance));
+
+        if distance >= max_depth {
+            continue;
+        }
 
         // Find items that depend on current
-        for item in items {
-            let deps = get_dependencies(item);
-            if deps.contains(&current) && !visited.contains(item) {
-                queue.push_back(item.clone());
-                visited.insert(item.clone());
+        for &idx in index.dependents_of(&items[current]) {
+            if !visited[idx] {
+                visited[idx] = true;
+                queue.push_back((idx, distance + 1));
             }
         }
     }
@@ -125,10 +227,37 @@ where
 /// assert!(prerequisites.contains(&"test"));
 /// assert!(prerequisites.contains(&"build"));
 /// ```
-pub fn compute_ancestors_fn<Id, F>(_items: &[Id], start: &Id, get_dependencies: F) -> Vec<Id>
+pub fn compute_ancestors_fn<Id, F>(items: &[Id], start: &Id, get_dependencies: F) -> Vec<Id>
 where
-    Id: Clone + Eq + Hash,
+    Id: Clone + IdBound,
     F: Fn(&Id) -> Vec<Id>,
+{
+    compute_ancestors_iter_fn(items, start, get_dependencies)
+}
+
+/// [`compute_ancestors_fn`] for closures that return any iterable of
+/// dependencies, owned or borrowed.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::layout::generic::impact::compute_ancestors_iter_fn;
+///
+/// // deps[i] = what item i depends on
+/// let deps: Vec<Vec<usize>> = vec![vec![], vec![0], vec![1]];
+/// let prerequisites = compute_ancestors_iter_fn(&[0, 1, 2], &2, |&id| &deps[id]);
+/// assert_eq!(prerequisites, vec![1, 0]);
+/// ```
+pub fn compute_ancestors_iter_fn<Id, F, I>(
+    _items: &[Id],
+    start: &Id,
+    get_dependencies: F,
+) -> Vec<Id>
+where
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> I,
+    I: IntoIterator,
+    I::Item: Borrow<Id>,
 {
     let mut ancestors = Vec::new();
     let mut visited = HashSet::new();
@@ -136,7 +265,8 @@ where
 
     // Start with direct dependencies
     for dep in get_dependencies(start) {
-        if !visited.contains(&dep) {
+        let dep = dep.borrow();
+        if !visited.contains(dep) {
             queue.push_back(dep.clone());
             visited.insert(dep.clone());
         }
@@ -147,7 +277,8 @@ where
         ancestors.push(current.clone());
 
         for dep in get_dependencies(&current) {
-            if !visited.contains(&dep) {
+            let dep = dep.borrow();
+            if !visited.contains(dep) {
                 queue.push_back(dep.clone());
                 visited.insert(dep.clone());
             }
@@ -182,11 +313,27 @@ pub fn compute_blast_radius_fn<Id, F>(
     get_dependencies: F,
 ) -> (Vec<Id>, Vec<Id>)
 where
-    Id: Clone + Eq + Hash,
+    Id: Clone + IdBound,
     F: Fn(&Id) -> Vec<Id> + Clone,
 {
-    let ancestors = compute_ancestors_fn(items, start, get_dependencies.clone());
-    let descendants = compute_descendants_fn(items, start, get_dependencies);
+    compute_blast_radius_iter_fn(items, start, get_dependencies)
+}
+
+/// [`compute_blast_radius_fn`] for closures that return any iterable of
+/// dependencies, owned or borrowed.
+pub fn compute_blast_radius_iter_fn<Id, F, I>(
+    items: &[Id],
+    start: &Id,
+    get_dependencies: F,
+) -> (Vec<Id>, Vec<Id>)
+where
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> I + Clone,
+    I: IntoIterator,
+    I::Item: Borrow<Id>,
+{
+    let ancestors = compute_ancestors_iter_fn(items, start, get_dependencies.clone());
+    let descendants = compute_descendants_iter_fn(items, start, get_dependencies);
     (ancestors, descendants)
 }
 
@@ -221,7 +368,7 @@ where
 /// ```
 pub trait ImpactAnalyzable {
     /// The type of identifiers in the graph.
-    type Id: Clone + Eq + Hash;
+    type Id: Clone + IdBound;
 
     /// Get all node IDs in the graph.
     fn get_all_ids(&self) -> Vec<Self::Id>;
@@ -256,6 +403,253 @@ pub trait ImpactAnalyzable {
     fn dependency_count(&self, start: &Self::Id) -> usize {
         self.compute_ancestors(start).len()
     }
+
+    /// Total blast-radius size: ancestors plus descendants.
+    fn blast_radius_size(&self, start: &Self::Id) -> usize {
+        let (ancestors, descendants) = self.compute_blast_radius(start);
+        ancestors.len() + descendants.len()
+    }
+}
+
+/// A reusable index for answering many impact queries against one graph.
+///
+/// [`compute_descendants_fn`] and friends rebuild their dependency index on
+/// every call. `ImpactIndex` builds the forward and reverse adjacency once,
+/// then memoizes each node's descendant and ancestor sets the first time they
+/// are asked for, so repeated queries return cached slices.
+///
+/// Unlike [`compute_ancestors_fn`], results only contain ids from `items`;
+/// dependencies outside `items` are ignored. Set orders match the free
+/// functions: breadth-first, closest first.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::layout::generic::impact::ImpactIndex;
+///
+/// let get_deps = |pkg: &&str| match *pkg {
+///     "app" => vec!["lib-a", "lib-b"],
+///     "lib-a" => vec!["core"],
+///     "lib-b" => vec!["core"],
+///     _ => vec![],
+/// };
+///
+/// let packages = ["app", "lib-a", "lib-b", "core"];
+/// let index = ImpactIndex::new(&packages, get_deps);
+///
+/// assert_eq!(index.descendants(&"core"), &["lib-a", "lib-b", "app"]);
+/// assert_eq!(index.ancestors(&"app"), &["lib-a", "lib-b", "core"]);
+///
+/// let (ancestors, descendants) = index.blast_radius(&"lib-a");
+/// assert_eq!(ancestors, &["core"]);
+/// assert_eq!(descendants, &["app"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ImpactIndex<Id> {
+    items: Vec<Id>,
+    positions: HashMap<Id, usize>,
+    deps: Vec<Vec<usize>>,       // deps[i] = positions items[i] depends on
+    dependents: Vec<Vec<usize>>, // dependents[i] = positions depending on items[i]
+    descendants: Vec<OnceCell<Vec<Id>>>, // Memoized per position
+    ancestors: Vec<OnceCell<Vec<Id>>>, // Memoized per position
+}
+
+impl<Id: Clone + IdBound> ImpactIndex<Id> {
+    /// Build an index over `items`, calling `get_dependencies` once per item.
+    ///
+    /// No impact sets are computed yet; see [`precompute`](Self::precompute).
+    pub fn new<F>(items: &[Id], get_dependencies: F) -> Self
+    where
+        F: Fn(&Id) -> Vec<Id>,
+    {
+        let index = DependencyIndex::build(items, get_dependencies);
+        let dependents = items
+            .iter()
+            .map(|id| index.dependents_of(id).to_vec())
+            .collect();
+        Self::from_adjacency(items.to_vec(), index.deps, dependents)
+    }
+
+    fn from_adjacency(items: Vec<Id>, deps: Vec<Vec<usize>>, dependents: Vec<Vec<usize>>) -> Self {
+        let positions = items
+            .iter()
+            .enumerate()
+            .map(|(idx, id)| (id.clone(), idx))
+            .collect();
+        let len = items.len();
+        Self {
+            items,
+            positions,
+            deps,
+            dependents,
+            descendants: (0..len).map(|_| OnceCell::new()).collect(),
+            ancestors: (0..len).map(|_| OnceCell::new()).collect(),
+        }
+    }
+
+    /// Replace the whole graph, dropping every cached set.
+    ///
+    /// Equivalent to building a new index, but reuses this binding.
+    pub fn rebuild<F>(&mut self, items: &[Id], get_dependencies: F)
+    where
+        F: Fn(&Id) -> Vec<Id>,
+    {
+        *self = Self::new(items, get_dependencies);
+    }
+
+    /// Compute and cache the impact sets of every item up front.
+    ///
+    /// Takes O(V * (V + E)); worthwhile when most nodes will be queried.
+    pub fn precompute(&self) {
+        for idx in 0..self.items.len() {
+            self.descendants_at(idx);
+            self.ancestors_at(idx);
+        }
+    }
+
+    /// Number of items in the index.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// `true` if the index has no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// All items that (transitively) depend on `id`.
+    ///
+    /// Empty if `id` is not one of the indexed items.
+    pub fn descendants(&self, id: &Id) -> &[Id] {
+        self.positions
+            .get(id)
+            .map_or(&[], |&idx| self.descendants_at(idx))
+    }
+
+    /// All items that `id` (transitively) depends on.
+    ///
+    /// Empty if `id` is not one of the indexed items.
+    pub fn ancestors(&self, id: &Id) -> &[Id] {
+        self.positions
+            .get(id)
+            .map_or(&[], |&idx| self.ancestors_at(idx))
+    }
+
+    /// Ancestors and descendants of `id`, like [`compute_blast_radius_fn`].
+    pub fn blast_radius(&self, id: &Id) -> (&[Id], &[Id]) {
+        (self.ancestors(id), self.descendants(id))
+    }
+
+    /// Replace the dependencies of `id` and invalidate only the cached sets the
+    /// change can affect.
+    ///
+    /// Dependencies outside the indexed items are ignored. Returns `false`
+    /// (and changes nothing) if `id` is not one of the indexed items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::layout::generic::impact::ImpactIndex;
+    ///
+    /// let get_deps = |&id: &usize| if id == 3 { vec![2] } else { vec![] };
+    /// let mut index = ImpactIndex::new(&[1, 2, 3], get_deps);
+    /// assert!(index.descendants(&1).is_empty());
+    ///
+    /// // 2 now depends on 1
+    /// index.set_dependencies(&2, &[1]);
+    /// assert_eq!(index.descendants(&1), &[2, 3]);
+    /// ```
+    pub fn set_dependencies(&mut self, id: &Id, dependencies: &[Id]) -> bool {
+        let Some(&idx) = self.positions.get(id) else {
+            return false;
+        };
+
+        // Nodes whose descendant sets include `id` before or after the change
+        let mut stale = reachable(idx, &self.deps);
+        for &dep in &self.deps[idx] {
+            self.dependents[dep].retain(|&d| d != idx);
+        }
+        self.deps[idx] = dependencies
+            .iter()
+            .filter_map(|dep| self.positions.get(dep).copied())
+            .collect();
+        for &dep in &self.deps[idx] {
+            self.dependents[dep].push(idx);
+        }
+        stale.extend(reachable(idx, &self.deps));
+        for pos in stale {
+            self.descendants[pos].take();
+        }
+
+        // Ancestor sets that pass through `id`: its own and its dependents'
+        self.ancestors[idx].take();
+        for pos in reachable(idx, &self.dependents) {
+            self.ancestors[pos].take();
+        }
+        true
+    }
+
+    fn descendants_at(&self, idx: usize) -> &[Id] {
+        self.descendants[idx].get_or_init(|| self.collect(idx, &self.dependents))
+    }
+
+    fn ancestors_at(&self, idx: usize) -> &[Id] {
+        self.ancestors[idx].get_or_init(|| self.collect(idx, &self.deps))
+    }
+
+    fn collect(&self, idx: usize, adjacency: &[Vec<usize>]) -> Vec<Id> {
+        reachable(idx, adjacency)
+            .into_iter()
+            .map(|pos| self.items[pos].clone())
+            .collect()
+    }
+}
+
+/// Positions reachable from `start` along `adjacency`, breadth-first.
+/// `start` itself is only included if it lies on a cycle.
+fn reachable(start: usize, adjacency: &[Vec<usize>]) -> Vec<usize> {
+    let mut visited = vec![false; adjacency.len()];
+    let mut order = Vec::new();
+    let mut queue: VecDeque<usize> = adjacency[start].iter().copied().collect();
+
+    while let Some(current) = queue.pop_front() {
+        if visited[current] {
+            continue;
+        }
+        visited[current] = true;
+        order.push(current);
+        queue.extend(adjacency[current].iter().copied());
+    }
+
+    order
+}
+
+impl<'a> DAG<'a> {
+    /// An [`ImpactIndex`] over this graph's node IDs, built straight from the
+    /// cached adjacency lists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(
+    ///     &[(1, "A"), (2, "B"), (3, "C")],
+    ///     &[(1, 2), (2, 3)]
+    /// );
+    ///
+    /// let index = dag.impact_index();
+    /// // Children depend on their parents
+    /// assert_eq!(index.descendants(&1), &[2, 3]);
+    /// assert_eq!(index.ancestors(&3), &[2, 1]);
+    /// ```
+    pub fn impact_index(&self) -> ImpactIndex<usize> {
+        ImpactIndex::from_adjacency(
+            self.nodes.iter().map(|&(id, _)| id).collect(),
+            self.parents.clone(),
+            self.children.clone(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -380,5 +774,76 @@ mod tests {
         assert_eq!(graph.compute_ancestors(&3).len(), 2);
         assert_eq!(graph.impact_count(&1), 2);
         assert_eq!(graph.dependency_count(&3), 2);
+        assert_eq!(graph.blast_radius_size(&2), 2); // 1 above, 3 below
+        assert_eq!(graph.blast_radius_size(&1), 2);
+    }
+
+    #[test]
+    fn test_descendants_within_distances() {
+        let get_deps = |&id: &usize| match id {
+            2 => vec![1],
+            3 => vec![2],
+            4 => vec![3],
+            _ => vec![],
+        };
+
+        let items = [1, 2, 3, 4];
+        let all = compute_descendants_within_fn(&items, &1, get_deps, usize::MAX);
+        assert_eq!(all, vec![(2, 1), (3, 2), (4, 3)]);
+
+        let near = compute_descendants_within_fn(&items, &1, get_deps, 2);
+        assert_eq!(near, vec![(2, 1), (3, 2)]);
+
+        assert!(compute_descendants_within_fn(&items, &1, get_deps, 0).is_empty());
+    }
+
+    #[test]
+    fn test_impact_index_matches_free_functions() {
+        // Layered graph: item i depends on i/2 and i/3
+        let get_deps = |&id: &usize| {
+            if id == 0 {
+                vec![]
+            } else {
+                vec![id / 2, id / 3]
+            }
+        };
+        let items: Vec<usize> = (0..60).collect();
+        let index = ImpactIndex::new(&items, get_deps);
+        index.precompute();
+
+        for id in &items {
+            assert_eq!(
+                index.descendants(id),
+                compute_descendants_fn(&items, id, get_deps)
+            );
+            assert_eq!(
+                index.ancestors(id),
+                compute_ancestors_fn(&items, id, get_deps)
+            );
+        }
+        assert!(index.descendants(&99).is_empty());
+        assert_eq!(index.len(), 60);
+    }
+
+    #[test]
+    fn test_impact_index_set_dependencies() {
+        let mut deps: Vec<Vec<usize>> = vec![vec![], vec![0], vec![1], vec![], vec![3]];
+        let items = [0, 1, 2, 3, 4];
+        let mut index = ImpactIndex::new(&items, |&id| deps[id].clone());
+        index.precompute();
+
+        // Move 1 from under 0 to under 4
+        deps[1] = vec![4];
+        assert!(index.set_dependencies(&1, &deps[1]));
+        let fresh = ImpactIndex::new(&items, |&id| deps[id].clone());
+        for id in &items {
+            assert_eq!(index.descendants(id), fresh.descendants(id));
+            assert_eq!(index.ancestors(id), fresh.ancestors(id));
+        }
+        assert!(!index.set_dependencies(&7, &[]));
+
+        index.rebuild(&[0, 1], |&id| if id == 1 { vec![0] } else { vec![] });
+        assert_eq!(index.descendants(&0), &[1]);
+        assert!(index.descendants(&4).is_empty());
     }
 }
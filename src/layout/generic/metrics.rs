@@ -20,16 +20,19 @@
 //!
 //! assert_eq!(metrics.node_count(), 4);
 //! assert_eq!(metrics.edge_count(), 4);
-//! // Max depth varies based on path (deploy has ancestors [build, compile] or [test, compile])
-//! assert!(metrics.max_depth() >= 2);
+//! // compile → build → deploy: three levels, two edges
+//! assert_eq!(metrics.longest_chain_len(), 3);
+//! assert_eq!(metrics.max_depth(), 2);
 //! ```
 
-use alloc::vec::Vec;
-use core::hash::Hash;
+use alloc::collections::VecDeque;
+use alloc::{vec, vec::Vec};
+use core::borrow::Borrow;
+use core::fmt;
 
-use super::impact::compute_ancestors_fn;
-use super::impact::compute_descendants_fn;
-use crate::cycles::generic::roots::find_roots_fn;
+use super::DependencyIndex;
+use crate::cycles::generic::IdBound;
+use crate::graph::DAG;
 
 /// Statistical metrics for a dependency graph.
 ///
@@ -52,15 +55,31 @@ use crate::cycles::generic::roots::find_roots_fn;
 /// println!("Max depth: {}", metrics.max_depth());
 /// println!("Avg dependencies: {:.2}", metrics.avg_dependencies());
 /// ```
+///
+/// With the `serde` feature, `GraphMetrics` implements `Serialize` with the
+/// stored counts as fields (`node_count`, `edge_count`, …, `path_count`,
+/// `heaviest_blast_radius`); [`to_json`](Self::to_json) writes the same fields without
+/// any dependency. Derived values such as density are left out.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct GraphMetrics {
     node_count: usize,
     edge_count: usize,
     root_count: usize,
     leaf_count: usize,
-    max_depth: usize,
+    longest_chain_len: usize,
     max_descendants: usize,
     total_dependencies: usize,
+    component_count: usize,
+    max_fan_in: usize,
+    max_fan_out: usize,
+    placeholder_count: usize,
+    level_widths: Vec<usize>,
+    path_count: Option<u128>,
+    avg_path_length: Option<f64>,
+    total_weight: u64,
+    max_weighted_depth: u64,
+    heaviest_blast_radius: u64,
 }
 
 impl GraphMetrics {
@@ -84,61 +103,163 @@ impl GraphMetrics {
     /// ```
     pub fn compute<Id, F>(items: &[Id], get_dependencies: F) -> Self
     where
-        Id: Clone + Eq + Hash,
+        Id: Clone + IdBound,
         F: Fn(&Id) -> Vec<Id> + Clone,
+    {
+        Self::compute_weighted_iter(items, get_dependencies, |_| 1)
+    }
+
+    /// [`compute`](Self::compute) for closures that return any iterable of
+    /// dependencies, owned or borrowed.
+    ///
+    /// Use this when the dependency lists already exist to avoid building a
+    /// `Vec` per closure call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::layout::generic::metrics::GraphMetrics;
+    ///
+    /// // deps[i] = what item i depends on
+    /// let deps: Vec<Vec<usize>> = vec![vec![], vec![0], vec![0, 1]];
+    /// let metrics = GraphMetrics::compute_iter(&[0, 1, 2], |&id| &deps[id]);
+    /// assert_eq!(metrics.edge_count(), 3);
+    /// assert_eq!(metrics.max_depth(), 2);
+    /// ```
+    pub fn compute_iter<Id, F, I>(items: &[Id], get_dependencies: F) -> Self
+    where
+        Id: Clone + IdBound,
+        F: Fn(&Id) -> I,
+        I: IntoIterator,
+        I::Item: Borrow<Id>,
+    {
+        Self::compute_weighted_iter(items, get_dependencies, |_| 1)
+    }
+
+    /// Compute metrics for a graph whose nodes have sizes, such as build
+    /// times or binary sizes.
+    ///
+    /// Adds [`total_weight`](Self::total_weight),
+    /// [`max_weighted_depth`](Self::max_weighted_depth) (the critical path by
+    /// weight), and [`heaviest_blast_radius`](Self::heaviest_blast_radius) to
+    /// the usual metrics, from the same index: the critical path takes one DP
+    /// over the topological order, the blast radius one traversal per node,
+    /// as [`max_descendants`](Self::max_descendants) already does. `weight` is
+    /// called once per item. [`compute`](Self::compute) is this with every
+    /// weight 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::layout::generic::metrics::GraphMetrics;
+    ///
+    /// let get_deps = |file: &&str| match *file {
+    ///     "app.exe" => vec!["main.o", "utils.o"],
+    ///     "main.o" => vec!["main.c", "types.h"],
+    ///     "utils.o" => vec!["utils.c", "types.h"],
+    ///     _ => vec![],
+    /// };
+    /// // Seconds to produce each file
+    /// let build_time = |file: &&str| match *file {
+    ///     "app.exe" => 4,
+    ///     "main.o" => 12,
+    ///     "utils.o" => 30,
+    ///     _ => 0,
+    /// };
+    ///
+    /// let files = ["app.exe", "main.o", "utils.o", "main.c", "utils.c", "types.h"];
+    /// let metrics = GraphMetrics::compute_weighted(&files, get_deps, build_time);
+    ///
+    /// assert_eq!(metrics.total_weight(), 46);
+    /// // utils.o → app.exe bounds any parallel build
+    /// assert_eq!(metrics.max_weighted_depth(), 34);
+    /// // Touching types.h rebuilds everything
+    /// assert_eq!(metrics.heaviest_blast_radius(), 46);
+    /// ```
+    pub fn compute_weighted<Id, F, W>(items: &[Id], get_dependencies: F, weight: W) -> Self
+    where
+        Id: Clone + IdBound,
+        F: Fn(&Id) -> Vec<Id>,
+        W: Fn(&Id) -> u64,
+    {
+        Self::compute_weighted_iter(items, get_dependencies, weight)
+    }
+
+    /// [`compute_weighted`](Self::compute_weighted) for closures that return
+    /// any iterable of dependencies, owned or borrowed.
+    pub fn compute_weighted_iter<Id, F, I, W>(items: &[Id], get_dependencies: F, weight: W) -> Self
+    where
+        Id: Clone + IdBound,
+        F: Fn(&Id) -> I,
+        I: IntoIterator,
+        I::Item: Borrow<Id>,
+        W: Fn(&Id) -> u64,
     {
         let node_count = items.len();
+        let weights: Vec<u64> = items.iter().map(weight).collect();
+
+        // Single pass over the closure; everything below works on indices
+        let index = DependencyIndex::build(items, get_dependencies);
 
         // Count edges and total dependencies
-        let mut edge_count = 0;
-        let mut total_dependencies = 0;
-        for item in items {
-            let deps = get_dependencies(item);
-            let dep_count = deps.len();
-            edge_count += dep_count;
-            total_dependencies += dep_count;
-        }
+        let edge_count: usize = index.dep_counts.iter().sum();
+        let total_dependencies = edge_count;
 
-        // Find roots and leaves
-        let roots = find_roots_fn(items, get_dependencies.clone());
-        let root_count = roots.len();
-
-        let mut leaf_count = 0;
-        for candidate in items {
-            let mut is_leaf = true;
-            for item in items {
-                if get_dependencies(item).contains(candidate) {
-                    is_leaf = false;
-                    break;
-                }
-            }
-            if is_leaf {
-                leaf_count += 1;
-            }
-        }
+        // Roots have no dependencies, leaves have no dependents
+        let root_count = index.dep_counts.iter().filter(|&&n| n == 0).count();
+        let leaf_count = items
+            .iter()
+            .filter(|item| index.dependents_of(item).is_empty())
+            .count();
 
-        // Calculate max depth (longest path from any root)
-        let mut max_depth = 0;
-        for item in items {
-            let ancestors = compute_ancestors_fn(items, item, get_dependencies.clone());
-            max_depth = max_depth.max(ancestors.len());
-        }
+        // Reverse adjacency by position, releasing a repeated id's dependents
+        // from its first copy only
+        let dependents: Vec<&[usize]> = (0..node_count)
+            .map(|idx| index.dependents_at(items, idx))
+            .collect();
 
-        // Calculate max descendants (most impactful node)
-        let mut max_descendants = 0;
-        for item in items {
-            let descendants = compute_descendants_fn(items, item, get_dependencies.clone());
-            max_descendants = max_descendants.max(descendants.len());
-        }
+        // Longest chain, level widths, and paths by DPs over the topological
+        // order, max descendants (most impactful node) with one BFS per node
+        let order = topological_order(&index.deps, |i| dependents[i]);
+        let chain = order
+            .as_deref()
+            .map(|order| chain_lengths(order, |i| dependents[i]))
+            .unwrap_or_default();
+        let longest_chain_len = chain.iter().copied().max().unwrap_or(0);
+        let mut visited = vec![usize::MAX; node_count];
+        let (max_descendants, heaviest_blast_radius) = (0..node_count)
+            .map(|idx| count_reachable(idx, |i| dependents[i], &weights, &mut visited, idx))
+            .fold((0, 0), |(count, weight), (c, w)| {
+                (count.max(c), weight.max(w))
+            });
+
+        let component_count = count_components(&index.deps);
+        let paths = order
+            .as_deref()
+            .map(|order| path_stats(order, &index.deps, |i| dependents[i]));
+        let max_fan_in = index.dep_counts.iter().copied().max().unwrap_or(0);
+        let max_fan_out = dependents.iter().map(|d| d.len()).max().unwrap_or(0);
 
         Self {
             node_count,
             edge_count,
             root_count,
             leaf_count,
-            max_depth,
+            longest_chain_len,
             max_descendants,
             total_dependencies,
+            component_count,
+            max_fan_in,
+            max_fan_out,
+            placeholder_count: 0,
+            level_widths: level_widths(&chain),
+            path_count: paths.map(|(count, _)| count),
+            avg_path_length: paths.map(|(_, avg)| avg),
+            total_weight: weights.iter().fold(0, |sum: u64, &w| sum.saturating_add(w)),
+            max_weighted_depth: order.as_deref().map_or(0, |order| {
+                heaviest_chain(order, |i| dependents[i], &weights)
+            }),
+            heaviest_blast_radius,
         }
     }
 
@@ -162,9 +283,37 @@ impl GraphMetrics {
         self.leaf_count
     }
 
-    /// Maximum depth (longest dependency chain from a root).
+    /// Maximum depth: the number of edges on the longest dependency chain
+    /// from a root, i.e. [`longest_chain_len`](Self::longest_chain_len) - 1.
+    ///
+    /// A diamond `A → B, A → C, B → D, C → D` has depth 2, however many
+    /// ancestors `D` has. 0 for empty and cyclic graphs.
     pub fn max_depth(&self) -> usize {
-        self.max_depth
+        self.longest_chain_len.saturating_sub(1)
+    }
+
+    /// Number of nodes (levels) on the longest root-to-leaf chain.
+    ///
+    /// Matches [`DAG::depth`] for the same graph. 0 for empty and cyclic
+    /// graphs, since a cycle has no longest chain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::layout::generic::metrics::GraphMetrics;
+    ///
+    /// // 1 → 2 → 4 and 1 → 3 → 4: three levels whichever way you go
+    /// let get_deps = |&id: &usize| match id {
+    ///     2 | 3 => vec![1],
+    ///     4 => vec![2, 3],
+    ///     _ => vec![],
+    /// };
+    /// let metrics = GraphMetrics::compute(&[1, 2, 3, 4], get_deps);
+    /// assert_eq!(metrics.longest_chain_len(), 3);
+    /// assert_eq!(metrics.max_depth(), 2);
+    /// ```
+    pub fn longest_chain_len(&self) -> usize {
+        self.longest_chain_len
     }
 
     /// Maximum number of descendants any single node has.
@@ -205,6 +354,209 @@ impl GraphMetrics {
         self.edge_count == self.node_count.saturating_sub(self.root_count)
     }
 
+    /// Number of connected components, treating edges as undirected.
+    ///
+    /// Dependencies on ids outside `items` are ignored.
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+
+    /// Check if the graph is a single connected component (edges treated as undirected).
+    ///
+    /// A disconnected dependency graph often means orphaned nodes that nothing
+    /// links to. An empty graph counts as connected.
+    pub fn is_connected(&self) -> bool {
+        self.component_count <= 1
+    }
+
+    /// Most dependencies declared by a single node (edges into it).
+    pub fn max_fan_in(&self) -> usize {
+        self.max_fan_in
+    }
+
+    /// Most dependents of a single node (edges out of it).
+    pub fn max_fan_out(&self) -> usize {
+        self.max_fan_out
+    }
+
+    /// Number of auto-created placeholder nodes.
+    ///
+    /// Only [`DAG::metrics`] can tell placeholders apart; metrics from
+    /// [`compute`](Self::compute) always report 0.
+    pub fn placeholder_count(&self) -> usize {
+        self.placeholder_count
+    }
+
+    /// Number of nodes on each level, where a node's level is the length of
+    /// the longest dependency chain leading to it (roots are level 0).
+    ///
+    /// This is the same layering as [`max_depth`](Self::max_depth), so its
+    /// length is [`longest_chain_len`](Self::longest_chain_len). Each entry is
+    /// how many nodes could run concurrently once the previous levels finish.
+    /// Empty for empty and cyclic graphs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::layout::generic::metrics::GraphMetrics;
+    ///
+    /// // 1 → 2 → 4 and 1 → 3 → 4
+    /// let get_deps = |&id: &usize| match id {
+    ///     2 | 3 => vec![1],
+    ///     4 => vec![2, 3],
+    ///     _ => vec![],
+    /// };
+    /// let metrics = GraphMetrics::compute(&[1, 2, 3, 4], get_deps);
+    /// assert_eq!(metrics.levels(), &[1, 2, 1]);
+    /// assert_eq!(metrics.max_level_width(), 2);
+    /// assert_eq!(metrics.widest_level(), Some(1));
+    /// ```
+    pub fn levels(&self) -> &[usize] {
+        &self.level_widths
+    }
+
+    /// Most nodes on a single level (0 for empty and cyclic graphs).
+    pub fn max_level_width(&self) -> usize {
+        self.level_widths.iter().copied().max().unwrap_or(0)
+    }
+
+    /// Index of the widest level, the first one if several tie.
+    pub fn widest_level(&self) -> Option<usize> {
+        let max = self.max_level_width();
+        self.level_widths.iter().position(|&width| width == max)
+    }
+
+    /// Average number of nodes per level.
+    pub fn avg_level_width(&self) -> f64 {
+        if self.level_widths.is_empty() {
+            0.0
+        } else {
+            self.node_count as f64 / self.level_widths.len() as f64
+        }
+    }
+
+    /// Number of distinct root-to-leaf paths, a measure of how many ways the
+    /// graph can be explained: every diamond doubles the paths through it.
+    ///
+    /// An isolated node counts as one path. Saturates at `u128::MAX`;
+    /// `None` for cyclic graphs. See [`count_paths_fn`].
+    pub fn path_count(&self) -> Option<u128> {
+        self.path_count
+    }
+
+    /// Mean number of edges on a root-to-leaf path, where roots have no
+    /// dependencies, leaves have no dependents, and every distinct path (as
+    /// counted by [`path_count`](Self::path_count)) weighs the same.
+    ///
+    /// Where [`max_depth`](Self::max_depth) gives the deepest path, this tells
+    /// deep graphs from flat ones with one deep branch. An isolated node is a
+    /// path of length 0. Found by a DP over the topological order without
+    /// enumerating paths. `None` for cyclic graphs, 0 for the empty graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::layout::generic::metrics::GraphMetrics;
+    ///
+    /// // 1 → 2 → 3 and 1 → 3: one path of 2 edges, one of 1
+    /// let get_deps = |&id: &usize| match id {
+    ///     2 => vec![1],
+    ///     3 => vec![1, 2],
+    ///     _ => vec![],
+    /// };
+    /// let metrics = GraphMetrics::compute(&[1, 2, 3], get_deps);
+    /// assert_eq!(metrics.max_depth(), 2);
+    /// assert_eq!(metrics.avg_path_length(), Some(1.5));
+    /// ```
+    pub fn avg_path_length(&self) -> Option<f64> {
+        self.avg_path_length
+    }
+
+    /// The stored metrics as a single-line JSON object, byte for byte what
+    /// `serde_json` writes for the `serde` serialization. `path_count` and
+    /// `avg_path_length` are `null` for cyclic graphs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::layout::generic::metrics::GraphMetrics;
+    ///
+    /// let metrics = GraphMetrics::compute(&[1, 2], |&id: &usize| if id == 2 { vec![1] } else { vec![] });
+    /// let json = metrics.to_json();
+    /// assert!(json.starts_with(r#"{"node_count":2,"edge_count":1,"#));
+    /// assert!(json.contains(r#""level_widths":[1,1],"path_count":1,"#));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_json(&self) -> String {
+        use core::fmt::Write;
+
+        let mut json = format!(
+            concat!(
+                r#"{{"node_count":{},"edge_count":{},"root_count":{},"leaf_count":{},"#,
+                r#""longest_chain_len":{},"max_descendants":{},"total_dependencies":{},"#,
+                r#""component_count":{},"max_fan_in":{},"max_fan_out":{},"#,
+                r#""placeholder_count":{},"level_widths":["#
+            ),
+            self.node_count,
+            self.edge_count,
+            self.root_count,
+            self.leaf_count,
+            self.longest_chain_len,
+            self.max_descendants,
+            self.total_dependencies,
+            self.component_count,
+            self.max_fan_in,
+            self.max_fan_out,
+            self.placeholder_count,
+        );
+        for (i, width) in self.level_widths.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write!(json, "{}", width).ok();
+        }
+        match self.path_count {
+            Some(paths) => write!(json, r#"],"path_count":{}"#, paths).ok(),
+            None => write!(json, r#"],"path_count":null"#).ok(),
+        };
+        // `{:?}` keeps the `.0` serde_json writes; JSON has no NaN or inf
+        match self.avg_path_length {
+            Some(avg) if avg.is_finite() => write!(json, r#","avg_path_length":{:?}"#, avg).ok(),
+            _ => write!(json, r#","avg_path_length":null"#).ok(),
+        };
+        write!(
+            json,
+            r#","total_weight":{},"max_weighted_depth":{},"heaviest_blast_radius":{}}}"#,
+            self.total_weight, self.max_weighted_depth, self.heaviest_blast_radius
+        )
+        .ok();
+        json
+    }
+
+    /// Sum of all node weights (the node count for unweighted metrics).
+    ///
+    /// Saturates at `u64::MAX`. See [`compute_weighted`](Self::compute_weighted).
+    pub fn total_weight(&self) -> u64 {
+        self.total_weight
+    }
+
+    /// Heaviest dependency chain by summed node weight: the critical path,
+    /// e.g. the shortest possible build time with unlimited parallelism.
+    ///
+    /// Equals [`longest_chain_len`](Self::longest_chain_len) for unweighted
+    /// metrics; 0 for empty and cyclic graphs.
+    pub fn max_weighted_depth(&self) -> u64 {
+        self.max_weighted_depth
+    }
+
+    /// Largest summed weight of any node's descendants: the most work a single
+    /// change can trigger.
+    ///
+    /// Equals [`max_descendants`](Self::max_descendants) for unweighted metrics.
+    pub fn heaviest_blast_radius(&self) -> u64 {
+        self.heaviest_blast_radius
+    }
+
     /// Check if the graph is sparse (few edges relative to nodes).
     pub fn is_sparse(&self) -> bool {
         self.density() < 0.1
@@ -216,10 +568,325 @@ impl GraphMetrics {
     }
 }
 
+/// A human-readable report, one metric per line.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::layout::generic::metrics::GraphMetrics;
+///
+/// let metrics = GraphMetrics::compute(&[1, 2], |&id: &usize| if id == 2 { vec![1] } else { vec![] });
+/// let report = metrics.to_string();
+/// assert!(report.starts_with("Nodes: 2\nEdges: 1\n"));
+/// assert!(report.contains("\nMax depth: 1\n"));
+/// ```
+impl fmt::Display for GraphMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Nodes: {}", self.node_count)?;
+        writeln!(f, "Edges: {}", self.edge_count)?;
+        writeln!(f, "Roots: {}", self.root_count)?;
+        writeln!(f, "Leaves: {}", self.leaf_count)?;
+        writeln!(f, "Max depth: {}", self.max_depth())?;
+        writeln!(f, "Max descendants: {}", self.max_descendants)?;
+        writeln!(f, "Avg dependencies: {:.2}", self.avg_dependencies())?;
+        writeln!(f, "Density: {:.2}%", self.density() * 100.0)?;
+        writeln!(f, "Components: {}", self.component_count)?;
+        writeln!(f, "Max fan-in: {}", self.max_fan_in)?;
+        writeln!(f, "Max fan-out: {}", self.max_fan_out)?;
+        writeln!(f, "Placeholders: {}", self.placeholder_count)?;
+        writeln!(f, "Level widths: {:?}", self.level_widths)?;
+        match (self.path_count, self.avg_path_length) {
+            (Some(paths), Some(avg)) => {
+                writeln!(f, "Paths: {}", paths)?;
+                writeln!(f, "Avg path length: {:.2}", avg)
+            }
+            _ => writeln!(f, "Paths: none (cyclic)"),
+        }
+    }
+}
+
+/// Count nodes reachable from `start` (excluding itself) by BFS over index
+/// adjacency, and sum their weights.
+///
+/// `visited` is shared between calls; `stamp` must be unique per call so the
+/// buffer never needs clearing.
+fn count_reachable<'g>(
+    start: usize,
+    next: impl Fn(usize) -> &'g [usize],
+    weights: &[u64],
+    visited: &mut [usize],
+    stamp: usize,
+) -> (usize, u64) {
+    let mut queue = VecDeque::new();
+    visited[start] = stamp;
+    queue.push_back(start);
+
+    let mut count = 0;
+    let mut weight: u64 = 0;
+    while let Some(idx) = queue.pop_front() {
+        for &n in next(idx) {
+            if visited[n] != stamp {
+                visited[n] = stamp;
+                count += 1;
+                weight = weight.saturating_add(weights[n]);
+                queue.push_back(n);
+            }
+        }
+    }
+    (count, weight)
+}
+
+/// Positions in Kahn's order, or `None` if a cycle leaves nodes unordered.
+///
+/// `deps[i]` are the positions `i` depends on and `dependents(i)` the
+/// positions depending on `i`, so each edge is seen once from each side.
+fn topological_order<'g>(
+    deps: &[Vec<usize>],
+    dependents: impl Fn(usize) -> &'g [usize],
+) -> Option<Vec<usize>> {
+    let mut in_degree: Vec<usize> = deps.iter().map(Vec::len).collect();
+    let mut queue: VecDeque<usize> = (0..deps.len()).filter(|&i| in_degree[i] == 0).collect();
+    let mut order = Vec::with_capacity(deps.len());
+
+    while let Some(idx) = queue.pop_front() {
+        order.push(idx);
+        for &next in dependents(idx) {
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    (order.len() == deps.len()).then_some(order)
+}
+
+/// Number of nodes on the longest chain ending at each position, by a DP over
+/// a [`topological_order`].
+fn chain_lengths<'g>(order: &[usize], dependents: impl Fn(usize) -> &'g [usize]) -> Vec<usize> {
+    let mut chain = vec![1; order.len()];
+    for &idx in order {
+        for &next in dependents(idx) {
+            chain[next] = chain[next].max(chain[idx] + 1);
+        }
+    }
+    chain
+}
+
+/// Heaviest chain weight, by the same DP as [`chain_lengths`] with each node
+/// counting its weight instead of 1. Sums saturate at `u64::MAX`.
+fn heaviest_chain<'g>(
+    order: &[usize],
+    dependents: impl Fn(usize) -> &'g [usize],
+    weights: &[u64],
+) -> u64 {
+    let mut best = weights.to_vec();
+    for &idx in order {
+        for &next in dependents(idx) {
+            best[next] = best[next].max(best[idx].saturating_add(weights[next]));
+        }
+    }
+    best.into_iter().max().unwrap_or(0)
+}
+
+/// Count root-to-leaf paths by a DP over a [`topological_order`]: a root has
+/// one path to itself, every other node the sum over its dependencies, and the
+/// total is the sum over leaves. Sums saturate at `u128::MAX`.
+///
+/// Alongside, returns the mean path length in edges. Each node also carries
+/// the summed length of the paths reaching it, which grows by one per path
+/// along every edge. These run in `f64` so the mean stays meaningful once the
+/// exact count saturates.
+fn path_stats<'g>(
+    order: &[usize],
+    deps: &[Vec<usize>],
+    dependents: impl Fn(usize) -> &'g [usize],
+) -> (u128, f64) {
+    let mut paths: Vec<u128> = deps.iter().map(|d| u128::from(d.is_empty())).collect();
+    let mut approx: Vec<f64> = paths.iter().map(|&p| p as f64).collect();
+    let mut lengths = vec![0.0; deps.len()];
+    let mut total: u128 = 0;
+    let (mut approx_total, mut length_total) = (0.0, 0.0);
+
+    for &idx in order {
+        let next_nodes = dependents(idx);
+        if next_nodes.is_empty() {
+            total = total.saturating_add(paths[idx]);
+            approx_total += approx[idx];
+            length_total += lengths[idx];
+        }
+        for &next in next_nodes {
+            paths[next] = paths[next].saturating_add(paths[idx]);
+            approx[next] += approx[idx];
+            lengths[next] += lengths[idx] + approx[idx];
+        }
+    }
+
+    let avg = if approx_total > 0.0 {
+        length_total / approx_total
+    } else {
+        0.0
+    };
+    (total, avg)
+}
+
+/// Count the distinct root-to-leaf paths in a dependency graph.
+///
+/// Roots are items with no dependencies (within `items`) and leaves are items
+/// nothing depends on; an isolated item is a path of its own. Runs in O(V + E)
+/// by a DP over the topological order, calling `get_dependencies` once per
+/// item. The count grows exponentially with stacked diamonds, so it saturates
+/// at `u128::MAX` instead of overflowing.
+///
+/// Returns `None` if the graph has a cycle.
+///
+/// # Examples
+///
+/// ```
+/// use ascii_dag::layout::generic::metrics::count_paths_fn;
+///
+/// // Diamond: 1 → 2 → 4 and 1 → 3 → 4
+/// let get_deps = |&id: &usize| match id {
+///     2 | 3 => vec![1],
+///     4 => vec![2, 3],
+///     _ => vec![],
+/// };
+/// assert_eq!(count_paths_fn(&[1, 2, 3, 4], get_deps), Some(2));
+///
+/// let cyclic = |&id: &usize| vec![3 - id];
+/// assert_eq!(count_paths_fn(&[1, 2], cyclic), None);
+/// ```
+pub fn count_paths_fn<Id, F>(items: &[Id], get_dependencies: F) -> Option<u128>
+where
+    Id: Clone + IdBound,
+    F: Fn(&Id) -> Vec<Id>,
+{
+    let index = DependencyIndex::build(items, get_dependencies);
+    let dependents = |i: usize| index.dependents_at(items, i);
+    let order = topological_order(&index.deps, dependents)?;
+    Some(path_stats(&order, &index.deps, dependents).0)
+}
+
+/// Histogram of chain lengths: entry `k` counts the nodes on level `k`.
+fn level_widths(chain: &[usize]) -> Vec<usize> {
+    let mut widths = vec![0; chain.iter().copied().max().unwrap_or(0)];
+    for &len in chain {
+        widths[len - 1] += 1;
+    }
+    widths
+}
+
+impl<'a> DAG<'a> {
+    /// Metrics for this graph, read straight from the cached adjacency lists.
+    ///
+    /// Counts, fan-in/fan-out, placeholders, components, `max_depth`, and level
+    /// widths take O(V + E): `max_depth` is the number of edges on the longest
+    /// path, found by a DP over the topological order (0 for cyclic graphs). Only
+    /// `max_descendants` needs one traversal per node. Much faster than
+    /// [`GraphMetrics::compute`] with a closure over the DAG.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(
+    ///     &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+    ///     &[(1, 2), (1, 3), (2, 4), (3, 4)]
+    /// );
+    /// dag.add_edge(4, 5); // 5 becomes a placeholder
+    ///
+    /// let metrics = dag.metrics();
+    /// assert_eq!(metrics.node_count(), 5);
+    /// assert_eq!(metrics.edge_count(), 5);
+    /// assert_eq!(metrics.max_depth(), 3);
+    /// assert_eq!(metrics.max_fan_out(), 2);
+    /// assert_eq!(metrics.max_fan_in(), 2);
+    /// assert_eq!(metrics.placeholder_count(), 1);
+    /// ```
+    pub fn metrics(&self) -> GraphMetrics {
+        let node_count = self.nodes.len();
+        let edge_count = self.edges.len();
+
+        let weights = vec![1; node_count];
+        let mut visited = vec![usize::MAX; node_count];
+        let max_descendants = (0..node_count)
+            .map(|idx| count_reachable(idx, |i| &self.children[i], &weights, &mut visited, idx).0)
+            .max()
+            .unwrap_or(0);
+        let order = topological_order(&self.parents, |i| &self.children[i]);
+        let chain = order
+            .as_deref()
+            .map(|order| chain_lengths(order, |i| &self.children[i]))
+            .unwrap_or_default();
+        let paths = order
+            .as_deref()
+            .map(|order| path_stats(order, &self.parents, |i| &self.children[i]));
+
+        GraphMetrics {
+            node_count,
+            edge_count,
+            root_count: self.root_count(),
+            leaf_count: self.leaf_count(),
+            longest_chain_len: self.depth(),
+            max_descendants,
+            total_dependencies: edge_count,
+            component_count: count_components(&self.parents),
+            max_fan_in: self.parents.iter().map(Vec::len).max().unwrap_or(0),
+            max_fan_out: self.children.iter().map(Vec::len).max().unwrap_or(0),
+            placeholder_count: self.auto_created.len(),
+            level_widths: level_widths(&chain),
+            path_count: paths.map(|(count, _)| count),
+            avg_path_length: paths.map(|(_, avg)| avg),
+            total_weight: node_count as u64,
+            max_weighted_depth: chain.iter().copied().max().unwrap_or(0) as u64,
+            heaviest_blast_radius: max_descendants as u64,
+        }
+    }
+}
+
+/// Count weakly connected components with union-find.
+///
+/// `deps[i]` lists the positions `i` is linked to (one direction suffices).
+fn count_components(deps: &[Vec<usize>]) -> usize {
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]]; // Path halving
+            i = parent[i];
+        }
+        i
+    }
+
+    let mut parent: Vec<usize> = (0..deps.len()).collect();
+    let mut components = deps.len();
+
+    for (idx, links) in deps.iter().enumerate() {
+        for &dep_idx in links {
+            let a = find(&mut parent, idx);
+            let b = find(&mut parent, dep_idx);
+            if a != b {
+                parent[a] = b;
+                components -= 1;
+            }
+        }
+    }
+
+    components
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_repeated_item() {
+        let get_deps = |&id: &u32| if id == 2 { vec![1] } else { vec![] };
+        let metrics = GraphMetrics::compute(&[1u32, 1, 2], get_deps);
+        assert_eq!(metrics.node_count(), 3);
+        assert_eq!(metrics.longest_chain_len(), 2);
+        assert_eq!(count_paths_fn(&[1u32, 1, 2], get_deps), Some(2));
+    }
+
     #[test]
     fn test_simple_chain() {
         let get_deps = |&id: &usize| match id {
@@ -257,10 +924,9 @@ mod tests {
         assert_eq!(metrics.edge_count(), 4);
         assert_eq!(metrics.root_count(), 1);
         assert_eq!(metrics.leaf_count(), 1);
-        // Max depth is 2: node 4 has ancestors [2, 3, 1] = 3 total ancestors
-        // But depth is number of levels, not number of ancestors
-        // 1 is at depth 0, 2/3 at depth 1, 4 at depth 2
-        assert!(metrics.max_depth() >= 2);
+        // Node 4 has three ancestors, but 1 → 2 → 4 is only two edges long
+        assert_eq!(metrics.max_depth(), 2);
+        assert_eq!(metrics.longest_chain_len(), 3);
         assert_eq!(metrics.max_descendants(), 3);
         assert!(!metrics.is_tree()); // Diamond has 4 edges, tree would have 3
     }
@@ -324,4 +990,326 @@ mod tests {
         // Total deps: 0 + 1 + 2 = 3, avg = 3/3 = 1.0
         assert_eq!(metrics.avg_dependencies(), 1.0);
     }
+
+    #[test]
+    fn test_component_count() {
+        let get_deps = |&id: &usize| match id {
+            2 => vec![1],
+            4 => vec![3],
+            _ => vec![],
+        };
+
+        let metrics = GraphMetrics::compute(&[1, 2, 3, 4, 5], get_deps);
+        assert_eq!(metrics.component_count(), 3); // {1,2}, {3,4}, {5}
+        assert!(!metrics.is_connected());
+
+        let get_deps = |&id: &usize| match id {
+            2 => vec![1],
+            3 => vec![1],
+            _ => vec![],
+        };
+        let metrics = GraphMetrics::compute(&[1, 2, 3], get_deps);
+        assert_eq!(metrics.component_count(), 1);
+        assert!(metrics.is_connected());
+    }
+
+    #[test]
+    fn test_dag_metrics_match_compute() {
+        let dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D"), (5, "E")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        let fast = dag.metrics();
+
+        let ids: Vec<usize> = dag.nodes.iter().map(|&(id, _)| id).collect();
+        let slow = GraphMetrics::compute(&ids, |&id| dag.get_parents(id));
+
+        assert_eq!(fast.node_count(), slow.node_count());
+        assert_eq!(fast.edge_count(), slow.edge_count());
+        assert_eq!(fast.root_count(), slow.root_count());
+        assert_eq!(fast.leaf_count(), slow.leaf_count());
+        assert_eq!(fast.max_descendants(), slow.max_descendants());
+        assert_eq!(fast.component_count(), slow.component_count());
+        assert_eq!(fast.max_fan_in(), slow.max_fan_in());
+        assert_eq!(fast.max_fan_out(), slow.max_fan_out());
+
+        // Longest chain 1 → 2 → 4, in edges
+        assert_eq!(fast.max_depth(), 2);
+        assert_eq!(fast.max_depth(), slow.max_depth());
+        assert_eq!(fast.longest_chain_len(), slow.longest_chain_len());
+        assert_eq!(fast.placeholder_count(), 0);
+        assert_eq!(fast.levels(), slow.levels());
+        assert_eq!(fast.total_weight(), slow.total_weight());
+        assert_eq!(fast.max_weighted_depth(), slow.max_weighted_depth());
+        assert_eq!(fast.heaviest_blast_radius(), slow.heaviest_blast_radius());
+    }
+
+    #[test]
+    fn test_longest_chain_wide_fan_in() {
+        // Many short paths into one node used to count as a deep chain
+        let get_deps = |&id: &usize| if id == 10 { (1..10).collect() } else { vec![] };
+        let items: Vec<usize> = (1..=10).collect();
+        let metrics = GraphMetrics::compute(&items, get_deps);
+        assert_eq!(metrics.longest_chain_len(), 2);
+        assert_eq!(metrics.max_depth(), 1);
+
+        let get_deps = |&id: &usize| match id {
+            1 => vec![2],
+            2 => vec![1],
+            _ => vec![],
+        };
+        let metrics = GraphMetrics::compute(&[1, 2], get_deps);
+        assert_eq!(metrics.longest_chain_len(), 0, "cyclic");
+        assert_eq!(metrics.max_depth(), 0);
+        assert!(metrics.levels().is_empty());
+    }
+
+    #[test]
+    fn test_level_widths() {
+        // Extreme divergence from the stress test: 1 → 8
+        let edges: Vec<(usize, usize)> = (2..=9).map(|child| (1, child)).collect();
+        let mut dag = DAG::new();
+        for &(from, to) in &edges {
+            dag.add_edge(from, to);
+        }
+        let metrics = dag.metrics();
+        assert_eq!(metrics.levels(), &[1, 8]);
+        assert_eq!(metrics.max_level_width(), 8);
+        assert_eq!(metrics.widest_level(), Some(1));
+        assert_eq!(metrics.avg_level_width(), 4.5);
+
+        // Complex multi-layer DAG from the stress test
+        let dag = DAG::from_edges(
+            &[
+                (1, "L1A"),
+                (2, "L1B"),
+                (3, "L1C"),
+                (4, "L2A"),
+                (5, "L2B"),
+                (6, "L3A"),
+                (7, "L3B"),
+                (8, "L3C"),
+                (9, "Final"),
+            ],
+            &[
+                (1, 4),
+                (2, 4),
+                (3, 5),
+                (4, 6),
+                (4, 7),
+                (5, 7),
+                (5, 8),
+                (6, 9),
+                (7, 9),
+                (8, 9),
+            ],
+        );
+        let metrics = dag.metrics();
+        assert_eq!(metrics.levels(), &[3, 2, 3, 1]);
+        assert_eq!(metrics.max_level_width(), 3);
+        assert_eq!(metrics.widest_level(), Some(0));
+
+        let ids: Vec<usize> = dag.nodes.iter().map(|&(id, _)| id).collect();
+        let slow = GraphMetrics::compute(&ids, |&id| dag.get_parents(id));
+        assert_eq!(slow.levels(), &[3, 2, 3, 1]);
+
+        let empty = GraphMetrics::compute(&[] as &[usize], |_| vec![]);
+        assert_eq!(empty.widest_level(), None);
+        assert_eq!(empty.avg_level_width(), 0.0);
+    }
+
+    #[test]
+    fn test_path_count() {
+        let chain = |&id: &usize| if id > 1 { vec![id - 1] } else { vec![] };
+        assert_eq!(count_paths_fn(&[1, 2, 3, 4], chain), Some(1));
+
+        let diamond = |&id: &usize| match id {
+            2 | 3 => vec![1],
+            4 => vec![2, 3],
+            _ => vec![],
+        };
+        let metrics = GraphMetrics::compute(&[1, 2, 3, 4], diamond);
+        assert_eq!(metrics.path_count(), Some(2));
+
+        // Diamond within diamond from the stress test
+        let dag = DAG::from_edges(
+            &[
+                (1, "Root"),
+                (2, "L1"),
+                (3, "R1"),
+                (4, "L2"),
+                (5, "R2"),
+                (6, "L3"),
+                (7, "R3"),
+                (8, "Merge1"),
+                (9, "Merge2"),
+            ],
+            &[
+                (1, 2),
+                (1, 3),
+                (2, 4),
+                (2, 5),
+                (3, 6),
+                (3, 7),
+                (4, 8),
+                (5, 8),
+                (6, 9),
+                (7, 9),
+            ],
+        );
+        assert_eq!(dag.metrics().path_count(), Some(4));
+
+        // 200 stacked diamonds: 2^200 paths saturate
+        let stacked = |&id: &usize| match id {
+            0 => vec![],
+            id if id % 3 == 0 => vec![id - 2, id - 1],
+            id => vec![id - id % 3],
+        };
+        let items: Vec<usize> = (0..=600).collect();
+        assert_eq!(count_paths_fn(&items, stacked), Some(u128::MAX));
+
+        let cyclic = |&id: &usize| vec![3 - id];
+        assert_eq!(GraphMetrics::compute(&[1, 2], cyclic).path_count(), None);
+        assert_eq!(count_paths_fn(&[] as &[usize], chain), Some(0));
+    }
+
+    #[test]
+    fn test_metrics_report() {
+        let get_deps = |&id: &usize| match id {
+            2 | 3 => vec![1],
+            4 => vec![2, 3],
+            _ => vec![],
+        };
+        let metrics = GraphMetrics::compute(&[1, 2, 3, 4], get_deps);
+
+        assert_eq!(
+            metrics.to_string(),
+            "Nodes: 4\n\
+             Edges: 4\n\
+             Roots: 1\n\
+             Leaves: 1\n\
+             Max depth: 2\n\
+             Max descendants: 3\n\
+             Avg dependencies: 1.00\n\
+             Density: 33.33%\n\
+             Components: 1\n\
+             Max fan-in: 2\n\
+             Max fan-out: 2\n\
+             Placeholders: 0\n\
+             Level widths: [1, 2, 1]\n\
+             Paths: 2\n\
+             Avg path length: 2.00\n"
+        );
+
+        #[cfg(feature = "std")]
+        assert_eq!(
+            metrics.to_json(),
+            concat!(
+                r#"{"node_count":4,"edge_count":4,"root_count":1,"leaf_count":1,"#,
+                r#""longest_chain_len":3,"max_descendants":3,"total_dependencies":4,"#,
+                r#""component_count":1,"max_fan_in":2,"max_fan_out":2,"#,
+                r#""placeholder_count":0,"level_widths":[1,2,1],"path_count":2,"#,
+                r#""avg_path_length":2.0,"total_weight":4,"max_weighted_depth":3,"heaviest_blast_radius":3}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_weighted_metrics() {
+        // Diamond 1 → {2, 3} → 4 where the 3 branch is slow
+        let get_deps = |&id: &usize| match id {
+            2 | 3 => vec![1],
+            4 => vec![2, 3],
+            _ => vec![],
+        };
+        let weight = |&id: &usize| [0, 5, 1, 20, 2][id];
+        let metrics = GraphMetrics::compute_weighted(&[1, 2, 3, 4], get_deps, weight);
+
+        assert_eq!(metrics.total_weight(), 28);
+        assert_eq!(metrics.max_weighted_depth(), 27); // 1 → 3 → 4
+        assert_eq!(metrics.heaviest_blast_radius(), 23); // Descendants of 1
+        assert_eq!(metrics.longest_chain_len(), 3);
+
+        // Unweighted metrics count every node as 1
+        let plain = GraphMetrics::compute(&[1, 2, 3, 4], get_deps);
+        assert_eq!(plain.total_weight(), 4);
+        assert_eq!(plain.max_weighted_depth(), 3);
+        assert_eq!(plain.heaviest_blast_radius(), 3);
+
+        // Sums saturate
+        let heavy = GraphMetrics::compute_weighted(&[1, 2, 3, 4], get_deps, |_| u64::MAX);
+        assert_eq!(heavy.total_weight(), u64::MAX);
+        assert_eq!(heavy.max_weighted_depth(), u64::MAX);
+
+        let cyclic = |&id: &usize| vec![3 - id];
+        let metrics = GraphMetrics::compute_weighted(&[1, 2], cyclic, |_| 7);
+        assert_eq!(metrics.max_weighted_depth(), 0);
+        assert_eq!(metrics.heaviest_blast_radius(), 7);
+    }
+
+    #[test]
+    fn test_avg_path_length() {
+        // Balanced binary tree of depth 3: every one of 8 paths has 3 edges
+        let tree = |&id: &usize| if id > 1 { vec![id / 2] } else { vec![] };
+        let items: Vec<usize> = (1..16).collect();
+        let metrics = GraphMetrics::compute(&items, tree);
+        assert_eq!(metrics.path_count(), Some(8));
+        assert_eq!(metrics.avg_path_length(), Some(3.0));
+        assert_eq!(metrics.max_depth(), 3);
+
+        // Skewed: a 4-edge chain 1 → … → 5 with a one-edge spur at each step
+        let skewed = |&id: &usize| match id {
+            2..=5 => vec![id - 1],
+            10..=13 => vec![id - 9],
+            _ => vec![],
+        };
+        let items = [1, 2, 3, 4, 5, 10, 11, 12, 13];
+        let metrics = GraphMetrics::compute(&items, skewed);
+        assert_eq!(metrics.max_depth(), 4);
+        // Spurs end after 1, 2, 3, and 4 edges; the chain after 4
+        assert_eq!(metrics.avg_path_length(), Some(14.0 / 5.0));
+
+        // The concrete-graph metrics agree
+        let mut dag = DAG::new();
+        for id in 2..16 {
+            dag.add_edge(id / 2, id);
+        }
+        assert_eq!(dag.metrics().avg_path_length(), Some(3.0));
+
+        // Isolated nodes are paths of length 0; cycles have no paths
+        let isolated = GraphMetrics::compute(&[1, 2], |_: &usize| vec![]);
+        assert_eq!(isolated.avg_path_length(), Some(0.0));
+        let cyclic = |&id: &usize| vec![3 - id];
+        assert_eq!(
+            GraphMetrics::compute(&[1, 2], cyclic).avg_path_length(),
+            None
+        );
+        let empty = GraphMetrics::compute(&[] as &[usize], |_| vec![]);
+        assert_eq!(empty.avg_path_length(), Some(0.0));
+    }
+
+    #[cfg(all(feature = "serde", feature = "std"))]
+    #[test]
+    fn test_to_json_matches_serde() {
+        let diamond = |&id: &usize| match id {
+            2 | 3 => vec![1],
+            4 => vec![2, 3],
+            _ => vec![],
+        };
+        let cyclic = |&id: &usize| if id == 1 { vec![2] } else { vec![1] };
+        let uneven = |&id: &usize| match id {
+            3 => vec![1, 2],
+            4 => vec![3],
+            5 => vec![2],
+            _ => vec![],
+        };
+        for metrics in [
+            GraphMetrics::compute(&[1, 2, 3, 4], diamond),
+            GraphMetrics::compute(&[1, 2], cyclic),
+            GraphMetrics::compute_weighted(&[1, 2, 3, 4, 5], uneven, |&id| id as u64),
+            GraphMetrics::compute(&[] as &[usize], diamond),
+        ] {
+            assert_eq!(serde_json::to_string(&metrics).unwrap(), metrics.to_json());
+        }
+    }
 }
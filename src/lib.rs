@@ -21,6 +21,9 @@
 //!
 //! - `std` (default): Standard library support
 //! - `generic` (default): Generic algorithms (cycle detection, topological sort, impact analysis, metrics)
+//! - `svg`: SVG export (`DAG::to_svg`)
+//! - `serde`: `Serialize` for `GraphMetrics`
+//! - `derive`: `#[derive(CycleDetectable)]` (implies `generic`)
 //! - `warnings`: Debug warnings for auto-created nodes
 //!
 //! To minimize bundle size, disable `generic`:
@@ -107,6 +110,12 @@
 //! ### [`layout`] - Graph Layout Algorithms
 //! Sugiyama hierarchical layout for positioning nodes.
 //!
+//! ### [`diff`] - Graph Diffs
+//! Added/removed/relabeled nodes and edges between two versions of a graph.
+//!
+//! ### [`validate`] - Graph Validation
+//! Cycles, self-loops, duplicate edges, unlabeled placeholders, and other issues in one report.
+//!
 //! ### [`render`] - ASCII Rendering
 //! Vertical, horizontal, and cycle visualization modes.
 
@@ -116,16 +125,18 @@ extern crate alloc;
 
 // Core modules (always available)
 pub mod cycles;
+pub mod diff;
 pub mod graph;
 pub mod layout;
 pub mod render;
+pub mod validate;
 
 // Backward compatibility re-exports
 pub use graph::{DAG, RenderMode};
 
 #[cfg(test)]
 mod tests {
-    use crate::graph::DAG;
+    use crate::graph::{DAG, RenderMode};
 
     #[test]
     fn test_empty_dag() {
@@ -375,4 +386,1656 @@ mod tests {
         assert!(a_pos < b_pos, "A should be left of B");
         assert!(b_pos < c_pos, "B should be left of C");
     }
+
+    #[test]
+    fn test_cause_chain_mode() {
+        let mut dag = DAG::from_edges(
+            &[
+                (1, "ConfigMissing"),
+                (2, "DBConnFail"),
+                (3, "AuthFail"),
+                (4, "InitError"),
+                (5, "StartupFail"),
+            ],
+            &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)],
+        );
+        dag.set_render_mode(RenderMode::CauseChain);
+
+        assert_eq!(
+            dag.render(),
+            "StartupFail\n\
+             Caused by:\n\
+             \x20 0: InitError (also caused by #3)\n\
+             \x20 1: DBConnFail\n\
+             \x20 2: ConfigMissing\n\
+             \x20 3: AuthFail (caused by #2)\n"
+        );
+    }
+
+    #[test]
+    fn test_cause_chain_one_block_per_leaf() {
+        let mut dag = DAG::new();
+        dag.add_node(1, "Root");
+        dag.add_node(2, "LeafA");
+        dag.add_node(3, "LeafB");
+        dag.add_edge(1, 2);
+        dag.add_edge(1, 3);
+        dag.add_edge(4, 3); // Auto-created cause
+        dag.set_render_mode(RenderMode::CauseChain);
+
+        let output = dag.render();
+        assert!(output.contains("LeafA\nCaused by:\n  0: Root\n"));
+        assert!(output.contains("LeafB (also caused by #1)\nCaused by:\n  0: Root\n  1: ⟨4⟩\n"));
+        assert!(!output.contains('['));
+    }
+
+    #[test]
+    fn test_render_ancestors_highlighted() {
+        let dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+
+        let plain = dag.render();
+        let output = dag.render_ancestors_highlighted(2);
+        assert!(output.contains("⟦A⟧"));
+        assert!(output.contains("⟦B⟧"));
+        assert!(output.contains("(C)"));
+        assert!(output.contains("(D)"));
+        // Same bracket widths, so the layout is unchanged
+        assert_eq!(output.lines().count(), plain.lines().count());
+
+        // Unknown node renders unmarked
+        assert_eq!(dag.render_ancestors_highlighted(99), plain);
+    }
+
+    #[test]
+    fn test_show_degrees() {
+        let mut dag = DAG::new();
+        dag.set_show_degrees(true);
+        dag.add_node(1, "Build");
+        dag.add_edge(1, 2);
+        dag.add_edge(1, 3);
+        dag.add_edge(4, 1);
+
+        let output = dag.render();
+        assert!(output.contains("[Build](1↓2↑)"));
+        assert!(output.contains("⟨2⟩(1↓0↑)"));
+        assert!(output.contains("⟨4⟩(0↓1↑)"));
+
+        // Cached widths include the suffix
+        let idx = dag.node_index(1).unwrap();
+        assert_eq!(dag.get_node_width(idx), "[Build](1↓2↑)".chars().count());
+
+        dag.set_show_degrees(false);
+        assert!(!dag.render().contains('↑'));
+        assert_eq!(dag.get_node_width(idx), "[Build]".chars().count());
+    }
+
+    #[test]
+    fn test_show_ids() {
+        let mut dag = DAG::from_edges(&[(1, "Build"), (10, "Test")], &[(1, 10), (10, 7)]);
+        dag.set_show_ids(true);
+
+        let output = dag.render();
+        assert!(output.contains("[1:Build]"), "{}", output);
+        assert!(output.contains("[10:Test]"), "{}", output);
+        assert!(output.contains("⟨7⟩"), "{}", output);
+
+        // Cached widths include the prefix
+        let idx = dag.node_index(10).unwrap();
+        assert_eq!(dag.get_node_width(idx), "[10:Test]".chars().count());
+
+        dag.set_show_ids(false);
+        assert!(dag.render().contains("[Test]"));
+        assert_eq!(dag.get_node_width(idx), "[Test]".chars().count());
+    }
+
+    #[test]
+    fn test_custom_arrows() {
+        let mut dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        let default_output = dag.render();
+        dag.set_arrows('v', '>');
+
+        let output = dag.render();
+        assert!(output.contains('v'));
+        assert!(!output.contains('↓'));
+        // Single-column replacement keeps every line the same width
+        let widths = |s: &str| s.lines().map(|l| l.chars().count()).collect::<Vec<_>>();
+        assert_eq!(widths(&output), widths(&default_output));
+
+        let mut cyclic = DAG::new();
+        cyclic.add_edge(1, 2);
+        cyclic.add_edge(2, 1);
+        cyclic.set_arrows('v', '>');
+        cyclic.set_cycle_arrow('=');
+        let output = cyclic.render();
+        assert!(output.contains("⟨1⟩ > ⟨2⟩ = ⟨1⟩"));
+    }
+
+    #[test]
+    fn test_level_gap() {
+        let mut dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        let default_output = dag.render();
+        dag.set_level_gap(0);
+        assert_eq!(dag.render(), default_output);
+
+        dag.set_level_gap(1);
+        let output = dag.render();
+        // Two connector blocks (3 levels), one blank row after each
+        assert_eq!(output.lines().count(), default_output.lines().count() + 2);
+        assert!(output.lines().any(|l| l.is_empty()));
+    }
+
+    #[test]
+    fn test_left_alignment() {
+        use crate::graph::Alignment;
+
+        let mut dag = DAG::from_edges(
+            &[(1, "Top"), (2, "Left"), (3, "Right"), (4, "Bottom")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        let centered = dag.render();
+        assert!(!centered.starts_with("[Top]"));
+
+        dag.set_alignment(Alignment::Left);
+        let output = dag.render();
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[0].starts_with("[Top]"));
+        assert!(
+            lines
+                .iter()
+                .find(|l| l.contains("[Bottom]"))
+                .unwrap()
+                .starts_with("[Bottom]")
+        );
+        // Connector rows are drawn in the same frame as the nodes
+        assert!(lines[1].starts_with(' ') || lines[1].starts_with('│'));
+    }
+
+    #[test]
+    fn test_center_alignment_recenters_levels() {
+        use crate::graph::Alignment;
+
+        let mut dag = DAG::from_edges(
+            &[
+                (1, "Root"),
+                (2, "Left"),
+                (3, "RightSide"),
+                (4, "C"),
+                (5, "D"),
+            ],
+            &[(1, 2), (1, 3), (3, 4), (4, 5)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+
+        // The median layout keeps the chain under RightSide...
+        let median = dag.render();
+        let indent = |output: &str, node: &str| {
+            let line = output.lines().find(|l| l.contains(node)).unwrap();
+            line.find(node).unwrap()
+        };
+        assert_eq!(
+            indent(&median, "[C]") + 1,
+            indent(&median, "[RightSide]") + 5
+        );
+
+        // ...while centering puts every level in the middle of the widest one
+        dag.set_alignment(Alignment::Center);
+        let (output, positions) = dag.render_with_positions();
+        let widest = "[Left]   [RightSide]".len();
+        assert_eq!(indent(&output, "[C]"), (widest - 3) / 2, "\n{}", output);
+        assert_eq!(indent(&output, "[D]"), (widest - 3) / 2, "\n{}", output);
+
+        // The connector still lands on the re-centered node
+        let lines: Vec<Vec<char>> = output.lines().map(|l| l.chars().collect()).collect();
+        let c = positions.iter().find(|p| p.id == 4).unwrap();
+        assert_eq!(
+            lines[c.row - 1].get(c.col + c.width / 2),
+            Some(&'↓'),
+            "\n{}",
+            output
+        );
+    }
+
+    /// Measure a rendered string the way `rendered_dimensions` reports it.
+    fn measure(output: &str) -> (usize, usize) {
+        let width = output.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+        (width, output.lines().count())
+    }
+
+    #[test]
+    fn test_rendered_dimensions_match_render() {
+        let mut graphs = vec![
+            DAG::new(),
+            DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C")], &[(1, 2), (2, 3)]),
+            DAG::from_edges(
+                &[(1, "Top"), (2, "Left"), (3, "Right"), (4, "Bottom")],
+                &[(1, 2), (1, 3), (2, 4), (3, 4)],
+            ),
+            DAG::from_edges(
+                &[(1, "E1"), (2, "E2"), (3, "E3"), (4, "Final")],
+                &[(1, 4), (2, 4), (3, 4)],
+            ),
+            // Disconnected components (stacked subgraph rendering)
+            DAG::from_edges(
+                &[(1, "A"), (2, "B"), (3, "C"), (4, "D"), (5, "Lonely")],
+                &[(1, 2), (1, 3), (3, 4)],
+            ),
+        ];
+
+        let mut cyclic = DAG::new();
+        cyclic.add_edge(1, 2);
+        cyclic.add_edge(2, 1);
+        graphs.push(cyclic);
+
+        let mut chain = graphs[2].clone();
+        chain.set_render_mode(RenderMode::CauseChain);
+        graphs.push(chain);
+
+        let mut gapped = graphs[2].clone();
+        gapped.set_level_gap(2);
+        graphs.push(gapped);
+
+        for dag in &graphs {
+            assert_eq!(dag.rendered_dimensions(), measure(&dag.render()));
+        }
+    }
+
+    #[test]
+    fn test_render_with_positions() {
+        let mut graphs = vec![
+            DAG::from_edges(
+                &[(1, "Top"), (2, "Left"), (3, "Right"), (4, "Bottom")],
+                &[(1, 2), (1, 3), (2, 4), (3, 4)],
+            ),
+            // Stacked subgraphs
+            DAG::from_edges(
+                &[(1, "A"), (2, "B"), (3, "C"), (4, "D"), (5, "E")],
+                &[(1, 2), (1, 3), (4, 5)],
+            ),
+        ];
+        let mut cyclic = DAG::new();
+        cyclic.add_node(1, "X");
+        cyclic.add_edge(1, 2);
+        cyclic.add_edge(2, 1);
+        graphs.push(cyclic);
+        let mut chain = graphs[0].clone();
+        chain.set_render_mode(RenderMode::CauseChain);
+        graphs.push(chain);
+
+        for dag in &graphs {
+            let (output, positions) = dag.render_with_positions();
+            assert_eq!(output, dag.render());
+            assert!(!positions.is_empty());
+
+            let lines: Vec<&str> = output.lines().collect();
+            for pos in &positions {
+                let text: String = lines[pos.row]
+                    .chars()
+                    .skip(pos.col)
+                    .take(pos.width)
+                    .collect();
+                let (_, label) = dag.nodes[dag.node_index(pos.id).unwrap()];
+                if label.is_empty() {
+                    assert!(text.contains(&pos.id.to_string()), "{:?}", text);
+                } else {
+                    assert!(text.contains(label), "{:?} vs {:?}", text, label);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_steps() {
+        let dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        let frames = dag.render_steps().unwrap();
+        assert_eq!(frames.len(), 5);
+
+        // Every frame has the same shape so it can be redrawn in place
+        let shape = |s: &str| s.lines().map(|l| l.chars().count()).collect::<Vec<_>>();
+        for frame in &frames {
+            assert_eq!(shape(frame), shape(&frames[0]));
+        }
+
+        assert!(frames[0].contains(" (A)") && !frames[0].contains('✓'));
+        assert!(frames[1].contains("✓[A]") && frames[1].contains(" (D)"));
+        assert_eq!(frames[4].matches('✓').count(), 4);
+
+        let mut cyclic = DAG::new();
+        cyclic.add_edge(1, 2);
+        cyclic.add_edge(2, 1);
+        assert!(cyclic.render_steps().is_err());
+    }
+
+    #[test]
+    fn test_render_pages() {
+        let nodes: Vec<(usize, &str)> = (0..30).map(|i| (i, "Step")).collect();
+        let edges: Vec<(usize, usize)> = (1..30).map(|i| (i - 1, i)).collect();
+        let mut dag = DAG::from_edges(&nodes, &edges);
+        dag.set_render_mode(RenderMode::Vertical);
+        let full = dag.render();
+
+        let pages = dag.render_pages(10);
+        assert!(pages.len() > 1);
+
+        let mut joined = String::new();
+        for (i, page) in pages.iter().enumerate() {
+            assert!(page.lines().count() <= 10);
+            let body = if i == 0 {
+                page.as_str()
+            } else {
+                let header = format!("── continued (page {}/{}) ──\n", i + 1, pages.len());
+                page.strip_prefix(header.as_str()).unwrap()
+            };
+            // Every page starts on a node row, never on a connector
+            assert!(body.trim_start().starts_with("[Step]"));
+            joined.push_str(body);
+        }
+        assert_eq!(joined, full);
+
+        assert_eq!(dag.render_pages(0), vec![full.clone()]);
+        assert_eq!(dag.render_pages(1000), vec![full]);
+    }
+
+    #[test]
+    fn test_render_pages_keep_headings_with_nodes() {
+        let mut dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 2), (2, 3), (3, 4)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        dag.set_title("Chain");
+        dag.set_group(&[3, 4], "late");
+
+        // At 4 rows the title alone would fill page 1; at 7 the group header
+        // would end page 2 with its node on page 3
+        for rows in [4, 7] {
+            let pages = dag.render_pages(rows);
+            assert!(
+                pages[0].starts_with("  Chain\n  ─────\n   [A]\n"),
+                "{:?}",
+                pages
+            );
+            for page in &pages {
+                let lines: Vec<&str> = page.lines().collect();
+                if let Some(i) = lines.iter().position(|l| l.starts_with("── late")) {
+                    assert_eq!(lines.get(i + 1), Some(&"late:[C]"), "{:?}", pages);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_isolated_nodes_compact() {
+        let nodes: Vec<(usize, &str)> = (0..20).map(|i| (i, "Node")).collect();
+        let dag = DAG::from_edges(&nodes, &[]);
+        let output = dag.render();
+
+        assert!(output.starts_with("isolated: [Node] [Node]"));
+        assert!(output.lines().count() <= 2, "{}", output);
+        assert_eq!(output.matches("[Node]").count(), 20);
+
+        // Connected subgraphs come first, isolated nodes after a blank line
+        let dag = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "Lone")], &[(1, 2)]);
+        let output = dag.render();
+        assert!(output.ends_with("\n\nisolated: [Lone]\n"), "{}", output);
+
+        let mut dag = DAG::from_edges(&nodes, &[]);
+        dag.set_compact_isolated(false);
+        assert_eq!(dag.render().lines().filter(|l| !l.is_empty()).count(), 20);
+    }
+
+    #[test]
+    fn test_parallel_edges_annotated() {
+        let mut dag = DAG::from_edges(
+            &[(1, "Root"), (2, "Left"), (3, "Right")],
+            &[(1, 2), (1, 2), (1, 2), (1, 3)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        let output = dag.render();
+        assert_eq!(output.matches("×3").count(), 1, "{}", output);
+        assert!(!output.contains("×1"));
+
+        // A chain with a doubled link stays horizontal
+        let dag = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C")], &[(1, 2), (1, 2), (2, 3)]);
+        assert_eq!(dag.render(), "[A] =2=> [B] → [C]\n");
+    }
+
+    #[test]
+    fn test_placeholder_style() {
+        let mut dag = DAG::new();
+        dag.add_node(1, "Root");
+        dag.add_node(3, "Right");
+        dag.add_edge(1, 2);
+        dag.add_edge(1, 3);
+        dag.set_render_mode(RenderMode::Vertical);
+
+        dag.set_placeholder_style("<?", ">");
+        let (output, positions) = dag.render_with_positions();
+        assert!(output.contains("<?2>"), "{}", output);
+        assert!(!output.contains('⟨'));
+
+        // Widths follow the bracket length so arrows stay centered on nodes
+        let line = |row: usize| output.lines().nth(row).unwrap().chars().collect::<Vec<_>>();
+        for pos in positions.iter().filter(|p| p.id != 1) {
+            assert_eq!(line(pos.row - 1)[pos.col + pos.width / 2], '↓');
+        }
+        let placeholder = positions.iter().find(|p| p.id == 2).unwrap();
+        assert_eq!(placeholder.width, 4);
+    }
+
+    #[test]
+    fn test_node_brackets_mixed_level() {
+        let mut dag = DAG::from_edges(
+            &[(1, "App"), (2, "Cause"), (3, "Lib"), (4, "Core")],
+            &[(1, 2), (1, 3), (1, 4)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        dag.set_node_brackets(2, "((", "))");
+        dag.set_node_brackets(3, "{", "}");
+
+        let (output, positions) = dag.render_with_positions();
+        assert!(output.contains("((Cause))   {Lib}   [Core]"), "{}", output);
+
+        // Every arrow lands on the center of its (differently bracketed) node
+        let lines: Vec<Vec<char>> = output.lines().map(|l| l.chars().collect()).collect();
+        for pos in positions.iter().filter(|p| p.id != 1) {
+            assert_eq!(lines[pos.row - 1][pos.col + pos.width / 2], '↓');
+        }
+
+        // Placeholders keep their reserved brackets until promoted
+        let mut dag = DAG::new();
+        dag.add_edge(1, 2);
+        dag.set_node_brackets(2, "{", "}");
+        assert!(dag.render().contains("⟨2⟩"));
+        dag.add_node(2, "Ext");
+        assert!(dag.render().contains("{Ext}"));
+    }
+
+    #[test]
+    fn test_render_with_labels_resolves_widths() {
+        let mut dag = DAG::new();
+        dag.add_node(1, "Root");
+        dag.add_edge(1, 2);
+        dag.add_edge(1, 3);
+        dag.set_render_mode(RenderMode::Vertical);
+
+        let output = dag.render_with_labels(|id| match id {
+            2 => Some("A much longer resolved label"),
+            _ => None,
+        });
+        let mut expected = DAG::from_edges(
+            &[(1, "Root"), (2, "A much longer resolved label")],
+            &[(1, 2), (1, 3)],
+        );
+        expected.set_render_mode(RenderMode::Vertical);
+        assert_eq!(output, expected.render());
+        assert!(output.contains("⟨3⟩"));
+    }
+
+    #[test]
+    fn test_hostile_labels_sanitized() {
+        let edges = [(1, 2), (1, 3), (1, 4), (2, 5), (3, 5), (4, 5)];
+        let mut hostile = DAG::from_edges(
+            &[
+                (1, "multi\nline"),
+                (2, "tab\there"),
+                (3, "\x1b[31mred\x1b[0m"),
+                (4, "bell\x07\r"),
+                (5, "ok"),
+            ],
+            &edges,
+        );
+        let mut clean = DAG::from_edges(
+            &[
+                (1, "multi␤line"),
+                (2, "tab␉here"),
+                (3, "red"),
+                (4, "bell"),
+                (5, "ok"),
+            ],
+            &edges,
+        );
+
+        for mode in [RenderMode::Vertical, RenderMode::CauseChain] {
+            hostile.set_render_mode(mode);
+            clean.set_render_mode(mode);
+            let output = hostile.render();
+            assert_eq!(output, clean.render());
+            assert!(!output.contains(|c: char| c.is_control() && c != '\n'));
+            assert_eq!(hostile.rendered_dimensions(), clean.rendered_dimensions());
+        }
+    }
+
+    #[test]
+    fn test_topological_sort_ties_by_insertion_order() {
+        // 9 and 4 are both roots; 9 was inserted first
+        let dag = DAG::from_edges(&[(9, "B"), (4, "A"), (7, "C")], &[(4, 7), (9, 7)]);
+        assert_eq!(dag.topological_sort(), Ok(vec![9, 4, 7]));
+
+        // Auto-created nodes are included
+        let mut dag = DAG::new();
+        dag.add_edge(5, 6);
+        assert_eq!(dag.topological_labels(), Ok(vec![(5, ""), (6, "")]));
+
+        let mut cyclic = DAG::new();
+        cyclic.add_edge(1, 2);
+        cyclic.add_edge(2, 3);
+        cyclic.add_edge(3, 1);
+        let cycle = cyclic.topological_sort().unwrap_err();
+        assert!(cycle.contains(&1) && cycle.contains(&2) && cycle.contains(&3));
+    }
+
+    #[test]
+    fn test_single_parent_children_straight_below() {
+        // The right branch is a chain: each link should sit under its parent
+        let mut dag = DAG::from_edges(
+            &[
+                (1, "Root"),
+                (2, "Left"),
+                (3, "RightSide"),
+                (4, "C"),
+                (5, "D"),
+            ],
+            &[(1, 2), (1, 3), (3, 4), (4, 5)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+
+        let (output, positions) = dag.render_with_positions();
+        let center = |id: usize| {
+            let pos = positions.iter().find(|p| p.id == id).unwrap();
+            pos.col + pos.width / 2
+        };
+        assert_eq!(center(4), center(3), "\n{}", output);
+        assert_eq!(center(5), center(4), "\n{}", output);
+
+        // The connector into each chain link lands on the link itself
+        let lines: Vec<Vec<char>> = output.lines().map(|l| l.chars().collect()).collect();
+        for id in [4, 5] {
+            let pos = positions.iter().find(|p| p.id == id).unwrap();
+            assert_eq!(
+                lines[pos.row - 1].get(center(id)),
+                Some(&'↓'),
+                "\n{}",
+                output
+            );
+        }
+    }
+
+    #[test]
+    fn test_horizontal_layered() {
+        let mut dag = DAG::from_edges(
+            &[
+                (1, "Fetch"),
+                (2, "Parse"),
+                (3, "Test"),
+                (4, "Build"),
+                (5, "Ship"),
+            ],
+            &[(1, 2), (2, 3), (2, 4), (3, 5), (4, 5)],
+        );
+        dag.set_render_mode(RenderMode::HorizontalLayered);
+
+        assert_eq!(
+            dag.render(),
+            "[Fetch] ─→ [Parse] ─┬─→ [Test] ──┬─→ [Ship]\n\
+             \x20                   │            │\n\
+             \x20                   └─→ [Build] ─┘\n"
+        );
+
+        let (output, positions) = dag.render_with_positions();
+        assert_eq!(output, dag.render());
+        assert_eq!(positions.len(), 5);
+    }
+
+    #[test]
+    fn test_ancestors_and_descendants() {
+        let dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D"), (5, "Lone")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+
+        let mut descendants = dag.descendants(1);
+        descendants.sort_unstable();
+        assert_eq!(descendants, vec![2, 3, 4]);
+
+        let mut ancestors = dag.ancestors(4);
+        ancestors.sort_unstable();
+        assert_eq!(ancestors, vec![1, 2, 3]);
+
+        assert!(dag.descendants(5).is_empty());
+        assert!(dag.ancestors(99).is_empty());
+        assert!(dag.descendants(99).is_empty());
+    }
+
+    #[test]
+    fn test_max_render_depth() {
+        let mut dag = DAG::from_edges(
+            &[(1, "Root"), (2, "L"), (3, "R"), (4, "LL"), (5, "Deep")],
+            &[(1, 2), (1, 3), (2, 4), (4, 5)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+
+        dag.set_max_render_depth(2);
+        let output = dag.render();
+        assert!(
+            output.contains("[L]") && output.contains("[R]"),
+            "{}",
+            output
+        );
+        assert!(!output.contains("[LL]"), "{}", output);
+        assert!(output.ends_with("… (2 more levels)\n"), "{}", output);
+
+        // A second component is cut at the same depth
+        dag.add_edge(10, 11);
+        dag.add_edge(11, 12);
+        dag.add_edge(10, 12);
+        let output = dag.render();
+        assert!(!output.contains("⟨12⟩"), "{}", output);
+        assert!(output.contains("… (1 more level)"), "{}", output);
+
+        dag.set_max_render_depth(0);
+        assert!(dag.render().contains("[Root]"));
+
+        dag.set_max_render_depth(usize::MAX);
+        assert!(dag.render().contains("[Deep]"));
+        assert!(!dag.render().contains('…'));
+    }
+
+    #[test]
+    fn test_path_between() {
+        let dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D"), (5, "E")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4), (1, 5), (5, 4)],
+        );
+
+        let path = dag.path_between(1, 4).unwrap();
+        assert_eq!(path.len(), 3);
+        assert_eq!((path[0], path[2]), (1, 4));
+        assert!(dag.has_edge(path[0], path[1]) && dag.has_edge(path[1], path[2]));
+
+        assert_eq!(dag.path_between(4, 1), None);
+        assert_eq!(dag.path_between(2, 3), None);
+        assert_eq!(dag.path_between(1, 99), None);
+        assert_eq!(dag.path_between(99, 1), None);
+        assert_eq!(dag.path_between(3, 3), Some(vec![3]));
+    }
+
+    #[test]
+    fn test_max_render_nodes() {
+        let mut dag = DAG::from_edges(
+            &[
+                (1, "Root"),
+                (2, "Mid"),
+                (3, "Side"),
+                (4, "LeafA"),
+                (5, "LeafB"),
+                (6, "LeafC"),
+            ],
+            &[(1, 2), (1, 3), (2, 4), (2, 5), (3, 6)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        dag.set_max_render_nodes(3);
+
+        let output = dag.render();
+        for kept in ["[Root]", "[Mid]", "[Side]"] {
+            assert!(output.contains(kept), "{}", output);
+        }
+        assert!(!output.contains("Leaf"), "{}", output);
+        assert_eq!(output.matches("⟨… +3 hidden⟩").count(), 1, "{}", output);
+
+        // Under the cap nothing changes
+        dag.set_max_render_nodes(6);
+        assert!(!dag.render().contains("hidden"));
+    }
+
+    #[test]
+    fn test_longest_path_and_depth() {
+        // Deep nesting from the stress test: a 10-level chain
+        let nodes: Vec<(usize, &str)> = (1..=10).map(|i| (i, "Level")).collect();
+        let edges: Vec<(usize, usize)> = (1..10).map(|i| (i, i + 1)).collect();
+        let mut dag = DAG::from_edges(&nodes, &edges);
+        assert_eq!(dag.depth(), 10);
+        assert_eq!(dag.longest_path(), (1..=10).collect::<Vec<_>>());
+
+        // A shortcut edge doesn't shorten the critical chain
+        dag.add_edge(1, 10);
+        assert_eq!(dag.depth(), 10);
+
+        assert_eq!(DAG::new().depth(), 0);
+        assert!(DAG::new().longest_path().is_empty());
+
+        dag.add_edge(10, 1);
+        assert_eq!(dag.depth(), 0);
+        assert!(dag.longest_path().is_empty());
+    }
+
+    #[test]
+    fn test_convergence_staircase() {
+        let nodes: Vec<(usize, &str)> = (1..=10)
+            .map(|i| (i, "Error"))
+            .chain([(11, "Fail")])
+            .collect();
+        let edges: Vec<(usize, usize)> = (1..=10).map(|i| (i, 11)).collect();
+        let mut dag = DAG::from_edges(&nodes, &edges);
+        dag.set_render_mode(RenderMode::Vertical);
+        let single = dag.render();
+
+        dag.set_max_convergence_span(usize::MAX);
+        assert_eq!(dag.render(), single);
+
+        dag.set_max_convergence_span(12);
+        let stepped = dag.render();
+        assert!(
+            stepped.lines().count() > single.lines().count(),
+            "{}",
+            stepped
+        );
+        assert_eq!(stepped.matches('↓').count(), 1, "{}", stepped);
+    }
+
+    #[test]
+    fn test_structural_equality_and_canonicalize() {
+        let mut batch = DAG::from_edges(
+            &[(1, "Top"), (2, "Left"), (3, "Right"), (4, "Bottom")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+
+        // Same diamond, built bottom-up with placeholders promoted later
+        let mut builder = DAG::new();
+        builder.add_edge(3, 4);
+        builder.add_edge(2, 4);
+        builder.add_node(4, "Bottom");
+        builder.add_edge(1, 3);
+        builder.add_edge(1, 2);
+        builder.add_node(3, "Right");
+        builder.add_node(1, "Top");
+        builder.add_node(2, "Left");
+        assert_eq!(batch, builder);
+
+        batch.canonicalize();
+        builder.canonicalize();
+        assert_eq!(batch, builder);
+        assert_eq!(batch.render(), builder.render());
+        assert_eq!(batch.topological_sort(), builder.topological_sort());
+
+        // Placeholder status participates
+        let mut placeholder = DAG::new();
+        placeholder.add_edge(1, 2);
+        let labeled_empty = DAG::from_edges(&[(1, ""), (2, "")], &[(1, 2)]);
+        assert_ne!(placeholder, labeled_empty);
+    }
+
+    #[test]
+    fn test_debug_format() {
+        let empty = DAG::with_mode(RenderMode::Vertical);
+        assert_eq!(
+            format!("{:?}", empty),
+            "DAG {\n    mode: Vertical\n    nodes (0):\n    edges (0):\n}"
+        );
+
+        // Labels are escaped, so every node stays on one line
+        let mut dag = DAG::new();
+        dag.add_node(7, "two\nlines");
+        assert!(format!("{:?}", dag).contains("        7: \"two\\nlines\"\n"));
+    }
+
+    #[test]
+    fn test_contract_diamond_middle() {
+        let mut dag = DAG::from_edges(
+            &[(1, "Top"), (2, "Left"), (3, "Right"), (4, "Bottom")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        dag.contract(&[2, 3], 5, "Middle");
+
+        assert_eq!(dag.edges, vec![(1, 5), (5, 4)]);
+        let mut chain = DAG::from_edges(
+            &[(1, "Top"), (5, "Middle"), (4, "Bottom")],
+            &[(1, 5), (5, 4)],
+        );
+        chain.set_render_mode(RenderMode::Vertical);
+        assert_eq!(dag.render(), chain.render());
+        assert_eq!(dag, chain);
+    }
+
+    #[test]
+    fn test_contract_can_create_cycle() {
+        // 2 → 3 → 4 with 2 and 4 merged becomes 5 ⇄ 3
+        let mut dag = DAG::from_edges(&[(2, "A"), (3, "B"), (4, "C")], &[(2, 3), (3, 4)]);
+        dag.contract(&[2, 4], 5, "AC");
+
+        assert!(dag.has_cycle());
+        assert!(dag.render().contains("CYCLE DETECTED"));
+    }
+
+    #[test]
+    fn test_render_padded_uniform_width() {
+        let mut dag = DAG::from_edges(
+            &[(1, "Root"), (2, "Left"), (3, "Right"), (4, "Join")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        let (width, height) = dag.rendered_dimensions();
+
+        let padded = dag.render_padded(width);
+        assert_eq!(padded.lines().count(), height);
+        assert!(padded.lines().all(|l| l.chars().count() == width));
+        assert_eq!(padded.replace(' ', ""), dag.render().replace(' ', ""));
+
+        let narrow = dag.render_padded(5);
+        assert!(narrow.lines().all(|l| l.chars().count() == 5));
+        assert!(narrow.contains('…'));
+    }
+
+    #[test]
+    fn test_render_padded_double_width_label() {
+        use crate::graph::str_width;
+
+        let dag = DAG::from_edges(&[(1, "日本"), (2, "B")], &[(1, 2)]);
+        // "[日本] → [B]": the two ideographs take two columns each
+        assert_eq!(dag.rendered_dimensions(), (12, 1));
+        assert_eq!(dag.render_padded(14), "[日本] → [B]  \n");
+        // A double-width character that doesn't fit before the `…` leaves a gap
+        assert_eq!(dag.render_padded(5), "[日 …\n");
+
+        let mut dag = DAG::from_edges(
+            &[(1, "Root"), (2, "日本語"), (3, "Right"), (4, "Join")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        let (width, height) = dag.rendered_dimensions();
+        let padded = dag.render_padded(width);
+        assert_eq!(padded.lines().count(), height);
+        assert!(padded.lines().all(|l| str_width(l) == width));
+    }
+
+    #[test]
+    fn test_group_headers() {
+        let mut dag = DAG::from_edges(
+            &[
+                (1, "Main"),
+                (2, "Button"),
+                (3, "Form"),
+                (4, "Api"),
+                (5, "Db"),
+            ],
+            &[(1, 2), (1, 3), (1, 4), (2, 5), (3, 5), (4, 5)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        dag.set_group(&[2, 3], "frontend");
+        dag.set_group(&[4, 5], "backend");
+        let output = dag.render();
+        let lines: Vec<&str> = output.lines().collect();
+
+        // One header row, above the first level with members, aligned to them
+        let header = lines
+            .iter()
+            .position(|l| l.contains("── frontend ──"))
+            .unwrap();
+        assert_eq!(output.matches("── backend ──").count(), 1);
+        assert!(lines[header].contains("── backend ──"));
+        let nodes = lines[header + 1];
+        assert!(nodes.contains("frontend:[Button]") && nodes.contains("backend:[Api]"));
+        let col = |line: &str, pat: &str| line[..line.find(pat).unwrap()].chars().count();
+        assert_eq!(col(lines[header], "──"), col(nodes, "frontend:"));
+        assert_eq!(col(lines[header], "── backend"), col(nodes, "backend:"));
+        assert!(output.contains("backend:[Db]"));
+
+        // Headers wider than their members are pushed apart instead of touching
+        let mut dag = DAG::from_edges(&[(1, "R"), (2, "A"), (3, "B")], &[(1, 2), (1, 3)]);
+        dag.set_render_mode(RenderMode::Vertical);
+        dag.set_group(&[2], "alpha");
+        dag.set_group(&[3], "beta");
+        assert!(dag.render().contains("── alpha ── ── beta ──"));
+    }
+
+    #[test]
+    fn test_subgraph_order_by_min_id() {
+        let nodes = [(1, "A"), (2, "B"), (3, "C"), (4, "D")];
+        let mut first = DAG::from_edges(&nodes, &[(1, 2), (3, 4)]);
+        let mut second = DAG::new();
+        second.add_edge(3, 4);
+        second.add_edge(1, 2);
+        for &(id, label) in nodes.iter().rev() {
+            second.add_node(id, label);
+        }
+        first.set_render_mode(RenderMode::Vertical);
+        second.set_render_mode(RenderMode::Vertical);
+
+        assert_eq!(first.render(), "[A] → [B]\n\n[C] → [D]\n");
+        assert_eq!(second.render(), first.render());
+    }
+
+    #[test]
+    fn test_theme_presets() {
+        use crate::render::ascii::Theme;
+
+        let mut dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        let light = dag.render();
+
+        // Same shape, different glyphs
+        let shape = |s: &str| s.lines().map(|l| l.chars().count()).collect::<Vec<_>>();
+        for (theme, glyphs) in [
+            (Theme::heavy(), "┃┏┓┗┛"),
+            (Theme::double(), "║╔╗╚╝⇓"),
+            (Theme::rounded(), "│╭╮╰╯"),
+        ] {
+            dag.set_theme(theme);
+            let output = dag.render();
+            assert_eq!(shape(&output), shape(&light));
+            for glyph in glyphs.chars() {
+                assert!(output.contains(glyph), "{} missing in\n{}", glyph, output);
+            }
+        }
+
+        // Horizontal chains and cycles use the theme's arrows too
+        let mut chain = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+        chain.set_theme(Theme::double());
+        assert_eq!(chain.render(), "[A] ⇒ [B]\n");
+        chain.add_edge(2, 1);
+        assert!(chain.render().contains("[A] ⇒ [B] ⇄ [A]"));
+    }
+
+    #[test]
+    fn test_find_subgraphs_long_chain() {
+        // Deep enough to overflow a recursive walk on a small stack
+        let n = 100_000;
+        let mut dag = DAG::new();
+        for i in 1..n {
+            dag.add_edge(i, i + 1);
+        }
+        dag.add_node(0, "Lone");
+
+        let subgraphs = dag.find_subgraphs();
+        assert_eq!(subgraphs.len(), 2);
+        assert_eq!(subgraphs[0], vec![n]); // ID 0 sorts first
+        assert_eq!(subgraphs[1].len(), n);
+        assert!(subgraphs[1].windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_render_markdown_fences() {
+        let mut dag = DAG::from_edges(&[(1, "run ```sh```"), (2, "B")], &[(1, 2)]);
+        let md = dag.render_markdown();
+        assert!(md.starts_with("````text\n"), "{}", md);
+        assert!(md.ends_with("\n````\n"));
+        assert!(md.contains(&dag.render()));
+
+        dag.add_node(3, "````");
+        assert!(dag.render_markdown().starts_with("`````text\n"));
+
+        // Title markup can't break out of the bold span
+        dag.set_title("*v2* `beta`");
+        assert!(
+            dag.render_markdown()
+                .starts_with("**\\*v2\\* \\`beta\\`**\n\n`````text\n")
+        );
+    }
+
+    #[test]
+    fn test_estimate_size_covers_wide_graphs() {
+        let labels: Vec<String> = (0..40).map(|i| format!("Service{}", i)).collect();
+        let mut dag = DAG::new();
+        dag.add_node(0, "Gateway");
+        for (i, label) in labels.iter().enumerate() {
+            dag.add_node(i + 1, label);
+            dag.add_edge(0, i + 1);
+            dag.add_edge(i + 1, 100);
+        }
+        dag.add_node(100, "Database");
+        dag.set_render_mode(RenderMode::Vertical);
+
+        let size = dag.estimate_size();
+        assert!(
+            size >= dag.render().len(),
+            "{} < {}",
+            size,
+            dag.render().len()
+        );
+        assert!(size < dag.render().len() * 8);
+
+        // Still covers a tall chain
+        let chain = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C")], &[(1, 2), (2, 3)]);
+        assert!(chain.estimate_size() >= chain.render().len());
+    }
+
+    #[test]
+    fn test_empty_label_is_not_placeholder() {
+        let mut dag = DAG::from_edges(&[(1, ""), (2, "X")], &[(1, 2)]);
+        dag.add_edge(2, 3);
+        assert_eq!(dag.render(), "[] → [X] → ⟨3⟩\n");
+
+        let idx = dag.node_index(1).unwrap();
+        assert_eq!(dag.get_node_width(idx), 2);
+    }
+
+    #[test]
+    fn test_from_edges_duplicate_ids_keep_first() {
+        let dag = DAG::from_edges(&[(1, "A"), (1, "B"), (2, "C")], &[(1, 2)]);
+        assert_eq!(dag.nodes, vec![(1, "A"), (2, "C")]);
+        assert_eq!(dag.node_index(2), Some(1));
+        assert_eq!(dag.get_children(1), vec![2]);
+        assert_eq!(dag.render(), "[A] → [C]\n");
+
+        let dag = DAG::from_edges(&[(1, "A"), (1, "B")], &[]);
+        assert_eq!(dag.nodes, vec![(1, "A")]);
+        assert!(dag.is_valid());
+    }
+
+    #[test]
+    fn test_upward_arrows_keep_layout() {
+        let mut dag = DAG::from_edges(
+            &[(1, "Top"), (2, "Left"), (3, "Right"), (4, "Bottom")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        let down = dag.render();
+        dag.set_upward_arrows(true);
+        let up = dag.render();
+
+        assert!(up.contains('↑'));
+        assert!(!up.contains('↓'));
+        assert_eq!(up.lines().count(), down.lines().count());
+        assert_eq!(
+            up.replace('↑', "│"),
+            down.replace('↓', "│"),
+            "only the arrowheads move"
+        );
+    }
+
+    #[test]
+    fn test_component_labels_number_headed_components() {
+        let mut dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "Lone"), (4, "X"), (5, "Y")],
+            &[(1, 2), (4, 5)],
+        );
+        assert!(!dag.render().contains("Component"));
+
+        dag.set_component_labels(true);
+        let output = dag.render();
+        assert_eq!(
+            dag.connected_components(),
+            vec![vec![1, 2], vec![3], vec![4, 5]]
+        );
+        // The isolated line has no header, so it doesn't use up a number
+        assert!(output.starts_with("── Component 1 ──\n[A] → [B]\n"));
+        assert!(output.contains("\n── Component 2 ──\n[X] → [Y]\n"));
+        assert!(!output.contains("Component 3"), "{}", output);
+        assert!(output.ends_with("isolated: [Lone]\n"));
+
+        // A single component needs no header
+        let mut single = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+        single.set_component_labels(true);
+        assert_eq!(single.render(), "[A] → [B]\n");
+    }
+
+    #[test]
+    fn test_title_heads_every_render() {
+        let modes = [
+            RenderMode::Auto,
+            RenderMode::Horizontal,
+            RenderMode::Vertical,
+            RenderMode::CauseChain,
+            RenderMode::HorizontalLayered,
+        ];
+        for mode in modes {
+            let mut dag = DAG::from_edges(
+                &[(1, "Fetch"), (2, "Parse"), (3, "Lint"), (4, "Build")],
+                &[(1, 2), (1, 3), (2, 4), (3, 4)],
+            );
+            dag.set_render_mode(mode);
+            let untitled = dag.render();
+            let width = untitled.lines().map(|l| l.chars().count()).max().unwrap();
+
+            dag.set_title("Build");
+            let output = dag.render();
+            let mut lines = output.lines();
+            let title = lines.next().unwrap();
+            let underline = lines.next().unwrap();
+            assert_eq!(title.trim_start(), "Build", "{:?}", mode);
+            assert_eq!(underline.trim_start(), "─────");
+            assert_eq!(title.len() - 5, (width - 5) / 2, "centered in {:?}", mode);
+            assert_eq!(output.split_once("─────\n").unwrap().1, untitled);
+
+            let (w, h) = dag.rendered_dimensions();
+            assert_eq!((w, h), (width, untitled.lines().count() + 2));
+        }
+
+        // Cycles and stacked subgraphs come after the title
+        let mut cyclic = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2), (2, 1)]);
+        cyclic.set_title("Loop");
+        assert!(cyclic.render().lines().nth(2).unwrap().contains("CYCLE"));
+
+        let mut stacked =
+            DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C"), (4, "D")], &[(1, 2), (3, 4)]);
+        stacked.set_title("Two");
+        assert_eq!(stacked.render(), "   Two\n   ───\n[A] → [B]\n\n[C] → [D]\n");
+
+        // Wide titles widen the canvas
+        let mut narrow = DAG::from_edges(&[(1, "A")], &[]);
+        narrow.set_title("A much longer title");
+        assert_eq!(narrow.rendered_dimensions(), (19, 3));
+        assert!(narrow.render().starts_with("A much longer title\n"));
+    }
+
+    #[test]
+    fn test_level_separators_between_levels() {
+        let mut dag = DAG::from_edges(
+            &[
+                (1, "Root"),
+                (2, "Left"),
+                (3, "Right"),
+                (4, "Join"),
+                (5, "Out"),
+            ],
+            &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)],
+        );
+        let plain = dag.render();
+        dag.set_level_separators(true);
+        let output = dag.render();
+
+        let separators: Vec<&str> = output.lines().filter(|l| l.starts_with('┄')).collect();
+        assert_eq!(separators.len(), dag.depth() - 1);
+        let (width, height) = dag.rendered_dimensions();
+        assert!(separators.iter().all(|l| l.chars().count() == width));
+        assert_eq!(height, plain.lines().count() + 3);
+
+        // Each separator sits right above a node row and pages break after it
+        let lines: Vec<&str> = output.lines().collect();
+        for (i, line) in lines.iter().enumerate() {
+            if line.starts_with('┄') {
+                assert!(lines[i + 1].contains('['), "{}", output);
+            }
+        }
+        for page in dag.render_pages(6) {
+            let first_body = page
+                .lines()
+                .find(|l| !l.starts_with("── continued"))
+                .unwrap();
+            assert!(!first_body.starts_with('┄'), "{}", page);
+        }
+    }
+
+    #[test]
+    fn test_show_order_numbers_nodes() {
+        let mut dag = DAG::from_edges(&[(1, "Fetch"), (3, "Test")], &[(1, 2), (2, 3)]);
+        dag.set_show_order(true);
+        let output = dag.render();
+        assert!(output.contains("[#1 Fetch]"), "{}", output);
+        assert!(output.contains("⟨#2 2⟩"), "{}", output);
+        assert!(output.contains("[#3 Test]"), "{}", output);
+        let widest = output.lines().map(|l| l.chars().count()).max().unwrap();
+        assert_eq!(dag.rendered_dimensions().0, widest);
+
+        // Numbers follow later edits
+        dag.add_node(0, "Init");
+        dag.add_edge(0, 1);
+        let output = dag.render();
+        assert!(output.contains("[#1 Init]"), "{}", output);
+        assert!(output.contains("[#2 Fetch]"), "{}", output);
+
+        // Cycles render the warning without numbers
+        dag.add_edge(3, 1);
+        let output = dag.render();
+        assert!(output.contains("CYCLE"), "{}", output);
+        assert!(!output.contains('#'), "{}", output);
+    }
+
+    #[test]
+    fn test_compact_mode_single_row_links() {
+        let mut dag = DAG::from_edges(
+            &[
+                (1, "Fetch"),
+                (2, "Build"),
+                (3, "Test"),
+                (4, "Lint"),
+                (5, "Ship"),
+            ],
+            &[(1, 2), (2, 3), (2, 4), (3, 5), (4, 5)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        let vertical = dag.render();
+        dag.set_render_mode(RenderMode::Compact);
+        let compact = dag.render();
+
+        // Only the 1-to-1 Fetch → Build link loses its line row
+        assert_eq!(compact.lines().count(), vertical.lines().count() - 1);
+        let lines: Vec<&str> = compact.lines().collect();
+        assert!(lines[0].contains("[Fetch]"));
+        assert_eq!(lines[1].trim(), "↓");
+        assert!(lines[2].contains("[Build]"));
+        assert!(
+            compact.contains('┌') && compact.contains('└'),
+            "{}",
+            compact
+        );
+
+        // Chains stay vertical, with arrows pointing the configured way
+        let mut chain = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C")], &[(1, 2), (2, 3)]);
+        chain.set_render_mode(RenderMode::Compact);
+        assert_eq!(chain.render(), "[A]\n ↓\n[B]\n ↓\n[C]\n");
+        chain.set_upward_arrows(true);
+        assert_eq!(chain.render(), "[A]\n ↑\n[B]\n ↑\n[C]\n");
+    }
+
+    #[test]
+    fn test_max_usize_ids() {
+        let max = usize::MAX.to_string();
+        let mut dag = DAG::new();
+        dag.add_node(1, "Root");
+        dag.add_edge(1, usize::MAX);
+
+        let output = dag.render();
+        assert!(output.contains(&format!("⟨{}⟩", max)), "{}", output);
+        let idx = dag.node_index(usize::MAX).unwrap();
+        assert_eq!(dag.get_node_width(idx), max.len() + 2);
+        assert_eq!(DAG::count_digits(usize::MAX), max.len());
+
+        dag.add_node(usize::MAX - 1, "Near");
+        dag.set_show_ids(true);
+        let output = dag.render();
+        assert!(
+            output.contains(&format!("[{}:Near]", usize::MAX - 1)),
+            "{}",
+            output
+        );
+    }
+
+    #[test]
+    fn test_mixed_convergence_divergence_routed() {
+        // A and B converge on C while B also diverges to D
+        let mut dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 3), (2, 3), (2, 4)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        let output = dag.render();
+        let lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+        assert_eq!(
+            lines,
+            [
+                "[A]   [B]",
+                " │     │",
+                " └┬────┴┐",
+                "  ↓     ↓",
+                " [C]   [D]"
+            ],
+            "{}",
+            output
+        );
+        let (_, report) = dag.render_with_report();
+        assert_eq!((report.convergences, report.divergences), (1, 1));
+
+        // A source above an unrelated target turns off before the target's
+        // line starts, so it never looks connected to it
+        let dag = DAG::from_edges(
+            &[
+                (1, "Parse"),
+                (2, "Lex"),
+                (3, "Config"),
+                (4, "Check"),
+                (5, "Emit"),
+                (6, "Lint"),
+            ],
+            &[(1, 4), (2, 4), (2, 5), (3, 5), (3, 6), (1, 6)],
+        );
+        let output = dag.render();
+        let lines: Vec<&str> = output.lines().collect();
+        let lex = lines[0].find("[Lex]").unwrap() + 2;
+        let lint_row = lines.iter().position(|l| l.contains("[Lint]")).unwrap();
+        let column: Vec<char> = lines[1..lint_row]
+            .iter()
+            .map(|l| l.chars().nth(lex).unwrap_or(' '))
+            .collect();
+        assert_eq!(column.first(), Some(&'│'), "{}", output);
+        assert!(column.contains(&'┴') && column.contains(&'┬'), "{}", output);
+        let turn = column.iter().position(|&c| c == '┴').unwrap();
+        let join = column.iter().position(|&c| c == '┬').unwrap();
+        assert!(turn < join, "{}", output);
+        assert!(!column.contains(&'┼'), "{}", output);
+    }
+
+    #[test]
+    fn test_one_to_one_link_beside_convergence() {
+        let dag = DAG::from_edges(
+            &[
+                (1, "L1A"),
+                (2, "L1B"),
+                (3, "L1C"),
+                (4, "L2A"),
+                (5, "L2B"),
+                (6, "Out"),
+            ],
+            &[(1, 4), (2, 4), (3, 5), (4, 6), (5, 6)],
+        );
+        let output = dag.render();
+        let lines: Vec<&str> = output.lines().collect();
+        let col = lines[0].find("[L1C]").unwrap() + 2;
+        for line in &lines[1..3] {
+            assert_eq!(line.chars().nth(col), Some('│'), "{}", output);
+        }
+    }
+
+    #[test]
+    fn test_map_ids_shifts_everything() {
+        let mut dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2), (2, 7)]);
+        dag.set_group(&[1], "core");
+        dag.set_node_brackets(2, "(", ")");
+
+        let shifted = dag.map_ids(|id| id + 1000).unwrap();
+        assert_eq!(shifted.descendants(1001), vec![1002, 1007]);
+        assert!(shifted.is_auto_created(1007));
+        assert_eq!(shifted.node_brackets.get(&1002), Some(&("(", ")")));
+        assert_eq!(shifted.groups.get(&1001), Some(&"core"));
+
+        // Placeholder widths follow the new IDs
+        let output = shifted.render();
+        assert!(output.contains("⟨1007⟩"), "{}", output);
+        assert!(output.contains("(B)"), "{}", output);
+
+        assert_eq!(dag.map_ids(|id| id % 2), Err(1));
+    }
+
+    #[test]
+    fn test_ascii_placeholders() {
+        let mut dag = DAG::from_edges(
+            &[(1, "Root"), (2, "Mid"), (3, "Side")],
+            &[(1, 2), (1, 3), (2, 4), (3, 5)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        dag.set_ascii_placeholders(true);
+
+        let output = dag.render();
+        assert!(
+            output.contains("<4>") && output.contains("<5>"),
+            "{}",
+            output
+        );
+        assert!(!output.contains('⟨'), "{}", output);
+        // The rest of the output stays Unicode
+        assert!(output.contains('↓'), "{}", output);
+
+        // Capped summaries follow the placeholder style, without an ID prefix
+        dag.set_show_ids(true);
+        dag.set_max_render_nodes(3);
+        let output = dag.render();
+        assert!(output.contains("<… +2 hidden>"), "{}", output);
+
+        // A real node drawn with the same brackets keeps its ID
+        dag.set_max_render_nodes(usize::MAX);
+        dag.set_node_brackets(2, "<", ">");
+        let output = dag.render();
+        assert!(output.contains("<2:Mid>"), "{}", output);
+
+        dag.set_ascii_placeholders(false);
+        assert!(dag.render().contains("⟨4⟩"));
+    }
+
+    #[test]
+    fn test_ascii_placeholders_keep_custom_style() {
+        let mut dag = DAG::from_edges(&[(1, "A")], &[(1, 2)]);
+        dag.set_placeholder_style("{", "}");
+        dag.set_ascii_placeholders(false);
+        assert_eq!(dag.render(), "[A] → {2}\n");
+
+        // Neither order of the two calls replaces the custom style
+        dag.set_ascii_placeholders(true);
+        assert_eq!(dag.render(), "[A] → {2}\n");
+        dag.set_placeholder_style("⟨", "⟩");
+        assert_eq!(dag.render(), "[A] → <2>\n");
+    }
+
+    #[test]
+    fn test_root_and_leaf_counts() {
+        let diamond = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        assert_eq!(diamond.root_count(), 1);
+        assert_eq!(diamond.leaf_count(), 1);
+        assert!(diamond.is_single_rooted());
+
+        // Forest: two trees plus an isolated node, which is both root and leaf
+        let forest = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D"), (5, "E"), (6, "F")],
+            &[(1, 2), (1, 3), (4, 5)],
+        );
+        assert_eq!(forest.root_count(), 3);
+        assert_eq!(forest.leaf_count(), 4);
+        assert_eq!(forest.root_count(), forest.roots().len());
+        assert!(!forest.is_single_rooted());
+
+        assert!(!DAG::new().is_single_rooted());
+    }
+
+    #[test]
+    fn test_edge_channels() {
+        let mut dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 2), (1, 3), (1, 4)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        let shared = dag.render();
+        assert!(shared.contains(" ┌─────┬─────┐\n"), "{}", shared);
+
+        // Each bent edge gets its own elbow; the straight one stays straight
+        dag.set_edge_channels(true);
+        let output = dag.render();
+        assert_eq!(
+            output,
+            "      [A]\n       │      \n ┌─────┤      \n │     ├─────┐\n ↓     ↓     ↓\n[B]   [C]   [D]\n",
+        );
+
+        // Convergences nest from the inside out
+        let mut dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 4), (2, 4), (3, 4)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        dag.set_edge_channels(true);
+        let output = dag.render();
+        assert!(
+            output.contains(" └─────┤     │\n       ├─────┘\n"),
+            "{}",
+            output
+        );
+
+        // Bands without bent edges are unchanged
+        let mut chain = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+        chain.set_render_mode(RenderMode::Vertical);
+        let plain = chain.render();
+        chain.set_edge_channels(true);
+        assert_eq!(chain.render(), plain);
+
+        dag.set_edge_channels(false);
+        assert!(dag.render().contains(" └─────┴─────┘\n"));
+    }
+
+    #[test]
+    fn test_impact_queries_on_dag() {
+        // Chain 1 → 2 → 3
+        let chain = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C")], &[(1, 2), (2, 3)]);
+        assert_eq!(chain.impacted_by(1), vec![2, 3]);
+        assert_eq!(chain.required_for(3), vec![2, 1]);
+
+        // Diamond: 4 is reached twice but listed once
+        let diamond = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        assert_eq!(diamond.impacted_by(1), vec![2, 3, 4]);
+        assert_eq!(diamond.required_for(4), vec![2, 3, 1]);
+
+        // Blast radius: 1 above 2, 3 and 4 below
+        let fork = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 2), (2, 3), (2, 4)],
+        );
+        assert_eq!(fork.blast_radius(2), (vec![1], vec![3, 4]));
+
+        // No impact: leaves, roots, and unknown IDs
+        assert!(fork.impacted_by(4).is_empty());
+        assert!(fork.required_for(1).is_empty());
+        assert_eq!(fork.blast_radius(99), (vec![], vec![]));
+    }
+
+    #[test]
+    fn test_try_render_matches_render() {
+        let mut cyclic = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+        cyclic.add_edge(2, 1);
+        let graphs = [
+            DAG::new(),
+            DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]),
+            DAG::from_edges(
+                &[(1, "A"), (2, "B"), (3, "C"), (4, "D"), (5, "E")],
+                &[(1, 3), (1, 4), (2, 4), (2, 5), (3, 6)],
+            ),
+            cyclic,
+        ];
+        let modes = [
+            RenderMode::Auto,
+            RenderMode::Vertical,
+            RenderMode::Horizontal,
+            RenderMode::CauseChain,
+            RenderMode::HorizontalLayered,
+            RenderMode::Compact,
+        ];
+        for mut dag in graphs {
+            for mode in modes {
+                dag.set_render_mode(mode);
+                assert_eq!(dag.try_render(), Ok(dag.render()), "{:?}", mode);
+            }
+        }
+    }
+
+    #[test]
+    fn test_horizontal_renders_every_root() {
+        let mut dag = DAG::from_edges(
+            &[
+                (1, "Fetch"),
+                (2, "Build"),
+                (3, "Lint"),
+                (4, "Report"),
+                (5, "Idle"),
+            ],
+            &[(1, 2), (3, 4)],
+        );
+        dag.set_render_mode(RenderMode::Horizontal);
+        assert_eq!(
+            dag.render(),
+            "[Fetch] → [Build]\n[Lint] → [Report]\n[Idle]\n"
+        );
+
+        assert_eq!(dag.render_horizontal_from(1), "[Fetch] → [Build]\n");
+        // Any node can start a chain
+        assert_eq!(dag.render_horizontal_from(4), "[Report]\n");
+    }
+
+    #[test]
+    fn test_index_of_and_id_at_round_trip() {
+        let mut dag = DAG::new();
+        dag.add_node(7, "G");
+        dag.add_node(3, "C");
+        dag.add_edge(3, 9); // auto-creates 9 at the end
+
+        for index in 0..3 {
+            let id = dag.id_at(index).unwrap();
+            assert_eq!(dag.index_of(id), Some(index));
+        }
+        assert_eq!(dag.index_of(9), Some(2));
+
+        // Canonicalizing sorts by ID, so positions change
+        dag.canonicalize();
+        assert_eq!(dag.index_of(3), Some(0));
+        assert_eq!(dag.id_at(1), Some(7));
+    }
+
+    #[test]
+    fn test_capped_render_keeps_options() {
+        // Ten errors converging on Fail → Report; Report gets folded away
+        let nodes: Vec<(usize, &str)> = (1..=10)
+            .map(|i| (i, "Error"))
+            .chain([(11, "Fail"), (12, "Report")])
+            .collect();
+        let edges: Vec<(usize, usize)> = (1..=10).map(|i| (i, 11)).chain([(11, 12)]).collect();
+        let mut dag = DAG::from_edges(&nodes, &edges);
+        dag.set_render_mode(RenderMode::Vertical);
+        dag.set_max_render_nodes(11);
+        let single = dag.render();
+        assert!(single.contains("⟨… +1 hidden⟩"), "{}", single);
+
+        dag.set_max_convergence_span(12);
+        let stepped = dag.render();
+        assert!(
+            stepped.lines().count() > single.lines().count(),
+            "{}",
+            stepped
+        );
+
+        dag.set_heat(crate::graph::HeatMetric::Descendants);
+        let heated = dag.render();
+        assert!(heated.contains("heat (descendants):"), "{}", heated);
+        assert!(heated.contains('*'), "{}", heated);
+    }
+
+    #[test]
+    fn test_mixed_band_lane_loop_takes_detour() {
+        // A → E runs under B and B → D runs under A: one of them must turn
+        // off first, so B detours and never looks connected to E
+        let mut dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D"), (5, "E"), (6, "F")],
+            &[(1, 4), (1, 5), (2, 4), (2, 6), (3, 5), (3, 6)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        let output = dag.render();
+        let lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+        assert_eq!(
+            lines,
+            [
+                "[A]   [B]   [C]",
+                " │     │     │",
+                " │     └┐    │",
+                " ├─────┬│────┤",
+                " ├─────│┴────┤",
+                " ↓     ↓     ↓",
+                "[D]   [E]   [F]"
+            ],
+            "{}",
+            output
+        );
+
+        dag.set_edge_channels(true);
+        let output = dag.render();
+        let lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+        assert_eq!(
+            lines,
+            [
+                "[A]   [B]   [C]",
+                " │     │     │",
+                " │    ┌┤     │",
+                " │    │└┐    │",
+                " ├────│┐│    │",
+                " ├────┘├│────┤",
+                " │     │└────┤",
+                " ↓     ↓     ↓",
+                "[D]   [E]   [F]"
+            ],
+            "{}",
+            output
+        );
+    }
 }
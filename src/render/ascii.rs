@@ -1,16 +1,25 @@
 //! ASCII rendering implementation for DAG visualization.
 
-use crate::graph::{DAG, RenderMode};
+use crate::graph::{Alignment, DAG, HeatMetric, NodeMark, RenderMode, char_width, str_width};
 use alloc::{string::String, vec, vec::Vec};
 use core::fmt::Write;
+use core::ops::RangeInclusive;
 
 // Box drawing characters (Unicode)
 pub(crate) const V_LINE: char = '│';
 pub(crate) const H_LINE: char = '─';
 pub(crate) const ARROW_DOWN: char = '↓';
 pub(crate) const ARROW_RIGHT: char = '→';
+pub(crate) const ARROW_UP: char = '↑';
+pub(crate) const ARROW_LEFT: char = '←';
 pub(crate) const CYCLE_ARROW: char = '⇄'; // For cycle detection
 
+// Directions a connector cell links to, combined into one glyph
+const LINK_UP: u8 = 1;
+const LINK_DOWN: u8 = 2;
+const LINK_LEFT: u8 = 4;
+const LINK_RIGHT: u8 = 8;
+
 // Convergence/divergence
 pub(crate) const CORNER_DR: char = '└'; // Down-Right corner
 pub(crate) const CORNER_DL: char = '┘'; // Down-Left corner
@@ -18,6 +27,379 @@ pub(crate) const TEE_DOWN: char = '┬'; // T pointing down
 pub(crate) const TEE_UP: char = '┴'; // T pointing up
 pub(crate) const CORNER_UR: char = '┌'; // Up-Right corner
 pub(crate) const CORNER_UL: char = '┐'; // Up-Left corner
+pub(crate) const TEE_RIGHT: char = '├'; // T pointing right
+pub(crate) const TEE_LEFT: char = '┤'; // T pointing left
+pub(crate) const CROSS: char = '┼'; // Four-way junction
+
+// Line width at which the compact isolated-node section wraps
+pub(crate) const ISOLATED_WRAP_WIDTH: usize = 80;
+
+/// Line and arrow glyphs used by the renderers (see [`DAG::set_theme`]).
+///
+/// Every glyph must occupy exactly one terminal column: layout math counts
+/// one column per `char`, regardless of its UTF-8 byte length. Corners are
+/// named by where they sit in a box (`top_left` is `┌`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Vertical line (`│`)
+    pub vertical: char,
+    /// Horizontal line (`─`)
+    pub horizontal: char,
+    /// `┌`, where a divergence line starts
+    pub top_left: char,
+    /// `┐`, where a divergence line ends
+    pub top_right: char,
+    /// `└`, where a convergence line starts
+    pub bottom_left: char,
+    /// `┘`, where a convergence line ends
+    pub bottom_right: char,
+    /// `┬`, a branch leaving a horizontal line downwards
+    pub tee_down: char,
+    /// `┴`, a branch joining a horizontal line from above
+    pub tee_up: char,
+    /// `├`, a vertical line continuing with a branch to the right
+    pub tee_right: char,
+    /// `┤`, a vertical line continuing with a branch to the left
+    pub tee_left: char,
+    /// `┼`, a vertical line crossing a horizontal one
+    pub cross: char,
+    /// Arrowhead of vertical connectors (`↓`)
+    pub arrow_down: char,
+    /// Arrowhead of vertical connectors with upward arrows (`↑`)
+    pub arrow_up: char,
+    /// Arrow in horizontal chains and cycle paths (`→`)
+    pub arrow_right: char,
+    /// Arrow in horizontal chains with upward arrows (`←`)
+    pub arrow_left: char,
+    /// Arrow closing a cycle in the cycle warning (`⇄`)
+    pub arrow_cycle: char,
+    /// Faint rule between levels with level separators (`┄`)
+    pub separator: char,
+}
+
+impl Theme {
+    /// Thin box-drawing lines (`│ ─ ┌ ┐`), the default.
+    pub const fn light() -> Self {
+        Self {
+            vertical: V_LINE,
+            horizontal: H_LINE,
+            top_left: CORNER_UR,
+            top_right: CORNER_UL,
+            bottom_left: CORNER_DR,
+            bottom_right: CORNER_DL,
+            tee_down: TEE_DOWN,
+            tee_up: TEE_UP,
+            tee_right: TEE_RIGHT,
+            tee_left: TEE_LEFT,
+            cross: CROSS,
+            arrow_down: ARROW_DOWN,
+            arrow_up: ARROW_UP,
+            arrow_right: ARROW_RIGHT,
+            arrow_left: ARROW_LEFT,
+            arrow_cycle: CYCLE_ARROW,
+            separator: '┄',
+        }
+    }
+
+    /// Heavy lines (`┃ ━ ┏ ┓`) with the default arrows.
+    pub const fn heavy() -> Self {
+        Self {
+            vertical: '┃',
+            horizontal: '━',
+            top_left: '┏',
+            top_right: '┓',
+            bottom_left: '┗',
+            bottom_right: '┛',
+            tee_down: '┳',
+            tee_up: '┻',
+            tee_right: '┣',
+            tee_left: '┫',
+            cross: '╋',
+            separator: '┅',
+            ..Self::light()
+        }
+    }
+
+    /// Double lines (`║ ═ ╔ ╗`) with double arrows (`⇓ ⇒`).
+    pub const fn double() -> Self {
+        Self {
+            vertical: '║',
+            horizontal: '═',
+            top_left: '╔',
+            top_right: '╗',
+            bottom_left: '╚',
+            bottom_right: '╝',
+            tee_down: '╦',
+            tee_up: '╩',
+            tee_right: '╠',
+            tee_left: '╣',
+            cross: '╬',
+            arrow_down: '⇓',
+            arrow_up: '⇑',
+            arrow_right: '⇒',
+            arrow_left: '⇐',
+            ..Self::light()
+        }
+    }
+
+    /// Thin lines with rounded corners (`╭ ╮ ╰ ╯`).
+    pub const fn rounded() -> Self {
+        Self {
+            top_left: '╭',
+            top_right: '╮',
+            bottom_left: '╰',
+            bottom_right: '╯',
+            ..Self::light()
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+/// Destination for rendered text: a `String`, or a sink that only measures.
+///
+/// The renderers only append, so a target needs `push`/`push_str` plus
+/// `fmt::Write` for `write!`. Node text is bracketed by `begin_node`/`end_node`
+/// so targets can record where nodes land.
+pub(crate) trait RenderTarget: Write {
+    fn push(&mut self, ch: char);
+    fn push_str(&mut self, s: &str);
+
+    /// Called right before the text of node `id` is written.
+    #[inline]
+    fn begin_node(&mut self, _id: usize) {}
+
+    /// Called right after the node's text has been written.
+    #[inline]
+    fn end_node(&mut self) {}
+
+    /// Called when the renderer makes a decision worth reporting.
+    #[inline]
+    fn note(&mut self, _event: RenderEvent) {}
+
+    /// Called at the start of a row that heads what follows it (a title line,
+    /// component label, or group header), so pagination keeps them together.
+    #[inline]
+    fn begin_heading(&mut self) {}
+}
+
+/// A rendering decision recorded for [`RenderReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RenderEvent {
+    /// Junctions where several inputs merge into one node
+    Convergences(usize),
+    /// Junctions where one node splits into several outputs
+    Divergences(usize),
+    /// Levels cut by the depth limit
+    HiddenLevels(usize),
+    /// Nodes folded into the summary node by the node cap
+    HiddenNodes(usize),
+    /// Edges spanning several levels, which the layout doesn't draw
+    UndrawnEdges(usize),
+}
+
+/// What a rendering actually drew (see [`DAG::render_with_report`]).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderReport {
+    /// Line and arrow glyphs that appear outside node text, in order of first use
+    pub glyphs: Vec<char>,
+    /// Convergence junctions drawn (several parents merging into one child)
+    pub convergences: usize,
+    /// Divergence junctions drawn (one parent splitting to several children)
+    pub divergences: usize,
+    /// Levels left out by [`DAG::set_max_render_depth`]
+    pub hidden_levels: usize,
+    /// Nodes folded into the summary node by [`DAG::set_max_render_nodes`]
+    pub hidden_nodes: usize,
+    /// Edges spanning more than one level, which layered layouts don't draw
+    pub undrawn_edges: usize,
+    /// Auto-created placeholder nodes in the graph
+    pub placeholders: usize,
+}
+
+/// Where a node's text landed in a rendering (see [`DAG::render_with_positions`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodePosition {
+    /// Node ID
+    pub id: usize,
+    /// Line index (0-based)
+    pub row: usize,
+    /// Starting column of the node text, brackets included (0-based, in display columns)
+    pub col: usize,
+    /// Width of the node text in columns, brackets and any suffix included
+    pub width: usize,
+}
+
+/// A `String` target that also records node positions.
+#[derive(Default)]
+struct Tracked {
+    output: String,
+    row: usize,
+    col: usize,
+    positions: Vec<NodePosition>,
+    headings: Vec<usize>,
+}
+
+impl RenderTarget for Tracked {
+    #[inline]
+    fn push(&mut self, ch: char) {
+        self.output.push(ch);
+        if ch == '\n' {
+            self.row += 1;
+            self.col = 0;
+        } else {
+            self.col += char_width(ch);
+        }
+    }
+
+    fn push_str(&mut self, s: &str) {
+        for ch in s.chars() {
+            RenderTarget::push(self, ch);
+        }
+    }
+
+    fn begin_node(&mut self, id: usize) {
+        self.positions.push(NodePosition {
+            id,
+            row: self.row,
+            col: self.col,
+            width: 0,
+        });
+    }
+
+    fn end_node(&mut self) {
+        if let Some(pos) = self.positions.last_mut() {
+            pos.width = self.col - pos.col;
+        }
+    }
+
+    fn begin_heading(&mut self) {
+        self.headings.push(self.row);
+    }
+}
+
+impl Write for Tracked {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        RenderTarget::push_str(self, s);
+        Ok(())
+    }
+}
+
+impl RenderTarget for String {
+    #[inline]
+    fn push(&mut self, ch: char) {
+        String::push(self, ch);
+    }
+
+    #[inline]
+    fn push_str(&mut self, s: &str) {
+        String::push_str(self, s);
+    }
+}
+
+/// Error returned by [`DAG::try_render`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderError {
+    /// The output buffer could not grow to hold the rendering
+    OutOfMemory,
+}
+
+impl core::fmt::Display for RenderError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RenderError::OutOfMemory => write!(f, "out of memory while rendering"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RenderError {}
+
+/// A `String` target that grows with `try_reserve` and stops at the first
+/// failed allocation instead of aborting.
+#[derive(Default)]
+struct Fallible {
+    output: String,
+    failed: bool,
+}
+
+impl RenderTarget for Fallible {
+    #[inline]
+    fn push(&mut self, ch: char) {
+        if !self.failed && self.output.try_reserve(ch.len_utf8()).is_ok() {
+            self.output.push(ch);
+        } else {
+            self.failed = true;
+        }
+    }
+
+    #[inline]
+    fn push_str(&mut self, s: &str) {
+        if !self.failed && self.output.try_reserve(s.len()).is_ok() {
+            self.output.push_str(s);
+        } else {
+            self.failed = true;
+        }
+    }
+}
+
+impl Write for Fallible {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        RenderTarget::push_str(self, s);
+        if self.failed {
+            Err(core::fmt::Error)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A [`RenderTarget`] that tracks the output's line count and widest line.
+#[derive(Debug, Default)]
+pub(crate) struct Measure {
+    lines: usize,
+    col: usize,
+    max_width: usize,
+}
+
+impl Measure {
+    /// `(width, height)` as `str::lines` would see the output.
+    pub(crate) fn dimensions(&self) -> (usize, usize) {
+        // A trailing partial line counts; a trailing newline doesn't add one
+        let height = self.lines + usize::from(self.col > 0);
+        (self.max_width, height)
+    }
+}
+
+impl RenderTarget for Measure {
+    #[inline]
+    fn push(&mut self, ch: char) {
+        if ch == '\n' {
+            self.lines += 1;
+            self.col = 0;
+        } else {
+            self.col += char_width(ch);
+            self.max_width = self.max_width.max(self.col);
+        }
+    }
+
+    fn push_str(&mut self, s: &str) {
+        for ch in s.chars() {
+            self.push(ch);
+        }
+    }
+}
+
+impl Write for Measure {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        RenderTarget::push_str(self, s);
+        Ok(())
+    }
+}
 
 impl<'a> DAG<'a> {
     /// Render the DAG to an ASCII string.
@@ -41,6 +423,63 @@ impl<'a> DAG<'a> {
         buf
     }
 
+    /// Render the DAG, reporting a failure to grow the output instead of
+    /// aborting.
+    ///
+    /// The output buffer is grown with `try_reserve`, so running out of memory
+    /// returns [`RenderError::OutOfMemory`] rather than aborting, which makes
+    /// this the entry point for `no_std` and embedded users. Layout scratch
+    /// space is still allocated as usual. The text is the same as
+    /// [`render`](Self::render).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// assert_eq!(dag.try_render(), Ok(dag.render()));
+    /// ```
+    pub fn try_render(&self) -> Result<String, RenderError> {
+        let mut target = Fallible::default();
+        if target.output.try_reserve(self.estimate_size()).is_err() {
+            return Err(RenderError::OutOfMemory);
+        }
+        self.render_into(&mut target);
+        if target.failed {
+            Err(RenderError::OutOfMemory)
+        } else {
+            Ok(target.output)
+        }
+    }
+
+    /// Render the chain starting at `root` on one line, as horizontal mode
+    /// does for each root: `[A] → [B] → [C]`.
+    ///
+    /// The chain follows each node's first child, so on a branching graph it
+    /// shows one path. `root` need not be a root; any node starts a chain.
+    /// Unknown IDs give an empty string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(
+    ///     &[(1, "Fetch"), (2, "Build"), (3, "Lint"), (4, "Report")],
+    ///     &[(1, 2), (3, 4)]
+    /// );
+    /// assert_eq!(dag.render_horizontal_from(3), "[Lint] → [Report]\n");
+    /// assert_eq!(dag.render_horizontal_from(9), "");
+    /// ```
+    pub fn render_horizontal_from(&self, root: usize) -> String {
+        let mut output = String::new();
+        if self.node_index(root).is_some() {
+            self.write_chain(&mut output, root);
+        }
+        output
+    }
+
     /// Render the DAG into a provided buffer (zero-allocation).
     ///
     /// # Examples
@@ -58,6 +497,308 @@ impl<'a> DAG<'a> {
     /// assert!(!buffer.is_empty());
     /// ```
     pub fn render_to(&self, output: &mut String) {
+        self.render_into(output);
+    }
+
+    /// Query the size of the rendering as `(width, height)` without building it.
+    ///
+    /// Width is the longest line in display columns (two for CJK and most
+    /// emoji), height the number of lines, i.e. exactly what measuring
+    /// `render()` would give.
+    /// The layout runs as usual, but output goes to a counter instead of a buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// assert_eq!(dag.rendered_dimensions(), (9, 1)); // "[A] → [B]"
+    /// ```
+    pub fn rendered_dimensions(&self) -> (usize, usize) {
+        let mut measure = Measure::default();
+        self.render_into(&mut measure);
+        measure.dimensions()
+    }
+
+    /// Render the DAG with every line right-padded to exactly `width` columns.
+    ///
+    /// Columns are terminal display columns, the same measure the layout uses
+    /// for labels and [`rendered_dimensions`](Self::rendered_dimensions)
+    /// reports: box-drawing characters and arrows take one column, CJK and
+    /// most emoji two, combining marks none. Lines longer than `width` are cut
+    /// and end in `…`. Passing the natural width from `rendered_dimensions`
+    /// pads without cutting anything.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// assert_eq!(dag.render_padded(12), "[A] → [B]   \n");
+    /// assert_eq!(dag.render_padded(6), "[A] →…\n");
+    /// ```
+    pub fn render_padded(&self, width: usize) -> String {
+        let rendered = self.render();
+        let mut output = String::with_capacity(rendered.len() + rendered.lines().count() * width);
+        for line in rendered.lines() {
+            let len = str_width(line);
+            if len <= width {
+                output.push_str(line);
+                output.extend(core::iter::repeat_n(' ', width - len));
+            } else if width > 0 {
+                // Keep as many whole characters as fit before the ellipsis,
+                // padding the gap a cut double-width character leaves
+                let mut used = 0;
+                for ch in line.chars() {
+                    let w = char_width(ch);
+                    if used + w > width - 1 {
+                        break;
+                    }
+                    output.push(ch);
+                    used += w;
+                }
+                output.extend(core::iter::repeat_n(' ', width - 1 - used));
+                output.push('…');
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Render the DAG as a fenced Markdown code block, ready to paste into an
+    /// issue or README.
+    ///
+    /// The rendering goes inside a ```` ```text ```` fence unchanged. If a label
+    /// itself contains a run of backticks, the fence is made one backtick
+    /// longer than the longest run so the block can't be closed early. A title
+    /// set with [`set_title`](Self::set_title) comes first as a bold line
+    /// instead of the underlined one inside the rendering.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// assert_eq!(dag.render_markdown(), "```text\n[A] → [B]\n```\n");
+    ///
+    /// dag.set_title("Pipeline");
+    /// assert_eq!(dag.render_markdown(), "**Pipeline**\n\n```text\n[A] → [B]\n```\n");
+    /// ```
+    pub fn render_markdown(&self) -> String {
+        // The title goes above the fence instead
+        let mut rendered = String::with_capacity(self.estimate_size());
+        self.render_untitled(&mut rendered);
+
+        let mut longest_run = 0;
+        let mut run = 0;
+        for ch in rendered.chars() {
+            run = if ch == '`' { run + 1 } else { 0 };
+            longest_run = longest_run.max(run);
+        }
+        let fence_len = longest_run.max(2) + 1;
+
+        let mut output = String::with_capacity(rendered.len() + 2 * fence_len + 16);
+        if let Some(title) = self.title {
+            let mut sanitized = String::new();
+            Self::write_label(&mut sanitized, title);
+            output.push_str("**");
+            for ch in sanitized.chars() {
+                if matches!(ch, '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>') {
+                    output.push('\\');
+                }
+                output.push(ch);
+            }
+            output.push_str("**\n\n");
+        }
+        output.extend(core::iter::repeat_n('`', fence_len));
+        output.push_str("text\n");
+        output.push_str(&rendered);
+        output.extend(core::iter::repeat_n('`', fence_len));
+        output.push('\n');
+        output
+    }
+
+    /// Render the DAG and report where each node's text landed.
+    ///
+    /// Useful for hit-testing clicks in a TUI. Positions are in render order,
+    /// one per occurrence (a node can appear more than once, e.g. when a cycle
+    /// closes back on its first node or in cause-chain mode). Rows count lines
+    /// of the returned string and columns its display columns, as in
+    /// [`rendered_dimensions`](Self::rendered_dimensions).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// let (output, positions) = dag.render_with_positions();
+    ///
+    /// assert_eq!(output, "[A] → [B]\n");
+    /// assert_eq!((positions[1].id, positions[1].row, positions[1].col), (2, 0, 6));
+    /// ```
+    pub fn render_with_positions(&self) -> (String, Vec<NodePosition>) {
+        let tracked = self.render_tracked();
+        (tracked.output, tracked.positions)
+    }
+
+    /// Render into a [`Tracked`] target, keeping node and heading rows.
+    fn render_tracked(&self) -> Tracked {
+        let mut tracked = Tracked {
+            output: String::with_capacity(self.estimate_size()),
+            ..Tracked::default()
+        };
+        self.render_into(&mut tracked);
+        tracked
+    }
+
+    /// Split the rendering into pages of at most `rows_per_page` lines.
+    ///
+    /// Pages break only on level boundaries: a node row always stays on the
+    /// same page as the connector rows drawn below it and the title, component
+    /// label or group header rows drawn above it, so the title is always on the
+    /// first page and a header never ends a page. Continuation pages start
+    /// with a `── continued (page 2/5) ──` header, which counts towards the
+    /// page height. A single level taller than a page gets a page of its own.
+    /// `rows_per_page == 0` disables pagination.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::{DAG, RenderMode};
+    ///
+    /// let mut dag = DAG::from_edges(
+    ///     &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+    ///     &[(1, 2), (2, 3), (3, 4)]
+    /// );
+    /// dag.set_render_mode(RenderMode::Vertical);
+    ///
+    /// let pages = dag.render_pages(7);
+    /// assert_eq!(pages.len(), 2);
+    /// assert!(pages[1].starts_with("── continued (page 2/2) ──\n"));
+    /// ```
+    pub fn render_pages(&self, rows_per_page: usize) -> Vec<String> {
+        let tracked = self.render_tracked();
+        let output = tracked.output;
+        let lines: Vec<&str> = output.lines().collect();
+        if rows_per_page == 0 || lines.len() <= rows_per_page {
+            return vec![output];
+        }
+
+        // A level block runs from the headings above one node row to the
+        // line before the next block
+        let mut starts: Vec<usize> = tracked
+            .positions
+            .iter()
+            .map(|p| {
+                let mut row = p.row;
+                while row > 0 && tracked.headings.contains(&(row - 1)) {
+                    row -= 1;
+                }
+                row
+            })
+            .collect();
+        starts.push(0);
+        starts.sort_unstable();
+        starts.dedup();
+        let blocks: Vec<&[&str]> = starts
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| {
+                let end = starts.get(i + 1).copied().unwrap_or(lines.len());
+                &lines[start..end]
+            })
+            .collect();
+
+        // Greedily fill pages (continuation pages lose a row to the header)
+        let mut pages: Vec<Vec<&str>> = vec![Vec::new()];
+        for block in blocks {
+            let capacity = if pages.len() == 1 {
+                rows_per_page
+            } else {
+                rows_per_page.saturating_sub(1)
+            };
+            let filled = pages.last().map_or(0, Vec::len);
+            if filled > 0 && filled + block.len() > capacity {
+                pages.push(Vec::new());
+            }
+            if let Some(page) = pages.last_mut() {
+                page.extend_from_slice(block);
+            }
+        }
+
+        let total = pages.len();
+        pages
+            .iter()
+            .enumerate()
+            .map(|(i, page)| {
+                let mut text = String::new();
+                if i > 0 {
+                    writeln!(text, "── continued (page {}/{}) ──", i + 1, total).ok();
+                }
+                for line in page {
+                    text.push_str(line);
+                    text.push('\n');
+                }
+                text
+            })
+            .collect()
+    }
+
+    /// Render into any [`RenderTarget`] (shared by `render_to` and measuring):
+    /// the title if set, the graph, then the summary block if enabled.
+    pub(crate) fn render_into(&self, output: &mut impl RenderTarget) {
+        self.write_title(output);
+        self.render_untitled(output);
+    }
+
+    /// Write the title centered over the rendering, underlined.
+    ///
+    /// The width comes from a measuring pass over the rest of the output.
+    /// Titles wider than that start at column 0 and widen the canvas.
+    pub(crate) fn write_title(&self, output: &mut impl RenderTarget) {
+        let Some(title) = self.title else {
+            return;
+        };
+        let mut text = String::new();
+        Self::write_label(&mut text, title);
+        let len = str_width(&text);
+
+        let mut measure = Measure::default();
+        self.render_untitled(&mut measure);
+        let pad = measure.dimensions().0.saturating_sub(len) / 2;
+
+        output.begin_heading();
+        for _ in 0..pad {
+            output.push(' ');
+        }
+        output.push_str(&text);
+        output.push('\n');
+        output.begin_heading();
+        for _ in 0..pad {
+            output.push(' ');
+        }
+        for _ in 0..len {
+            output.push(self.theme.horizontal);
+        }
+        output.push('\n');
+    }
+
+    /// Everything [`render_into`](Self::render_into) writes after the title.
+    fn render_untitled(&self, output: &mut impl RenderTarget) {
+        if self.summary {
+            let report = self.render_reported(output);
+            self.write_summary(output, &report);
+        } else {
+            self.render_body(output);
+        }
+    }
+
+    /// Render the graph itself, dispatching on cycles, caps, and render mode.
+    pub(crate) fn render_body(&self, output: &mut impl RenderTarget) {
         if self.nodes.is_empty() {
             output.push_str("Empty DAG");
             return;
@@ -69,6 +810,24 @@ impl<'a> DAG<'a> {
             return;
         }
 
+        // Oversized graphs are reduced to their most important nodes first
+        if self.nodes.len() > self.max_render_nodes {
+            self.render_capped(output);
+            return;
+        }
+
+        // Heat markers are graded once per render, on a copy
+        if self.heat != HeatMetric::Off && self.heat_levels.is_empty() {
+            self.render_heat(output);
+            return;
+        }
+
+        // Order numbers likewise
+        if self.show_order && self.order_numbers.is_empty() {
+            self.render_ordered(output);
+            return;
+        }
+
         // Determine actual render mode
         let mode = match self.render_mode {
             RenderMode::Auto => {
@@ -83,12 +842,227 @@ impl<'a> DAG<'a> {
 
         match mode {
             RenderMode::Horizontal => self.render_horizontal(output),
-            RenderMode::Vertical | RenderMode::Auto => self.render_vertical(output),
+            RenderMode::CauseChain => self.render_cause_chain(output),
+            RenderMode::HorizontalLayered => self.render_horizontal_layered(output),
+            RenderMode::Vertical | RenderMode::Compact | RenderMode::Auto => {
+                self.render_vertical(output)
+            }
+        }
+    }
+
+    /// Render a copy with every node numbered by its topological position.
+    fn render_ordered(&self, output: &mut impl RenderTarget) {
+        let Some(order) = self.topological_order() else {
+            // Unreachable after the cycle check, but never render unnumbered twice
+            return;
+        };
+        let mut ordered = self.clone();
+        ordered.order_numbers = vec![0; self.nodes.len()];
+        for (position, idx) in order.into_iter().enumerate() {
+            ordered.order_numbers[idx] = position + 1;
         }
+        ordered.refresh_node_widths();
+        ordered.render_body(output);
+    }
+
+    /// Render the DAG with `node` and everything it depends on emphasized.
+    ///
+    /// This is the "why does this exist" view: the node and its ancestors are
+    /// drawn as `⟦Label⟧` (placeholders as `⟪ID⟫`), every other node is dimmed
+    /// to `(Label)`. With the default brackets the widths are unchanged, so the
+    /// layout is identical to [`render`](Self::render). An unknown `node`
+    /// renders the graph unmarked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(
+    ///     &[(1, "Src"), (2, "Obj"), (3, "Docs"), (4, "App")],
+    ///     &[(1, 2), (1, 3), (2, 4)]
+    /// );
+    ///
+    /// let output = dag.render_ancestors_highlighted(2);
+    /// assert!(output.contains("⟦Src⟧"));
+    /// assert!(output.contains("⟦Obj⟧"));
+    /// assert!(output.contains("(Docs)"));
+    /// assert!(output.contains("(App)"));
+    /// ```
+    pub fn render_ancestors_highlighted(&self, node: usize) -> String {
+        let Some(idx) = self.node_index(node) else {
+            return self.render();
+        };
+
+        let mut marked = self.clone();
+        marked.node_marks = self
+            .reachable_mask(idx, true)
+            .into_iter()
+            .map(|hit| {
+                if hit {
+                    NodeMark::Emphasized
+                } else {
+                    NodeMark::Dimmed
+                }
+            })
+            .collect();
+        marked.refresh_node_widths();
+        marked.render()
+    }
+
+    /// Render with labels looked up at render time.
+    ///
+    /// `resolver` is asked for every node's label; `Some` overrides the stored
+    /// label (promoting auto-created placeholders), `None` keeps it. The graph
+    /// itself is not modified. Layout uses the resolved labels' widths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut dag = DAG::new();
+    /// dag.add_node(1, "Request");
+    /// dag.add_edge(1, 2); // label of 2 not known yet
+    ///
+    /// let names: HashMap<usize, String> = [(2, "Timeout".to_string())].into();
+    /// let output = dag.render_with_labels(|id| names.get(&id).map(String::as_str));
+    /// assert_eq!(output, "[Request] → [Timeout]\n");
+    /// assert!(dag.render().contains("⟨2⟩"));
+    /// ```
+    pub fn render_with_labels<'r>(&'r self, resolver: impl Fn(usize) -> Option<&'r str>) -> String {
+        let mut resolved: DAG<'r> = self.clone();
+        for &(id, _) in &self.nodes {
+            if let Some(label) = resolver(id) {
+                resolved.add_node(id, label);
+            }
+        }
+        resolved.render()
+    }
+
+    /// Render one frame per step of a topological walk through the graph.
+    ///
+    /// Frame `k` shows the first `k` nodes of the topological order as done
+    /// (`✓[Label]`) and the rest as pending (` (Label)`), so there are
+    /// `node_count + 1` frames from "nothing done" to "everything done". Done and
+    /// pending nodes are the same width, so every frame has identical dimensions
+    /// and can be redrawn in place.
+    ///
+    /// Returns `Err` with the cycle path if the graph is not acyclic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(&[(1, "Fetch"), (2, "Build")], &[(1, 2)]);
+    /// let frames = dag.render_steps().unwrap();
+    ///
+    /// assert_eq!(frames.len(), 3);
+    /// assert!(frames[1].contains("✓[Fetch]"));
+    /// assert!(frames[1].contains(" (Build)"));
+    /// ```
+    pub fn render_steps(&self) -> Result<Vec<String>, Vec<usize>> {
+        let Some(order) = self.topological_order() else {
+            return Err(self.find_cycle_path().unwrap_or_default());
+        };
+
+        let mut frame = self.clone();
+        frame.node_marks = vec![NodeMark::Pending; self.nodes.len()];
+        frame.refresh_node_widths();
+
+        let mut frames = Vec::with_capacity(order.len() + 1);
+        frames.push(frame.render());
+        for idx in order {
+            frame.node_marks[idx] = NodeMark::Done;
+            frames.push(frame.render());
+        }
+        Ok(frames)
+    }
+
+    /// Draw lines, corners, and arrows with another set of glyphs, e.g. one of
+    /// the [`Theme`] presets.
+    ///
+    /// The theme applies to every render mode. All presets use single-column
+    /// glyphs, so the layout is unchanged; only the characters differ.
+    /// [`set_arrows`](Self::set_arrows) and
+    /// [`set_cycle_arrow`](Self::set_cycle_arrow) adjust the current theme.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::{DAG, RenderMode};
+    /// use ascii_dag::render::ascii::Theme;
+    ///
+    /// let mut dag = DAG::from_edges(
+    ///     &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+    ///     &[(1, 2), (1, 3), (2, 4), (3, 4)],
+    /// );
+    /// dag.set_render_mode(RenderMode::Vertical);
+    /// dag.set_theme(Theme::rounded());
+    ///
+    /// let output = dag.render();
+    /// assert!(output.contains(" ╭─────╮\n"));
+    /// assert!(output.contains(" ╰─────╯\n"));
+    /// assert!(!output.contains('┌'));
+    /// ```
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// Point connector arrows up, toward the dependencies, for a
+    /// "depended on by" reading of the same layout.
+    ///
+    /// Levels stay top-down; only the arrowheads move. Each connector starts
+    /// with `↑` under its upper node and ends in a plain line at the lower one,
+    /// so a divergence (`┌──┐`) now reads as dependents converging upward.
+    /// Horizontal chains use `←`. No reversed graph is built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::{DAG, RenderMode};
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "Lib"), (2, "App")], &[(1, 2)]);
+    /// dag.set_upward_arrows(true);
+    /// assert_eq!(dag.render(), "[Lib] ← [App]\n");
+    ///
+    /// dag.set_render_mode(RenderMode::Vertical);
+    /// assert!(dag.render().contains("↑"));
+    /// assert!(!dag.render().contains("↓"));
+    /// ```
+    pub fn set_upward_arrows(&mut self, on: bool) {
+        self.upward_arrows = on;
+    }
+
+    /// Override the arrowhead glyphs (for fonts that render `↓`/`→` poorly).
+    ///
+    /// `down` is used by the vertical connectors, `right` by horizontal chains
+    /// and cycle paths. Each glyph must be a single-column character (any
+    /// UTF-8 length is fine, but double-width glyphs would misalign the layout).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// dag.set_arrows('v', '>');
+    /// assert_eq!(dag.render(), "[A] > [B]\n");
+    /// ```
+    pub fn set_arrows(&mut self, down: char, right: char) {
+        self.theme.arrow_down = down;
+        self.theme.arrow_right = right;
+    }
+
+    /// Override the glyph that closes a cycle in the cycle warning (default `⇄`).
+    pub fn set_cycle_arrow(&mut self, cycle: char) {
+        self.theme.arrow_cycle = cycle;
     }
 
     /// Render a graph with cycles (not a valid DAG, but useful for error visualization).
-    fn render_cycle(&self, output: &mut String) {
+    fn render_cycle(&self, output: &mut impl RenderTarget) {
         writeln!(output, "⚠️  CYCLE DETECTED - Not a valid DAG").ok();
         writeln!(output).ok();
 
@@ -101,13 +1075,13 @@ impl<'a> DAG<'a> {
                     self.write_node(output, id, label);
 
                     if i < cycle_nodes.len() - 1 {
-                        write!(output, " → ").ok();
+                        write!(output, " {} ", self.theme.arrow_right).ok();
                     } else {
                         // Last node, show it cycles back
                         if let Some(&(first_id, first_label)) =
                             self.nodes.iter().find(|(nid, _)| nid == &cycle_nodes[0])
                         {
-                            write!(output, " {} ", CYCLE_ARROW).ok();
+                            write!(output, " {} ", self.theme.arrow_cycle).ok();
                             self.write_node(output, first_id, first_label);
                         }
                     }
@@ -138,34 +1112,27 @@ impl<'a> DAG<'a> {
         }
 
         // Check if every node has at most 1 parent and 1 child
-        for &(node_id, _) in &self.nodes {
-            let parents = self.get_parents(node_id);
-            let children = self.get_children(node_id);
-
-            if parents.len() > 1 || children.len() > 1 {
-                return false;
-            }
-        }
-
-        true
+        (0..self.nodes.len()).all(|idx| self.is_chain_link(idx))
     }
 
-    /// Render in horizontal mode: [A] → [B] → [C]
-    fn render_horizontal(&self, output: &mut String) {
-        // Find the root (node with no parents)
-        let roots: Vec<_> = self
-            .nodes
-            .iter()
-            .filter(|(id, _)| self.get_parents(*id).is_empty())
-            .collect();
+    /// Render in horizontal mode: [A] → [B] → [C], one line per root.
+    fn render_horizontal(&self, output: &mut impl RenderTarget) {
+        let roots = self.roots();
 
         if roots.is_empty() {
             output.push_str("(no root)");
             return;
         }
 
-        // Follow the chain from root
-        let mut current_id = roots[0].0;
+        for root in roots {
+            self.write_chain(output, root);
+        }
+    }
+
+    /// Write the chain from `start` on one line, following each node's first
+    /// child until a leaf or a node already on the line.
+    fn write_chain(&self, output: &mut impl RenderTarget, start: usize) {
+        let mut current_id = start;
         let mut visited = Vec::new();
 
         loop {
@@ -184,7 +1151,7 @@ impl<'a> DAG<'a> {
             }
 
             // Draw arrow
-            write!(output, " {} ", ARROW_RIGHT).ok();
+            self.write_chain_arrow(output, current_id, children[0]);
 
             // Move to next
             current_id = children[0];
@@ -195,65 +1162,341 @@ impl<'a> DAG<'a> {
             }
         }
 
-        writeln!(output).ok();
+        writeln!(output).ok();
+    }
+
+    /// Write the arrow between two nodes of a horizontal chain: ` → `, or
+    /// ` =N=> ` when there are N parallel edges between them.
+    fn write_chain_arrow(&self, output: &mut impl RenderTarget, from: usize, to: usize) {
+        let count = match (self.node_index(from), self.node_index(to)) {
+            (Some(from_idx), Some(to_idx)) => self.edge_multiplicity(from_idx, to_idx),
+            _ => 1,
+        };
+        if self.upward_arrows {
+            if count > 1 {
+                write!(output, " <={}= ", count).ok();
+            } else {
+                write!(output, " {} ", self.theme.arrow_left).ok();
+            }
+        } else if count > 1 {
+            write!(output, " ={}=> ", count).ok();
+        } else {
+            write!(output, " {} ", self.theme.arrow_right).ok();
+        }
+    }
+
+    /// Glyph under each source node in the first connector row: the line, or
+    /// an arrowhead pointing up into the node with upward arrows.
+    fn source_stub(&self) -> char {
+        if self.upward_arrows {
+            self.theme.arrow_up
+        } else {
+            self.theme.vertical
+        }
+    }
+
+    /// Glyph above each target node in the last connector row: an arrowhead,
+    /// or a plain line with upward arrows.
+    fn target_stub(&self) -> char {
+        if self.upward_arrows {
+            self.theme.vertical
+        } else {
+            self.theme.arrow_down
+        }
+    }
+
+    /// Write a row of arrowheads over `cols`, each followed by `×N` where
+    /// `marks` records N > 1 parallel edges at that column. Annotations only go
+    /// into blank cells (extending the row if needed), so they never overwrite
+    /// a neighbouring arrow.
+    fn write_arrow_row(
+        &self,
+        output: &mut impl RenderTarget,
+        cols: RangeInclusive<usize>,
+        has_arrow: impl Fn(usize) -> bool,
+        marks: &[(usize, usize)],
+    ) {
+        self.write_stub_row(output, cols, has_arrow, marks, self.target_stub());
+    }
+
+    /// The one connector row of 1-to-1 links in [`RenderMode::Compact`]:
+    /// arrowheads straight below the sources, pointing the configured way.
+    fn write_compact_row(
+        &self,
+        output: &mut impl RenderTarget,
+        cols: RangeInclusive<usize>,
+        has_arrow: impl Fn(usize) -> bool,
+        marks: &[(usize, usize)],
+    ) {
+        let arrow = if self.upward_arrows {
+            self.theme.arrow_up
+        } else {
+            self.theme.arrow_down
+        };
+        self.write_stub_row(output, cols, has_arrow, marks, arrow);
+    }
+
+    /// [`write_arrow_row`](Self::write_arrow_row) with `stub` as the glyph.
+    fn write_stub_row(
+        &self,
+        output: &mut impl RenderTarget,
+        cols: RangeInclusive<usize>,
+        has_arrow: impl Fn(usize) -> bool,
+        marks: &[(usize, usize)],
+        stub: char,
+    ) {
+        let start = *cols.start();
+        let mut row: Vec<char> = cols
+            .map(|i| if has_arrow(i) { stub } else { ' ' })
+            .collect();
+
+        for &(col, count) in marks {
+            let mut text = String::new();
+            write!(text, "×{}", count).ok();
+            let at = col + 1 - start;
+            let end = at + text.chars().count();
+            if row.len() < end {
+                row.resize(end, ' ');
+            }
+            if row[at..end].iter().all(|&c| c == ' ') {
+                for (cell, ch) in row[at..end].iter_mut().zip(text.chars()) {
+                    *cell = ch;
+                }
+            }
+        }
+
+        for ch in row {
+            output.push(ch);
+        }
+        writeln!(output).ok();
+    }
+
+    /// Render in cause-chain mode, one block per leaf:
+    ///
+    /// ```text
+    /// StartupFail
+    /// Caused by:
+    ///   0: InitError (also caused by #3)
+    ///   1: DBConnFail
+    ///   2: ConfigMissing
+    ///   3: AuthFail (caused by #2)
+    /// ```
+    ///
+    /// Causes are numbered in depth-first order over parents. Each line continues
+    /// into the next one when that is one of its parents; any other parents
+    /// (branches, or ancestors already listed) are shown as `#k` cross-references.
+    fn render_cause_chain(&self, output: &mut impl RenderTarget) {
+        let mut first = true;
+
+        for leaf_idx in 0..self.nodes.len() {
+            if !self.children[leaf_idx].is_empty() {
+                continue;
+            }
+
+            if !first {
+                writeln!(output).ok();
+            }
+            first = false;
+
+            // Number every ancestor in depth-first preorder (first parent first)
+            let mut numbers: Vec<Option<usize>> = vec![None; self.nodes.len()];
+            let mut order: Vec<usize> = Vec::new();
+            let mut stack: Vec<usize> = self.parents[leaf_idx].iter().rev().copied().collect();
+
+            while let Some(idx) = stack.pop() {
+                if numbers[idx].is_some() {
+                    continue;
+                }
+                numbers[idx] = Some(order.len());
+                order.push(idx);
+                for &parent_idx in self.parents[idx].iter().rev() {
+                    if numbers[parent_idx].is_none() {
+                        stack.push(parent_idx);
+                    }
+                }
+            }
+
+            self.write_cause_label(output, leaf_idx);
+            self.write_cause_refs(output, leaf_idx, order.first().copied(), &numbers);
+            writeln!(output).ok();
+
+            if order.is_empty() {
+                continue;
+            }
+
+            writeln!(output, "Caused by:").ok();
+            for (k, &idx) in order.iter().enumerate() {
+                write!(output, "  {}: ", k).ok();
+                self.write_cause_label(output, idx);
+                self.write_cause_refs(output, idx, order.get(k + 1).copied(), &numbers);
+                writeln!(output).ok();
+            }
+        }
+    }
+
+    /// Write a node for cause-chain output (plain label, placeholders as `⟨id⟩`).
+    fn write_cause_label(&self, output: &mut impl RenderTarget, idx: usize) {
+        let (id, label) = self.nodes[idx];
+        if self.is_auto_created(id) {
+            self.write_node(output, id, label);
+        } else {
+            output.begin_node(id);
+            Self::write_label(output, label);
+            output.end_node();
+        }
+    }
+
+    /// Write `#k` cross-references for the parents of `idx` not covered by the next line.
+    fn write_cause_refs(
+        &self,
+        output: &mut impl RenderTarget,
+        idx: usize,
+        next: Option<usize>,
+        numbers: &[Option<usize>],
+    ) {
+        let continues = next.is_some_and(|n| self.parents[idx].contains(&n));
+
+        let mut wrote_any = false;
+        for &parent_idx in &self.parents[idx] {
+            if continues && Some(parent_idx) == next {
+                continue;
+            }
+            if let Some(k) = numbers[parent_idx] {
+                if !wrote_any {
+                    output.push_str(if continues {
+                        " (also caused by "
+                    } else {
+                        " (caused by "
+                    });
+                    wrote_any = true;
+                } else {
+                    output.push_str(", ");
+                }
+                write!(output, "#{}", k).ok();
+            }
+        }
+        if wrote_any {
+            output.push(')');
+        }
+    }
+
+    /// Render edgeless nodes on `isolated: [A] [B] ...` lines, wrapped at
+    /// [`ISOLATED_WRAP_WIDTH`] columns (a node wider than that gets its own line).
+    fn render_isolated(&self, output: &mut impl RenderTarget, indices: &[usize]) {
+        const PREFIX: &str = "isolated: ";
+
+        output.push_str(PREFIX);
+        let mut col = PREFIX.len();
+        for (i, &idx) in indices.iter().enumerate() {
+            let width = self.get_node_width(idx);
+            if i > 0 {
+                if col + 1 + width > ISOLATED_WRAP_WIDTH {
+                    output.push('\n');
+                    for _ in 0..PREFIX.len() {
+                        output.push(' ');
+                    }
+                    col = PREFIX.len();
+                } else {
+                    output.push(' ');
+                    col += 1;
+                }
+            }
+            let (id, label) = self.nodes[idx];
+            self.write_node(output, id, label);
+            col += width;
+        }
+        output.push('\n');
     }
 
     /// Render in vertical mode (Sugiyama layout).
-    fn render_vertical(&self, output: &mut String) {
+    fn render_vertical(&self, output: &mut impl RenderTarget) {
         // Detect if we have multiple disconnected subgraphs
         let subgraphs = self.find_subgraphs();
 
         if subgraphs.len() > 1 {
-            // Render each subgraph separately
-            for (i, subgraph_nodes) in subgraphs.iter().enumerate() {
+            // Edgeless nodes are collected into one compact section at the end
+            let (isolated, connected): (Vec<&Vec<usize>>, Vec<&Vec<usize>>) =
+                subgraphs.iter().partition(|subgraph| {
+                    self.compact_isolated
+                        && subgraph.len() == 1
+                        && self.children[subgraph[0]].is_empty()
+                        && self.parents[subgraph[0]].is_empty()
+                });
+
+            // Render each subgraph separately, numbering the headed blocks
+            for (i, subgraph_nodes) in connected.iter().enumerate() {
                 if i > 0 {
                     writeln!(output).ok();
                 }
+                if self.component_labels {
+                    output.begin_heading();
+                    writeln!(
+                        output,
+                        "{h}{h} Component {} {h}{h}",
+                        i + 1,
+                        h = self.theme.horizontal
+                    )
+                    .ok();
+                }
                 self.render_subgraph(output, subgraph_nodes);
             }
+
+            if !isolated.is_empty() {
+                if !connected.is_empty() {
+                    writeln!(output).ok();
+                }
+                let isolated: Vec<usize> = isolated.iter().map(|subgraph| subgraph[0]).collect();
+                self.render_isolated(output, &isolated);
+            }
             return;
         }
 
         // Single connected graph - 4-Pass Sugiyama-inspired layout
-        let level_data = self.calculate_levels();
-        let max_level = level_data.iter().map(|(_, l)| *l).max().unwrap_or(0);
-
-        // Group nodes by level
-        let mut levels: Vec<Vec<usize>> = vec![Vec::new(); max_level + 1];
-        for (idx, level) in &level_data {
-            levels[*level].push(*idx);
-        }
-
-        // === PASS 1: Crossing Reduction (Median Heuristic) ===
-        self.reduce_crossings(&mut levels, max_level);
-
-        // === PASS 2: Character-Level Coordinate Assignment ===
-        let node_x_coords = self.assign_x_coordinates(&mut levels, max_level);
-
-        // === PASS 3: Calculate Canvas Width and Centering ===
-        let (level_widths, max_canvas_width) =
-            self.calculate_canvas_dimensions(&levels, &node_x_coords);
+        // === PASS 1 & 2: Crossing Reduction and Coordinate Assignment ===
+        let (levels, node_x_coords) = self.layout_levels();
+        let max_level = (levels.len() - 1).min(self.max_render_depth - 1);
+        self.note_undrawn_edges(output, &levels, max_level);
+        let mut headed_groups = Vec::new();
+
+        // === PASS 3: Canvas Width and Level Offsets ===
+        let drawn = &levels[..=max_level];
+        let (level_widths, max_level_width) =
+            self.calculate_canvas_dimensions(drawn, &node_x_coords);
+        let level_offsets: Vec<usize> = drawn
+            .iter()
+            .zip(&level_widths)
+            .map(|(level_nodes, &width)| {
+                self.level_offset(
+                    self.level_min_x(level_nodes, &node_x_coords),
+                    width,
+                    max_level_width,
+                )
+            })
+            .collect();
+        let canvas_width = level_widths
+            .iter()
+            .zip(&level_offsets)
+            .map(|(width, offset)| width + offset)
+            .max()
+            .unwrap_or(0);
 
         // === PASS 4: Render with Manhattan Routing ===
-        for (current_level, level_nodes) in levels.iter().enumerate() {
+        for (current_level, level_nodes) in drawn.iter().enumerate() {
             if level_nodes.is_empty() {
                 continue;
             }
 
-            // Calculate centering offset for this level
-            let level_width = level_widths[current_level];
-            let level_offset = if max_canvas_width > level_width {
-                (max_canvas_width - level_width) / 2
-            } else {
-                0
-            };
-
             // Find minimum x-coordinate in this level
-            let min_x = level_nodes
-                .iter()
-                .map(|&idx| node_x_coords[idx])
-                .min()
-                .unwrap_or(0);
+            let min_x = self.level_min_x(level_nodes, &node_x_coords);
+            let level_offset = level_offsets[current_level];
+
+            if !self.groups.is_empty() {
+                let placed: Vec<(usize, usize)> = level_nodes
+                    .iter()
+                    .map(|&idx| (idx, node_x_coords[idx] - min_x + level_offset))
+                    .collect();
+                self.write_group_headers(output, &placed, &mut headed_groups);
+            }
 
             // Render nodes at their assigned x-coordinates
             let mut current_col = 0;
@@ -275,12 +1518,7 @@ impl<'a> DAG<'a> {
 
             // Draw connections if not last level
             if current_level < max_level {
-                let next_level_width = level_widths[current_level + 1];
-                let next_level_offset = if max_canvas_width > next_level_width {
-                    (max_canvas_width - next_level_width) / 2
-                } else {
-                    0
-                };
+                let next_level_offset = level_offsets[current_level + 1];
 
                 self.draw_connections_sugiyama(
                     output,
@@ -291,14 +1529,131 @@ impl<'a> DAG<'a> {
                     level_offset,
                     next_level_offset,
                 );
+                self.write_level_gap(output, canvas_width);
+            }
+        }
+        self.write_depth_marker(output, levels.len());
+    }
+
+    /// Horizontal offset of a level within the canvas, per the configured alignment.
+    ///
+    /// `min_x` is the leftmost layout coordinate of the level: median output
+    /// keeps the layout's positions, centered output centers the level's
+    /// width against the widest level, left-aligned output starts it at 0.
+    pub(crate) fn level_offset(
+        &self,
+        min_x: usize,
+        level_width: usize,
+        max_level_width: usize,
+    ) -> usize {
+        match self.alignment {
+            Alignment::Median => min_x,
+            Alignment::Center => max_level_width.saturating_sub(level_width) / 2,
+            Alignment::Left => 0,
+        }
+    }
+
+    /// Leftmost layout coordinate of a level.
+    fn level_min_x(&self, level_nodes: &[usize], x_coords: &[usize]) -> usize {
+        level_nodes
+            .iter()
+            .map(|&idx| x_coords[idx])
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Emit a `── name ──` row for every group that first appears on this level.
+    ///
+    /// `placed` holds the level's nodes with their columns, left to right; each
+    /// header starts above its group's leftmost member, or just after the
+    /// previous header if they would touch. `headed` collects the groups
+    /// already given a header in this rendering.
+    fn write_group_headers(
+        &self,
+        output: &mut impl RenderTarget,
+        placed: &[(usize, usize)],
+        headed: &mut Vec<&'a str>,
+    ) {
+        let mut col = 0;
+        let mut wrote = false;
+        for &(idx, x) in placed {
+            let Some(&group) = self.groups.get(&self.nodes[idx].0) else {
+                continue;
+            };
+            if headed.contains(&group) {
+                continue;
+            }
+            headed.push(group);
+
+            if !wrote {
+                output.begin_heading();
+            }
+            let start = if wrote { x.max(col + 1) } else { x };
+            while col < start {
+                output.push(' ');
+                col += 1;
+            }
+            write!(output, "{h}{h} {} {h}{h}", group, h = self.theme.horizontal).ok();
+            col += str_width(group) + 6;
+            wrote = true;
+        }
+        if wrote {
+            writeln!(output).ok();
+        }
+    }
+
+    /// Emit the configured extra blank rows after a level's connector block,
+    /// then the level separator (a `width`-wide rule) if enabled.
+    fn write_level_gap(&self, output: &mut impl RenderTarget, width: usize) {
+        for _ in 0..self.level_gap {
+            writeln!(output).ok();
+        }
+        if self.level_separators {
+            for _ in 0..width {
+                output.push(self.theme.separator);
+            }
+            writeln!(output).ok();
+        }
+    }
+
+    /// Emit the `… (N more levels)` line when `level_count` exceeds the
+    /// configured maximum render depth.
+    fn write_depth_marker(&self, output: &mut impl RenderTarget, level_count: usize) {
+        let omitted = level_count.saturating_sub(self.max_render_depth);
+        if omitted > 0 {
+            output.note(RenderEvent::HiddenLevels(omitted));
+            let noun = if omitted == 1 { "level" } else { "levels" };
+            writeln!(output, "… ({} more {})", omitted, noun).ok();
+        }
+    }
+
+    /// Report edges between drawn levels that skip a level (not drawn).
+    pub(crate) fn note_undrawn_edges(
+        &self,
+        output: &mut impl RenderTarget,
+        levels: &[Vec<usize>],
+        max_level: usize,
+    ) {
+        let mut level_of = vec![usize::MAX; self.nodes.len()];
+        for (level, nodes) in levels.iter().enumerate().take(max_level + 1) {
+            for &idx in nodes {
+                level_of[idx] = level;
             }
         }
+        let undrawn = (0..self.nodes.len())
+            .filter(|&idx| level_of[idx] != usize::MAX)
+            .flat_map(|idx| self.children[idx].iter().map(move |&child| (idx, child)))
+            .filter(|&(from, to)| level_of[to] != usize::MAX && level_of[to] != level_of[from] + 1)
+            .count();
+        if undrawn > 0 {
+            output.note(RenderEvent::UndrawnEdges(undrawn));
+        }
     }
 
     /// PASS 4: Draw connections with Manhattan routing.
     fn draw_connections_sugiyama(
         &self,
-        output: &mut String,
+        output: &mut impl RenderTarget,
         current_nodes: &[usize],
         next_nodes: &[usize],
         x_coords: &[usize],
@@ -334,16 +1689,21 @@ impl<'a> DAG<'a> {
             })
             .collect();
 
-        // Find connections
+        // Find connections (parallel edges are drawn once and counted)
         let mut connections: Vec<(usize, usize)> = Vec::new();
+        let mut parallel: Vec<(usize, usize, usize)> = Vec::new(); // (from_pos, to_pos, count)
         for &(curr_idx, from_pos) in &current_centers {
-            let node_id = self.nodes[curr_idx].0;
-            for child_id in self.get_children(node_id) {
-                if let Some(&(_, to_pos)) = next_centers
-                    .iter()
-                    .find(|(idx, _)| self.nodes[*idx].0 == child_id)
-                {
+            let children = self.get_children_indices(curr_idx);
+            for (i, &child_idx) in children.iter().enumerate() {
+                if children[..i].contains(&child_idx) {
+                    continue;
+                }
+                if let Some(&(_, to_pos)) = next_centers.iter().find(|(idx, _)| *idx == child_idx) {
                     connections.push((from_pos, to_pos));
+                    let count = self.edge_multiplicity(curr_idx, child_idx);
+                    if count > 1 {
+                        parallel.push((from_pos, to_pos, count));
+                    }
                 }
             }
         }
@@ -380,22 +1740,60 @@ impl<'a> DAG<'a> {
             .max()
             .unwrap_or(0);
 
+        // `×N` marks go next to the arrowhead: at the target, or straight below
+        // the source when the simple drawer is used
+        let marks_at = |at_target: bool| -> Vec<(usize, usize)> {
+            parallel
+                .iter()
+                .map(|&(from, to, count)| (if at_target { to } else { from }, count))
+                .collect()
+        };
+
+        let junctions =
+            |groups: &[(usize, Vec<usize>)]| groups.iter().filter(|(_, v)| v.len() > 1).count();
+
         // Draw based on pattern
-        if has_convergence && !has_divergence {
-            self.draw_convergence_manhattan(output, &target_groups, min_pos, max_pos);
-        } else if has_divergence && !has_convergence {
-            self.draw_divergence_manhattan(output, &source_groups, min_pos, max_pos);
+        // Edges whose target isn't straight below the source need an elbow
+        let shifted = connections.iter().any(|&(f, t)| f != t);
+        let channeled = self.edge_channels && shifted;
+        if channeled || (has_convergence && has_divergence) {
+            if has_convergence {
+                output.note(RenderEvent::Convergences(junctions(&target_groups)));
+            }
+            if has_divergence {
+                output.note(RenderEvent::Divergences(junctions(&source_groups)));
+            }
+            self.draw_mixed_manhattan(output, &connections, max_pos, &marks_at(true));
+        } else if has_convergence {
+            output.note(RenderEvent::Convergences(junctions(&target_groups)));
+            let marks = marks_at(true);
+            self.draw_convergence_manhattan(output, &target_groups, min_pos, max_pos, &marks);
+        } else if has_divergence {
+            output.note(RenderEvent::Divergences(junctions(&source_groups)));
+            let marks = marks_at(true);
+            self.draw_divergence_manhattan(output, &source_groups, min_pos, max_pos, &marks);
+        } else if self.render_mode == RenderMode::Compact {
+            self.write_compact_row(
+                output,
+                min_pos..=max_pos,
+                |i| connections.iter().any(|(f, _)| *f == i),
+                &marks_at(false),
+            );
+        } else if shifted {
+            self.draw_mixed_manhattan(output, &connections, max_pos, &marks_at(true));
         } else {
-            self.draw_simple_manhattan(output, &connections, min_pos, max_pos);
+            let marks = marks_at(false);
+            self.draw_simple_manhattan(output, &connections, min_pos, max_pos, &marks);
         }
     }
 
     fn draw_convergence_manhattan(
         &self,
-        output: &mut String,
+        output: &mut impl RenderTarget,
         target_groups: &[(usize, Vec<usize>)],
         min_pos: usize,
         max_pos: usize,
+        marks: &[(usize, usize)],
     ) {
         let all_sources: Vec<usize> = target_groups
             .iter()
@@ -405,30 +1803,100 @@ impl<'a> DAG<'a> {
         // Line 1: Vertical drops
         for i in min_pos..=max_pos {
             output.push(if all_sources.contains(&i) {
-                V_LINE
+                self.source_stub()
             } else {
                 ' '
             });
         }
         writeln!(output).ok();
 
+        // Staircase rows: fan-ins wider than the configured span merge in steps,
+        // each step joining neighbouring inputs into one junction
+        let mut inputs: Vec<Vec<usize>> = target_groups
+            .iter()
+            .map(|(_, sources)| {
+                let mut sources = sources.clone();
+                sources.sort_unstable();
+                sources.dedup();
+                sources
+            })
+            .collect();
+        let mut stepped = vec![false; target_groups.len()];
+        let too_wide = |points: &[usize]| {
+            points.len() > 1 && points[points.len() - 1] - points[0] > self.max_convergence_span
+        };
+
+        while inputs.iter().any(|points| too_wide(points)) {
+            let mut row = vec![' '; max_pos - min_pos + 1];
+            for (group, (target, _)) in target_groups.iter().enumerate() {
+                let points = &inputs[group];
+                if !too_wide(points) {
+                    for &p in points {
+                        row[p - min_pos] = self.theme.vertical;
+                    }
+                    continue;
+                }
+
+                let mut junctions = Vec::new();
+                for cluster in self.convergence_clusters(points, *target) {
+                    let (first, last) = (cluster[0], cluster[cluster.len() - 1]);
+                    let junction = (*target).clamp(first, last);
+                    junctions.push(junction);
+                    if cluster.len() == 1 {
+                        row[first - min_pos] = self.theme.vertical;
+                        continue;
+                    }
+
+                    for cell in &mut row[first - min_pos..=last - min_pos] {
+                        *cell = self.theme.horizontal;
+                    }
+                    for &p in cluster {
+                        row[p - min_pos] = self.theme.tee_up;
+                    }
+                    row[first - min_pos] = self.theme.bottom_left;
+                    row[last - min_pos] = self.theme.bottom_right;
+                    row[junction - min_pos] = if junction == first {
+                        self.theme.tee_right
+                    } else if junction == last {
+                        self.theme.tee_left
+                    } else if cluster.contains(&junction) {
+                        self.theme.cross
+                    } else {
+                        self.theme.tee_down
+                    };
+                }
+                junctions.dedup();
+                inputs[group] = junctions;
+                stepped[group] = true;
+            }
+            for ch in row {
+                output.push(ch);
+            }
+            writeln!(output).ok();
+        }
+
         // Line 2: Horizontal convergence └──┴──┘
         for i in min_pos..=max_pos {
             let mut ch = ' ';
-            for (_, sources) in target_groups.iter() {
+            for (group, sources) in inputs.iter().enumerate() {
                 if sources.len() <= 1 {
+                    // A staircase that already merged everything, or a plain
+                    // 1-to-1 link, continues straight down
+                    if sources.contains(&i) && (stepped[group] || ch == ' ') {
+                        ch = self.theme.vertical;
+                    }
                     continue;
                 }
-                let min_src = *sources.iter().min().unwrap();
-                let max_src = *sources.iter().max().unwrap();
+                let min_src = sources[0];
+                let max_src = sources[sources.len() - 1];
                 if i == min_src {
-                    ch = CORNER_DR;
+                    ch = self.theme.bottom_left;
                 } else if i == max_src {
-                    ch = CORNER_DL;
+                    ch = self.theme.bottom_right;
                 } else if sources.contains(&i) {
-                    ch = TEE_UP;
+                    ch = self.theme.tee_up;
                 } else if i > min_src && i < max_src {
-                    ch = H_LINE;
+                    ch = self.theme.horizontal;
                 }
             }
             output.push(ch);
@@ -436,29 +1904,74 @@ impl<'a> DAG<'a> {
         writeln!(output).ok();
 
         // Line 3: Arrows down
-        for i in min_pos..=max_pos {
-            output.push(if target_groups.iter().any(|(t, _)| *t == i) {
-                ARROW_DOWN
-            } else {
-                ' '
-            });
+        self.write_arrow_row(
+            output,
+            min_pos..=max_pos,
+            |i| target_groups.iter().any(|(t, _)| *t == i),
+            marks,
+        );
+    }
+
+    /// Split sorted convergence inputs into runs of neighbours spanning at most
+    /// `max_convergence_span` columns.
+    ///
+    /// Inputs left and right of `target` are grouped from the outside in, so
+    /// junctions step toward the target. A run takes at least two inputs when
+    /// it can, and two lone inputs straddling the target are joined directly,
+    /// so every staircase step reduces the number of lines.
+    fn convergence_clusters<'p>(&self, points: &'p [usize], target: usize) -> Vec<&'p [usize]> {
+        let split = points.partition_point(|&p| p <= target);
+        if split <= 1 && points.len() - split <= 1 {
+            return vec![points];
         }
-        writeln!(output).ok();
+
+        let mut clusters = Vec::new();
+        let left = &points[..split];
+        let mut start = 0;
+        while start < left.len() {
+            let mut end = start + 1;
+            while end < left.len() && left[end] - left[start] <= self.max_convergence_span {
+                end += 1;
+            }
+            if end == start + 1 && end < left.len() {
+                end += 1;
+            }
+            clusters.push(&left[start..end]);
+            start = end;
+        }
+
+        let right = &points[split..];
+        let mut right_clusters = Vec::new();
+        let mut end = right.len();
+        while end > 0 {
+            let mut start = end - 1;
+            while start > 0 && right[end - 1] - right[start - 1] <= self.max_convergence_span {
+                start -= 1;
+            }
+            if start + 1 == end && start > 0 {
+                start -= 1;
+            }
+            right_clusters.push(&right[start..end]);
+            end = start;
+        }
+        clusters.extend(right_clusters.into_iter().rev());
+        clusters
     }
 
     fn draw_divergence_manhattan(
         &self,
-        output: &mut String,
+        output: &mut impl RenderTarget,
         source_groups: &[(usize, Vec<usize>)],
         min_pos: usize,
         max_pos: usize,
+        marks: &[(usize, usize)],
     ) {
         let all_sources: Vec<usize> = source_groups.iter().map(|(s, _)| *s).collect();
 
         // Line 1: Vertical from sources
         for i in min_pos..=max_pos {
             output.push(if all_sources.contains(&i) {
-                V_LINE
+                self.source_stub()
             } else {
                 ' '
             });
@@ -468,20 +1981,26 @@ impl<'a> DAG<'a> {
         // Line 2: Horizontal divergence ┌──┬──┐
         for i in min_pos..=max_pos {
             let mut ch = ' ';
-            for (_, targets) in source_groups.iter() {
+            for (source, targets) in source_groups.iter() {
                 if targets.len() <= 1 {
+                    // Plain 1-to-1 link
+                    if *source == i && ch == ' ' {
+                        ch = self.theme.vertical;
+                    }
                     continue;
                 }
-                let min_tgt = *targets.iter().min().unwrap();
-                let max_tgt = *targets.iter().max().unwrap();
+                let (Some(&min_tgt), Some(&max_tgt)) = (targets.iter().min(), targets.iter().max())
+                else {
+                    continue;
+                };
                 if i == min_tgt {
-                    ch = CORNER_UR;
+                    ch = self.theme.top_left;
                 } else if i == max_tgt {
-                    ch = CORNER_UL;
+                    ch = self.theme.top_right;
                 } else if targets.contains(&i) {
-                    ch = TEE_DOWN;
+                    ch = self.theme.tee_down;
                 } else if i > min_tgt && i < max_tgt {
-                    ch = H_LINE;
+                    ch = self.theme.horizontal;
                 }
             }
             output.push(ch);
@@ -493,27 +2012,296 @@ impl<'a> DAG<'a> {
             .iter()
             .flat_map(|(_, t)| t.iter().copied())
             .collect();
-        for i in min_pos..=max_pos {
-            output.push(if all_targets.contains(&i) {
-                ARROW_DOWN
+        self.write_arrow_row(
+            output,
+            min_pos..=max_pos,
+            |i| all_targets.contains(&i),
+            marks,
+        );
+    }
+
+    /// Route a band where some nodes converge and others diverge.
+    ///
+    /// Each source fans out to its targets along one horizontal segment
+    /// (`└──┬──┐`), and segments that would overlap go on separate lane rows.
+    /// Segments may share a lane where they meet at a common target, which
+    /// draws the convergence there (`──┬──`). A source standing above another
+    /// source's target turns off on a higher lane, so the two lines never share
+    /// a column stretch, and lines crossing without joining are drawn as a
+    /// hop (`─│─`) rather than a junction.
+    ///
+    /// With [edge channels](DAG::set_edge_channels) every edge is a net of its
+    /// own and lanes are never shared, so each bend gets its own elbow. Nets
+    /// are then also ordered so that an edge leaving from under another's
+    /// span turns off above it and one landing under another's span turns
+    /// off below it, which nests the elbows instead of crossing them.
+    fn draw_mixed_manhattan(
+        &self,
+        output: &mut impl RenderTarget,
+        connections: &[(usize, usize)],
+        max_pos: usize,
+        marks: &[(usize, usize)],
+    ) {
+        // One net per source (per edge with channels):
+        // (source, lowest column, highest column, targets)
+        let per_edge = self.edge_channels;
+        let mut nets: Vec<(usize, usize, usize, Vec<usize>)> = Vec::new();
+        for &(from, to) in connections {
+            let net = nets
+                .iter_mut()
+                .find(|(source, ..)| !per_edge && *source == from);
+            match net {
+                Some((_, lo, hi, targets)) => {
+                    *lo = (*lo).min(to);
+                    *hi = (*hi).max(to);
+                    targets.push(to);
+                }
+                None => nets.push((from, from.min(to), from.max(to), vec![to])),
+            }
+        }
+        nets.sort_by_key(|&(_, lo, hi, _)| (lo, hi));
+
+        // Nets that need a horizontal segment get a lane; a net must sit below
+        // every net whose source is one of its target columns
+        let laned = |n: usize| nets[n].1 != nets[n].2;
+        let inside = |col: usize, m: usize| nets[m].1 < col && col < nets[m].2;
+        let above = |n: usize| -> Vec<usize> {
+            (0..nets.len())
+                .filter(|&m| m != n && laned(m))
+                .filter(|&m| {
+                    nets[n].3.contains(&nets[m].0)
+                        || (per_edge && (inside(nets[m].0, n) || inside(nets[n].3[0], m)))
+                })
+                .collect()
+        };
+        // Two nets that each need the other to turn off first (A → E under B
+        // while B → D under A) can't both run straight down. One of them then
+        // takes a detour: it turns off on an upper lane into the free column
+        // beside its source and fans out to its targets further down.
+        // `detour[n]` = (gap column, upper lane)
+        let mut detour: Vec<Option<(usize, usize)>> = vec![None; nets.len()];
+        // Lane where a net's line from its source turns off
+        let source_lane =
+            |m: usize, lane_of: &[Option<usize>], detour: &[Option<(usize, usize)>]| {
+                detour[m].map(|(_, upper)| upper).or(lane_of[m])
+            };
+        // Spans per lane: (net, lowest column, highest column, upper part of a detour)
+        let mut lanes: Vec<Vec<(usize, usize, usize, bool)>> = Vec::new();
+        let mut place = |min_lane: usize, (n, lo, hi, upper): (usize, usize, usize, bool)| {
+            // Spans may only touch at a target both are heading for
+            let shared = |col: usize, m: usize, m_upper: bool| {
+                !per_edge
+                    && !upper
+                    && !m_upper
+                    && nets[n].3.contains(&col)
+                    && nets[m].3.contains(&col)
+            };
+            let fits = |&(m, o_lo, o_hi, m_upper): &(usize, usize, usize, bool)| {
+                hi < o_lo
+                    || o_hi < lo
+                    || (hi == o_lo && shared(hi, m, m_upper))
+                    || (o_hi == lo && shared(lo, m, m_upper))
+            };
+            let lane = (min_lane..lanes.len())
+                .find(|&lane| lanes[lane].iter().all(fits))
+                .unwrap_or(lanes.len());
+            if lane == lanes.len() {
+                lanes.push(Vec::new());
+            }
+            lanes[lane].push((n, lo, hi, upper));
+            lane
+        };
+        let mut lane_of: Vec<Option<usize>> = vec![None; nets.len()];
+        let mut pending: Vec<usize> = (0..nets.len()).filter(|&n| laned(n)).collect();
+        while !pending.is_empty() {
+            // Next net whose upper neighbours have turned off
+            let ready = pending.iter().position(|&n| {
+                above(n)
+                    .iter()
+                    .all(|&m| source_lane(m, &lane_of, &detour).is_some())
+            });
+            let Some(pick) = ready else {
+                // They form a loop: detour the net most others wait on
+                let waiting_on =
+                    |v: usize| pending.iter().filter(|&&m| above(m).contains(&v)).count();
+                let Some(v) = pending
+                    .iter()
+                    .copied()
+                    .filter(|&v| detour[v].is_none())
+                    .max_by_key(|&v| (waiting_on(v), core::cmp::Reverse(v)))
+                else {
+                    break;
+                };
+                let (source, _, hi, _) = nets[v];
+                let gap = if hi > source { source + 1 } else { source - 1 };
+                let upper = place(0, (v, source.min(gap), source.max(gap), true));
+                detour[v] = Some((gap, upper));
+                continue;
+            };
+            let n = pending.remove(pick);
+            let mut min_lane = above(n)
+                .iter()
+                .filter_map(|&m| source_lane(m, &lane_of, &detour))
+                .map(|lane| lane + 1)
+                .max()
+                .unwrap_or(0);
+            let (source, mut lo, mut hi, ref targets) = nets[n];
+            if let Some((gap, upper)) = detour[n] {
+                // The fan-out starts from the gap column; a target right
+                // below the source is reached from the upper lane
+                min_lane = min_lane.max(upper + 1);
+                let cols = targets
+                    .iter()
+                    .copied()
+                    .filter(|&t| t != source)
+                    .chain([gap]);
+                lo = cols.clone().min().unwrap_or(gap);
+                hi = cols.max().unwrap_or(gap);
+            }
+            lane_of[n] = Some(place(min_lane, (n, lo, hi, false)));
+        }
+
+        // Each net's direction masks per lane row and column
+        let rows = lanes.len();
+        let mut grid: Vec<Vec<Vec<(usize, u8)>>> = vec![vec![Vec::new(); max_pos + 1]; rows];
+        let mut link = |row: usize, col: usize, net: usize, mask: u8| {
+            let cell: &mut Vec<(usize, u8)> = &mut grid[row][col];
+            match cell.iter_mut().find(|(n, _)| *n == net) {
+                Some((_, m)) => *m |= mask,
+                None => cell.push((net, mask)),
+            }
+        };
+        for (n, (source, lo, hi, targets)) in nets.iter().enumerate() {
+            let Some(lane) = lane_of[n] else {
+                for row in 0..rows {
+                    link(row, *source, n, LINK_UP | LINK_DOWN);
+                }
+                continue;
+            };
+            // Row where the source line turns off, and the span fanned out on `lane`
+            let (turn, lo, hi) = match detour[n] {
+                Some((gap, upper)) => {
+                    for col in (*source).min(gap)..(*source).max(gap) {
+                        link(upper, col, n, LINK_RIGHT);
+                        link(upper, col + 1, n, LINK_LEFT);
+                    }
+                    link(upper, gap, n, LINK_DOWN);
+                    for row in upper + 1..lane {
+                        link(row, gap, n, LINK_UP | LINK_DOWN);
+                    }
+                    link(lane, gap, n, LINK_UP);
+                    let cols = targets.iter().copied().filter(|t| t != source).chain([gap]);
+                    (
+                        upper,
+                        cols.clone().min().unwrap_or(gap),
+                        cols.max().unwrap_or(gap),
+                    )
+                }
+                None => (lane, *lo, *hi),
+            };
+            for row in 0..turn {
+                link(row, *source, n, LINK_UP | LINK_DOWN);
+            }
+            link(turn, *source, n, LINK_UP);
+            for col in lo..hi {
+                link(lane, col, n, LINK_RIGHT);
+                link(lane, col + 1, n, LINK_LEFT);
+            }
+            for &target in targets {
+                // Only a detour reaches its own column before its lane
+                let from = if target == *source { turn } else { lane };
+                link(from, target, n, LINK_DOWN);
+                for row in from + 1..rows {
+                    link(row, target, n, LINK_UP | LINK_DOWN);
+                }
+            }
+        }
+
+        // Line 1: Vertical from sources
+        for i in 0..=max_pos {
+            output.push(if nets.iter().any(|(source, ..)| *source == i) {
+                self.source_stub()
             } else {
                 ' '
             });
         }
         writeln!(output).ok();
+
+        // Lane rows: lines from the same source or heading for the same
+        // target join, others hop. A detour's fan-out only passes its source.
+        let ends_at = |n: usize, row: usize, col: usize| match detour[n] {
+            Some((gap, upper)) => {
+                (nets[n].0 == col && row <= upper) || gap == col || nets[n].3.contains(&col)
+            }
+            None => nets[n].0 == col || nets[n].3.contains(&col),
+        };
+        for (r, row) in grid.iter().enumerate() {
+            for (col, cell) in row.iter().enumerate() {
+                let joined = cell
+                    .iter()
+                    .filter(|(n, _)| ends_at(*n, r, col))
+                    .fold(0, |mask, (_, m)| mask | m);
+                let crossing = cell.iter().filter(|(n, _)| !ends_at(*n, r, col));
+                let ch = match (joined, crossing.clone().count()) {
+                    (_, 0) => self.link_glyph(joined),
+                    (0, 1) => self.link_glyph(crossing.fold(0, |mask, (_, m)| mask | m)),
+                    _ => self.theme.vertical,
+                };
+                output.push(ch);
+            }
+            writeln!(output).ok();
+        }
+
+        // Last line: Arrows down
+        self.write_arrow_row(
+            output,
+            0..=max_pos,
+            |i| connections.iter().any(|&(_, to)| to == i),
+            marks,
+        );
+    }
+
+    /// Box-drawing glyph joining the directions set in `mask` (`LINK_*` bits).
+    fn link_glyph(&self, mask: u8) -> char {
+        const ALL: u8 = LINK_UP | LINK_DOWN | LINK_LEFT | LINK_RIGHT;
+        const TEE_DOWN: u8 = LINK_LEFT | LINK_RIGHT | LINK_DOWN;
+        const TEE_UP: u8 = LINK_LEFT | LINK_RIGHT | LINK_UP;
+        const TEE_RIGHT: u8 = LINK_UP | LINK_DOWN | LINK_RIGHT;
+        const TEE_LEFT: u8 = LINK_UP | LINK_DOWN | LINK_LEFT;
+        const TOP_LEFT: u8 = LINK_DOWN | LINK_RIGHT;
+        const TOP_RIGHT: u8 = LINK_DOWN | LINK_LEFT;
+        const BOTTOM_LEFT: u8 = LINK_UP | LINK_RIGHT;
+        const BOTTOM_RIGHT: u8 = LINK_UP | LINK_LEFT;
+
+        let t = &self.theme;
+        match mask {
+            0 => ' ',
+            ALL => t.cross,
+            TEE_DOWN => t.tee_down,
+            TEE_UP => t.tee_up,
+            TEE_RIGHT => t.tee_right,
+            TEE_LEFT => t.tee_left,
+            TOP_LEFT => t.top_left,
+            TOP_RIGHT => t.top_right,
+            BOTTOM_LEFT => t.bottom_left,
+            BOTTOM_RIGHT => t.bottom_right,
+            m if m & (LINK_LEFT | LINK_RIGHT) != 0 => t.horizontal,
+            _ => t.vertical,
+        }
     }
 
     fn draw_simple_manhattan(
         &self,
-        output: &mut String,
+        output: &mut impl RenderTarget,
         connections: &[(usize, usize)],
         min_pos: usize,
         max_pos: usize,
+        marks: &[(usize, usize)],
     ) {
         // Line 1: Vertical
         for i in min_pos..=max_pos {
             output.push(if connections.iter().any(|(f, _)| *f == i) {
-                V_LINE
+                self.source_stub()
             } else {
                 ' '
             });
@@ -521,18 +2309,20 @@ impl<'a> DAG<'a> {
         writeln!(output).ok();
 
         // Line 2: Arrows
-        for i in min_pos..=max_pos {
-            output.push(if connections.iter().any(|(f, _)| *f == i) {
-                ARROW_DOWN
-            } else {
-                ' '
-            });
-        }
-        writeln!(output).ok();
+        self.write_arrow_row(
+            output,
+            min_pos..=max_pos,
+            |i| connections.iter().any(|(f, _)| *f == i),
+            marks,
+        );
     }
 
     /// Render a specific subgraph.
-    pub(crate) fn render_subgraph(&self, output: &mut String, subgraph_indices: &[usize]) {
+    pub(crate) fn render_subgraph(
+        &self,
+        output: &mut impl RenderTarget,
+        subgraph_indices: &[usize],
+    ) {
         // Build a mini-DAG with just these nodes
         let _subgraph_node_ids: Vec<usize> = subgraph_indices
             .iter()
@@ -552,15 +2342,9 @@ impl<'a> DAG<'a> {
         // Check if it's a simple chain - render horizontally
         if self.is_subgraph_simple_chain(subgraph_indices) {
             // Render horizontally
-            let roots: Vec<_> = subgraph_indices
-                .iter()
-                .filter(|&&idx| {
-                    let node_id = self.nodes[idx].0;
-                    self.get_parents(node_id).is_empty()
-                })
-                .collect();
+            let roots = self.component_roots(subgraph_indices);
 
-            if let Some(&&root_idx) = roots.first() {
+            if let Some(&root_idx) = roots.first() {
                 let mut current_id = self.nodes[root_idx].0;
                 let mut visited = Vec::new();
 
@@ -579,7 +2363,7 @@ impl<'a> DAG<'a> {
                         break;
                     }
 
-                    write!(output, " {} ", ARROW_RIGHT).ok();
+                    self.write_chain_arrow(output, current_id, children[0]);
                     current_id = children[0];
 
                     if visited.contains(&current_id) {
@@ -593,11 +2377,40 @@ impl<'a> DAG<'a> {
         }
 
         // Render vertically for complex subgraphs
-        for (current_level, node_indices) in levels.iter().enumerate() {
+        let max_level = max_level.min(self.max_render_depth - 1);
+        self.note_undrawn_edges(output, &levels, max_level);
+        let mut headed_groups = Vec::new();
+        // Nodes are packed 3 columns apart
+        let canvas_width = levels
+            .iter()
+            .take(max_level + 1)
+            .map(|node_indices| {
+                let nodes: usize = node_indices
+                    .iter()
+                    .map(|&idx| self.get_node_width(idx))
+                    .sum();
+                nodes + 3 * node_indices.len().saturating_sub(1)
+            })
+            .max()
+            .unwrap_or(0);
+        for (current_level, node_indices) in levels.iter().enumerate().take(max_level + 1) {
             if node_indices.is_empty() {
                 continue;
             }
 
+            if !self.groups.is_empty() {
+                let mut col = 0;
+                let placed: Vec<(usize, usize)> = node_indices
+                    .iter()
+                    .map(|&idx| {
+                        let x = col;
+                        col += self.get_node_width(idx) + 3;
+                        (idx, x)
+                    })
+                    .collect();
+                self.write_group_headers(output, &placed, &mut headed_groups);
+            }
+
             // Draw nodes with appropriate formatting
             for (pos, &idx) in node_indices.iter().enumerate() {
                 let (id, label) = self.nodes[idx];
@@ -612,13 +2425,15 @@ impl<'a> DAG<'a> {
             // Draw connections if not last level
             if current_level < max_level {
                 self.draw_vertical_connections(output, node_indices, &levels[current_level + 1]);
+                self.write_level_gap(output, canvas_width);
             }
         }
+        self.write_depth_marker(output, levels.len());
     }
 
     fn draw_vertical_connections(
         &self,
-        output: &mut String,
+        output: &mut impl RenderTarget,
         current_nodes: &[usize],
         next_nodes: &[usize],
     ) {
@@ -646,19 +2461,24 @@ impl<'a> DAG<'a> {
             pos += label_len + 3; // +3 for spacing
         }
 
-        // Find connections
+        // Find connections (parallel edges are drawn once and counted)
         let mut connections: Vec<(usize, usize, usize)> = Vec::new(); // (from_idx, from_pos, to_pos)
+        let mut parallel: Vec<(usize, usize, usize)> = Vec::new(); // (from_pos, to_pos, count)
 
         for &(current_idx, from_pos, _, _) in &current_positions {
-            let node_id = self.nodes[current_idx].0;
-            let children = self.get_children(node_id);
+            let children = self.get_children_indices(current_idx);
 
-            for child_id in children {
-                if let Some(&(_, to_pos)) = next_positions
-                    .iter()
-                    .find(|(idx, _)| self.nodes[*idx].0 == child_id)
+            for (i, &child_idx) in children.iter().enumerate() {
+                if children[..i].contains(&child_idx) {
+                    continue;
+                }
+                if let Some(&(_, to_pos)) = next_positions.iter().find(|(idx, _)| *idx == child_idx)
                 {
                     connections.push((current_idx, from_pos, to_pos));
+                    let count = self.edge_multiplicity(current_idx, child_idx);
+                    if count > 1 {
+                        parallel.push((from_pos, to_pos, count));
+                    }
                 }
             }
         }
@@ -695,26 +2515,66 @@ impl<'a> DAG<'a> {
         // Check if we have any divergence (one source to multiple targets)
         let has_any_divergence = source_groups.iter().any(|(_, v)| v.len() > 1);
 
+        // `×N` marks go next to the arrowhead (see draw_connections_sugiyama)
+        let marks_at = |at_target: bool| -> Vec<(usize, usize)> {
+            parallel
+                .iter()
+                .map(|&(from, to, count)| (if at_target { to } else { from }, count))
+                .collect()
+        };
+
         // Choose rendering strategy based on pattern complexity
-        if has_any_convergence && !has_any_divergence {
+        let junctions = |groups: &[(usize, Vec<(usize, usize, usize)>)]| {
+            groups.iter().filter(|(_, v)| v.len() > 1).count()
+        };
+        let channeled = self.edge_channels && connections.iter().any(|&(_, f, t)| f != t);
+        if channeled || (has_any_convergence && has_any_divergence) {
+            // Mixed pattern (or edge channels): every source, or every edge,
+            // routed to its targets on its own lane
+            if has_any_convergence {
+                output.note(RenderEvent::Convergences(junctions(&target_groups)));
+            }
+            if has_any_divergence {
+                output.note(RenderEvent::Divergences(junctions(&source_groups)));
+            }
+            let positions: Vec<(usize, usize)> = connections
+                .iter()
+                .map(|&(_, from, to)| (from, to))
+                .collect();
+            let max_pos = positions.iter().map(|&(f, t)| f.max(t)).max().unwrap_or(0);
+            self.draw_mixed_manhattan(output, &positions, max_pos, &marks_at(true));
+        } else if has_any_convergence {
             // Pure convergence pattern(s)
-            self.draw_multiple_convergences(output, &target_groups);
-        } else if has_any_divergence && !has_any_convergence {
+            output.note(RenderEvent::Convergences(junctions(&target_groups)));
+            self.draw_multiple_convergences(output, &target_groups, &marks_at(true));
+        } else if has_any_divergence {
             // Pure divergence pattern(s)
-            self.draw_multiple_divergences(output, &source_groups);
-        } else if has_any_convergence && has_any_divergence {
-            // Mixed pattern - draw simple connections
-            self.draw_simple_verticals(output, &connections);
+            output.note(RenderEvent::Divergences(junctions(&source_groups)));
+            self.draw_multiple_divergences(output, &source_groups, &marks_at(true));
+        } else if self.render_mode == RenderMode::Compact {
+            // Simple 1-to-1 connections, one row
+            let max_pos = connections
+                .iter()
+                .map(|(_, from, to)| (*from).max(*to))
+                .max()
+                .unwrap_or(0);
+            self.write_compact_row(
+                output,
+                0..=max_pos,
+                |i| connections.iter().any(|(_, from, _)| *from == i),
+                &marks_at(false),
+            );
         } else {
             // Simple 1-to-1 connections
-            self.draw_simple_verticals(output, &connections);
+            self.draw_simple_verticals(output, &connections, &marks_at(false));
         }
     }
 
     fn draw_multiple_convergences(
         &self,
-        output: &mut String,
+        output: &mut impl RenderTarget,
         target_groups: &[(usize, Vec<(usize, usize, usize)>)],
+        marks: &[(usize, usize)],
     ) {
         // Find all unique source and target positions
         let all_connections: Vec<_> = target_groups
@@ -735,7 +2595,7 @@ impl<'a> DAG<'a> {
         // Line 1: Vertical drops from sources
         for i in min_pos..=max_pos {
             if all_connections.iter().any(|(_, from, _)| *from == i) {
-                output.push(V_LINE);
+                output.push(self.source_stub());
             } else {
                 output.push(' ');
             }
@@ -748,22 +2608,29 @@ impl<'a> DAG<'a> {
 
             for (_, conns) in target_groups.iter() {
                 if conns.len() <= 1 {
+                    // Plain 1-to-1 link
+                    if conns.iter().any(|(_, from, _)| *from == i) && char_at_pos == ' ' {
+                        char_at_pos = self.theme.vertical;
+                    }
                     continue;
                 }
 
                 let sources: Vec<_> = conns.iter().map(|(_, from, _)| from).collect();
-                let min_source = **sources.iter().min().unwrap();
-                let max_source = **sources.iter().max().unwrap();
+                let (Some(&&min_source), Some(&&max_source)) =
+                    (sources.iter().min(), sources.iter().max())
+                else {
+                    continue;
+                };
 
                 if i == min_source {
-                    char_at_pos = CORNER_DR; // └
+                    char_at_pos = self.theme.bottom_left; // └
                 } else if i == max_source {
-                    char_at_pos = CORNER_DL; // ┘
+                    char_at_pos = self.theme.bottom_right; // ┘
                 } else if sources.contains(&&i) {
-                    char_at_pos = TEE_UP; // ┴
+                    char_at_pos = self.theme.tee_up; // ┴
                 } else if i > min_source && i < max_source {
                     if char_at_pos == ' ' {
-                        char_at_pos = H_LINE; // ─
+                        char_at_pos = self.theme.horizontal; // ─
                     }
                 }
             }
@@ -773,20 +2640,19 @@ impl<'a> DAG<'a> {
         writeln!(output).ok();
 
         // Line 3: Arrows pointing down to targets
-        for i in min_pos..=max_pos {
-            if target_groups.iter().any(|(target_pos, _)| *target_pos == i) {
-                output.push(ARROW_DOWN);
-            } else {
-                output.push(' ');
-            }
-        }
-        writeln!(output).ok();
+        self.write_arrow_row(
+            output,
+            min_pos..=max_pos,
+            |i| target_groups.iter().any(|(target_pos, _)| *target_pos == i),
+            marks,
+        );
     }
 
     fn draw_multiple_divergences(
         &self,
-        output: &mut String,
+        output: &mut impl RenderTarget,
         source_groups: &[(usize, Vec<(usize, usize, usize)>)],
+        marks: &[(usize, usize)],
     ) {
         let all_connections: Vec<_> = source_groups
             .iter()
@@ -808,7 +2674,7 @@ impl<'a> DAG<'a> {
             if i < min_pos {
                 output.push(' ');
             } else if all_connections.iter().any(|(_, from, _)| *from == i) {
-                output.push(V_LINE);
+                output.push(self.source_stub());
             } else {
                 output.push(' ');
             }
@@ -822,22 +2688,29 @@ impl<'a> DAG<'a> {
             if i >= min_pos {
                 for (_, conns) in source_groups.iter() {
                     if conns.len() <= 1 {
+                        // Plain 1-to-1 link
+                        if conns.iter().any(|(_, from, _)| *from == i) && char_at_pos == ' ' {
+                            char_at_pos = self.theme.vertical;
+                        }
                         continue;
                     }
 
                     let targets: Vec<_> = conns.iter().map(|(_, _, to)| to).collect();
-                    let min_target = **targets.iter().min().unwrap();
-                    let max_target = **targets.iter().max().unwrap();
+                    let (Some(&&min_target), Some(&&max_target)) =
+                        (targets.iter().min(), targets.iter().max())
+                    else {
+                        continue;
+                    };
 
                     if i == min_target {
-                        char_at_pos = CORNER_UR; // ┌
+                        char_at_pos = self.theme.top_left; // ┌
                     } else if i == max_target {
-                        char_at_pos = CORNER_UL; // ┐
+                        char_at_pos = self.theme.top_right; // ┐
                     } else if targets.contains(&&i) {
-                        char_at_pos = TEE_DOWN; // ┬
+                        char_at_pos = self.theme.tee_down; // ┬
                     } else if i > min_target && i < max_target {
                         if char_at_pos == ' ' {
-                            char_at_pos = H_LINE; // ─
+                            char_at_pos = self.theme.horizontal; // ─
                         }
                     }
                 }
@@ -848,19 +2721,20 @@ impl<'a> DAG<'a> {
         writeln!(output).ok();
 
         // Line 3: Arrows pointing down
-        for i in 0..=max_pos {
-            if i < min_pos {
-                output.push(' ');
-            } else if all_connections.iter().any(|(_, _, to)| *to == i) {
-                output.push(ARROW_DOWN);
-            } else {
-                output.push(' ');
-            }
-        }
-        writeln!(output).ok();
+        self.write_arrow_row(
+            output,
+            0..=max_pos,
+            |i| i >= min_pos && all_connections.iter().any(|(_, _, to)| *to == i),
+            marks,
+        );
     }
 
-    fn draw_simple_verticals(&self, output: &mut String, connections: &[(usize, usize, usize)]) {
+    fn draw_simple_verticals(
+        &self,
+        output: &mut impl RenderTarget,
+        connections: &[(usize, usize, usize)],
+        marks: &[(usize, usize)],
+    ) {
         let max_pos = connections
             .iter()
             .map(|(_, from, to)| (*from).max(*to))
@@ -870,7 +2744,7 @@ impl<'a> DAG<'a> {
         // Line 1: Vertical lines
         for i in 0..=max_pos {
             if connections.iter().any(|(_, from, _)| *from == i) {
-                output.push(V_LINE);
+                output.push(self.source_stub());
             } else {
                 output.push(' ');
             }
@@ -878,13 +2752,11 @@ impl<'a> DAG<'a> {
         writeln!(output).ok();
 
         // Line 2: Arrows
-        for i in 0..=max_pos {
-            if connections.iter().any(|(_, from, _)| *from == i) {
-                output.push(ARROW_DOWN);
-            } else {
-                output.push(' ');
-            }
-        }
-        writeln!(output).ok();
+        self.write_arrow_row(
+            output,
+            0..=max_pos,
+            |i| connections.iter().any(|(_, from, _)| *from == i),
+            marks,
+        );
     }
 }
@@ -0,0 +1,96 @@
+//! Rendering of graphs over the node cap (see `DAG::set_max_render_nodes`).
+
+use crate::graph::DAG;
+use crate::render::ascii::{RenderEvent, RenderTarget};
+use alloc::{format, vec, vec::Vec};
+
+impl<'a> DAG<'a> {
+    /// Render the `max_render_nodes` nodes with the most descendants plus a
+    /// summary node for everything else.
+    pub(crate) fn render_capped(&self, output: &mut impl RenderTarget) {
+        // Rank by descendant count, ties in insertion order
+        let mut ranked: Vec<(usize, usize)> = (0..self.nodes.len())
+            .map(|idx| {
+                let reachable = self.reachable_mask(idx, false);
+                (idx, reachable.iter().filter(|&&r| r).count() - 1)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        let mut kept = vec![false; self.nodes.len()];
+        for &(idx, _) in ranked.iter().take(self.max_render_nodes) {
+            kept[idx] = true;
+        }
+        let hidden = self.nodes.len() - self.max_render_nodes;
+        output.note(RenderEvent::HiddenNodes(hidden));
+
+        // First ID not in use, counting down from the top of the range
+        let summary_id = (0..=usize::MAX)
+            .rev()
+            .find(|&id| self.node_index(id).is_none())
+            .unwrap_or(usize::MAX);
+        let summary_label = format!("… +{} hidden", hidden);
+
+        // Every render option carries over; only the graph itself is rebuilt
+        let mut reduced: DAG<'_> = self.clone();
+        reduced.nodes.clear();
+        reduced.edges.clear();
+        reduced.auto_created.clear();
+        reduced.node_brackets.clear();
+        reduced.groups.clear();
+        reduced.node_marks.clear();
+        reduced.heat_levels.clear();
+        reduced.order_numbers.clear();
+        reduced.max_render_nodes = usize::MAX;
+        reduced.rebuild_caches();
+
+        for (idx, &(id, label)) in self.nodes.iter().enumerate() {
+            if !kept[idx] {
+                continue;
+            }
+            if self.is_auto_created(id) {
+                reduced.ensure_node_exists(id);
+            } else {
+                reduced.add_node(id, label);
+            }
+            if let Some(&(open, close)) = self.node_brackets.get(&id) {
+                reduced.set_node_brackets(id, open, close);
+            }
+            if let Some(&group) = self.groups.get(&id) {
+                reduced.set_group(&[id], group);
+            }
+        }
+        reduced.add_node(summary_id, &summary_label);
+        let (open, close) = self.placeholder_brackets();
+        reduced.set_node_brackets(summary_id, open, close);
+        reduced.summary_node = Some(summary_id);
+
+        for (idx, &(from, _)) in self.nodes.iter().enumerate() {
+            if !kept[idx] {
+                continue;
+            }
+            let mut to_summary = false;
+            for &child in &self.children[idx] {
+                if kept[child] {
+                    reduced.add_edge(from, self.nodes[child].0);
+                } else {
+                    to_summary = true;
+                }
+            }
+            if to_summary {
+                reduced.add_edge(from, summary_id);
+            }
+        }
+
+        if !self.node_marks.is_empty() {
+            reduced.node_marks = reduced
+                .nodes
+                .iter()
+                .map(|&(id, _)| self.node_mark(id))
+                .collect();
+            reduced.refresh_node_widths();
+        }
+
+        reduced.render_body(output);
+    }
+}
@@ -0,0 +1,219 @@
+//! Plain-prose description of a graph, for screen readers, alt text, and diffs.
+
+use crate::graph::{DAG, DescribeDirection};
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+impl<'a> DAG<'a> {
+    /// Describe the graph in plain sentences, without any box drawing.
+    ///
+    /// Same as [`describe_with`](Self::describe_with) listing each node's
+    /// dependencies (parents).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(&[(1, "Fetch"), (2, "Build")], &[(1, 2)]);
+    /// assert_eq!(
+    ///     dag.describe(),
+    ///     "Graph with 2 nodes and 1 edge.\n\
+    ///      Roots: Fetch.\n\
+    ///      Leaves: Build.\n\
+    ///      Level 0:\n\
+    ///      \x20 Fetch depends on nothing.\n\
+    ///      Level 1:\n\
+    ///      \x20 Build depends on: Fetch.\n"
+    /// );
+    /// ```
+    pub fn describe(&self) -> String {
+        self.describe_with(DescribeDirection::Dependencies)
+    }
+
+    /// Describe the graph in plain sentences, listing each node's dependencies
+    /// or dependents.
+    ///
+    /// The description starts with the node and edge counts, the roots, and
+    /// the leaves, then lists nodes level by level (as in the vertical layout),
+    /// in insertion order within a level. Parallel edges are named once.
+    /// Auto-created nodes are called `node N (placeholder)`, and nodes with an
+    /// empty label `node N`. A cyclic graph has no levels: the cycle is named,
+    /// followed by every node in insertion order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::{DAG, DescribeDirection};
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "Fetch")], &[]);
+    /// dag.add_edge(1, 2);
+    /// let text = dag.describe_with(DescribeDirection::Dependents);
+    /// assert!(text.contains("  Fetch is depended on by: node 2 (placeholder).\n"));
+    /// ```
+    pub fn describe_with(&self, direction: DescribeDirection) -> String {
+        let mut text = String::new();
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+        writeln!(
+            text,
+            "Graph with {} node{} and {} edge{}.",
+            self.nodes.len(),
+            plural(self.nodes.len()),
+            self.edges.len(),
+            plural(self.edges.len())
+        )
+        .ok();
+        if self.nodes.is_empty() {
+            return text;
+        }
+
+        let roots: Vec<usize> = (0..self.nodes.len())
+            .filter(|&idx| self.parents[idx].is_empty())
+            .collect();
+        let leaves: Vec<usize> = (0..self.nodes.len())
+            .filter(|&idx| self.children[idx].is_empty())
+            .collect();
+        self.describe_list(&mut text, "Roots: ", &roots, "none");
+        self.describe_list(&mut text, "Leaves: ", &leaves, "none");
+
+        if let Some(cycle) = self.find_cycle_path() {
+            text.push_str("Contains a cycle: ");
+            for &id in &cycle {
+                if let Some(idx) = self.node_index(id) {
+                    self.describe_name(&mut text, idx);
+                    text.push_str(" -> ");
+                }
+            }
+            if let Some(idx) = cycle.first().and_then(|&id| self.node_index(id)) {
+                self.describe_name(&mut text, idx);
+            }
+            text.push_str(".\nNodes:\n");
+            for idx in 0..self.nodes.len() {
+                self.describe_node(&mut text, idx, direction);
+            }
+            return text;
+        }
+
+        let mut levels: Vec<Vec<usize>> = Vec::new();
+        for (idx, level) in self.calculate_levels() {
+            if levels.len() <= level {
+                levels.resize(level + 1, Vec::new());
+            }
+            levels[level].push(idx);
+        }
+        for (level, nodes) in levels.iter().enumerate() {
+            writeln!(text, "Level {}:", level).ok();
+            for &idx in nodes {
+                self.describe_node(&mut text, idx, direction);
+            }
+        }
+        text
+    }
+
+    /// One `  X depends on: A, B.` line.
+    fn describe_node(&self, text: &mut String, idx: usize, direction: DescribeDirection) {
+        let (related, verb) = match direction {
+            DescribeDirection::Dependencies => (&self.parents[idx], "depends on"),
+            DescribeDirection::Dependents => (&self.children[idx], "is depended on by"),
+        };
+        let mut unique: Vec<usize> = Vec::with_capacity(related.len());
+        for &other in related {
+            if !unique.contains(&other) {
+                unique.push(other);
+            }
+        }
+
+        text.push_str("  ");
+        self.describe_name(text, idx);
+        write!(text, " {}", verb).ok();
+        if unique.is_empty() {
+            text.push_str(" nothing.\n");
+        } else {
+            self.describe_list(text, ": ", &unique, "");
+        }
+    }
+
+    /// `prefix` followed by the comma-separated names and a full stop.
+    fn describe_list(&self, text: &mut String, prefix: &str, indices: &[usize], empty: &str) {
+        text.push_str(prefix);
+        if indices.is_empty() {
+            text.push_str(empty);
+        }
+        for (i, &idx) in indices.iter().enumerate() {
+            if i > 0 {
+                text.push_str(", ");
+            }
+            self.describe_name(text, idx);
+        }
+        text.push_str(".\n");
+    }
+
+    /// A node's label, or `node N` / `node N (placeholder)` when it has none.
+    fn describe_name(&self, text: &mut String, idx: usize) {
+        let (id, label) = self.nodes[idx];
+        if self.is_auto_created(id) {
+            write!(text, "node {} (placeholder)", id).ok();
+        } else if label.is_empty() {
+            write!(text, "node {}", id).ok();
+        } else {
+            Self::write_label(text, label);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{DAG, DescribeDirection};
+
+    fn diamond() -> DAG<'static> {
+        DAG::from_edges(
+            &[(1, "Top"), (2, "Left"), (3, "Right"), (4, "Bottom")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        )
+    }
+
+    #[test]
+    fn test_describe_diamond() {
+        assert_eq!(
+            diamond().describe(),
+            "Graph with 4 nodes and 4 edges.\n\
+             Roots: Top.\n\
+             Leaves: Bottom.\n\
+             Level 0:\n\
+             \x20 Top depends on nothing.\n\
+             Level 1:\n\
+             \x20 Left depends on: Top.\n\
+             \x20 Right depends on: Top.\n\
+             Level 2:\n\
+             \x20 Bottom depends on: Left, Right.\n"
+        );
+        assert_eq!(
+            diamond().describe_with(DescribeDirection::Dependents),
+            "Graph with 4 nodes and 4 edges.\n\
+             Roots: Top.\n\
+             Leaves: Bottom.\n\
+             Level 0:\n\
+             \x20 Top is depended on by: Left, Right.\n\
+             Level 1:\n\
+             \x20 Left is depended on by: Bottom.\n\
+             \x20 Right is depended on by: Bottom.\n\
+             Level 2:\n\
+             \x20 Bottom is depended on by nothing.\n"
+        );
+    }
+
+    #[test]
+    fn test_describe_cycle() {
+        let mut dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2), (2, 1)]);
+        dag.add_edge(2, 3);
+        let text = dag.describe();
+        assert!(text.contains("Roots: none.\n"), "{}", text);
+        assert!(
+            text.contains("Contains a cycle: A -> B -> A.\n"),
+            "{}",
+            text
+        );
+        assert!(text.ends_with("  node 3 (placeholder) depends on: B.\n"));
+        assert!(text.is_ascii());
+    }
+}
@@ -0,0 +1,155 @@
+//! Visual diff of two DAGs.
+
+use crate::graph::{DAG, NodeMark, RenderMode};
+use alloc::{string::String, vec::Vec};
+
+impl<'a> DAG<'a> {
+    /// Render the union of `old` and `new` with the changes marked.
+    ///
+    /// - Added nodes render as `+[Label]`, removed nodes as `-[Label]`.
+    /// - Added/removed edges get a `+`/`-` next to their connector: beside the
+    ///   source's `│` for single-child sources, otherwise beside the target's arrow.
+    /// - Unchanged nodes and edges render normally; labels come from `new`.
+    ///
+    /// The union always uses the vertical layout (so every edge has a connector
+    /// to mark) and keeps `new`'s other display settings. Edges spanning several
+    /// levels are not drawn by the vertical renderer, so they carry no marker.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let old = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// let new = DAG::from_edges(&[(1, "A"), (3, "C")], &[(1, 3)]);
+    ///
+    /// let diff = DAG::render_diff(&old, &new);
+    /// assert!(diff.contains("+[C]"));
+    /// assert!(diff.contains("-[B]"));
+    /// ```
+    pub fn render_diff(old: &DAG<'a>, new: &DAG<'a>) -> String {
+        let diff = old.diff(new);
+        let mut union = new.clone();
+        union.set_render_mode(RenderMode::Vertical);
+
+        // Bring back what only exists in `old`
+        for &id in &diff.removed_nodes {
+            if old.is_auto_created(id) {
+                union.ensure_node_exists(id);
+            } else if let Some(idx) = old.node_index(id) {
+                union.add_node(id, old.nodes[idx].1);
+            }
+        }
+        for &(from, to) in &diff.removed_edges {
+            union.add_edge(from, to);
+        }
+
+        union.node_marks = union
+            .nodes
+            .iter()
+            .map(|&(id, _)| {
+                if diff.added_nodes.contains(&id) {
+                    NodeMark::Added
+                } else if diff.removed_nodes.contains(&id) {
+                    NodeMark::Removed
+                } else {
+                    NodeMark::Normal
+                }
+            })
+            .collect();
+        union.refresh_node_widths();
+
+        let (output, positions) = union.render_with_positions();
+        if diff.added_edges.is_empty() && diff.removed_edges.is_empty() {
+            return output;
+        }
+
+        // Overlay the edge markers on the rendered text
+        let mut lines: Vec<Vec<char>> = output.lines().map(|l| l.chars().collect()).collect();
+        let position_of = |id: usize| positions.iter().find(|p| p.id == id);
+
+        let edge_marks = diff
+            .added_edges
+            .iter()
+            .map(|&edge| (edge, '+'))
+            .chain(diff.removed_edges.iter().map(|&edge| (edge, '-')));
+        for ((from, to), marker) in edge_marks {
+            let (Some(src), Some(dst)) = (position_of(from), position_of(to)) else {
+                continue;
+            };
+
+            // Only edges between adjacent node rows have a connector
+            if dst.row <= src.row || positions.iter().any(|p| p.row > src.row && p.row < dst.row) {
+                continue;
+            }
+
+            let siblings = union
+                .get_children(from)
+                .into_iter()
+                .filter(|&child| position_of(child).is_some_and(|p| p.row == dst.row))
+                .count();
+
+            // Beside the source's │ when it is unambiguous, else beside the target's arrow
+            let (row, col) = if siblings > 1 {
+                (dst.row - 1, dst.col + dst.width / 2 + 1)
+            } else {
+                (src.row + 1, src.col + src.width / 2 + 1)
+            };
+
+            let line = &mut lines[row];
+            if line.len() <= col {
+                line.resize(col + 1, ' ');
+            }
+            if line[col] == ' ' {
+                line[col] = marker;
+            } else if col >= 2 && line[col - 2] == ' ' {
+                line[col - 2] = marker;
+            }
+        }
+
+        let mut marked = String::with_capacity(output.len() + lines.len());
+        for line in &lines {
+            marked.extend(line.iter());
+            marked.push('\n');
+        }
+        marked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::DAG;
+
+    const DIAMOND_NODES: &[(usize, &str)] = &[(1, "A"), (2, "B"), (3, "C"), (4, "D"), (5, "E")];
+    const DIAMOND_EDGES: &[(usize, usize)] = &[(1, 2), (1, 3), (2, 4), (3, 4)];
+
+    #[test]
+    fn test_added_edge_marked_once() {
+        let old = DAG::from_edges(DIAMOND_NODES, DIAMOND_EDGES);
+        let mut new = old.clone();
+        new.add_edge(4, 5);
+
+        let diff = DAG::render_diff(&old, &new);
+        assert_eq!(diff.matches('+').count(), 1, "{}", diff);
+        assert!(!diff.contains('-'));
+    }
+
+    #[test]
+    fn test_removed_edge_and_node() {
+        let old = DAG::from_edges(DIAMOND_NODES, &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)]);
+        let new = DAG::from_edges(&DIAMOND_NODES[..4], DIAMOND_EDGES);
+
+        let diff = DAG::render_diff(&old, &new);
+        assert!(diff.contains("-[E]"), "{}", diff);
+        assert_eq!(diff.matches('-').count(), 2, "{}", diff); // node and edge
+        assert!(!diff.contains('+'));
+    }
+
+    #[test]
+    fn test_identical_graphs_unmarked() {
+        let dag = DAG::from_edges(DIAMOND_NODES, DIAMOND_EDGES);
+        let mut vertical = dag.clone();
+        vertical.set_render_mode(crate::graph::RenderMode::Vertical);
+        assert_eq!(DAG::render_diff(&dag, &dag), vertical.render());
+    }
+}
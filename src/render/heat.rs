@@ -0,0 +1,229 @@
+//! Heat markers grading nodes by impact (see `DAG::set_heat`).
+
+use crate::graph::{DAG, HeatMetric, NodeMark};
+use crate::render::ascii::RenderTarget;
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+/// Marked buckets above "none"; bucket `b` is drawn as `b` stars.
+const HEAT_BUCKETS: usize = 4;
+
+/// Largest graph whose reach counts are computed exactly (see `reach_counts`).
+const EXACT_REACH_LIMIT: usize = 4096;
+
+impl<'a> DAG<'a> {
+    /// Render a copy with heat buckets assigned, followed by the legend.
+    pub(crate) fn render_heat(&self, output: &mut impl RenderTarget) {
+        let upward = self.heat == HeatMetric::Ancestors;
+        let counts = self.reach_counts(upward);
+        let max = counts.iter().copied().max().unwrap_or(0);
+
+        let mut heated = self.clone();
+        heated.heat_levels = (0..self.nodes.len())
+            .map(|idx| {
+                let manual = self
+                    .node_marks
+                    .get(idx)
+                    .is_some_and(|&m| m != NodeMark::Normal);
+                if manual || counts[idx] == 0 {
+                    0
+                } else {
+                    counts[idx].saturating_mul(HEAT_BUCKETS).div_ceil(max) as u8
+                }
+            })
+            .collect();
+        heated.refresh_node_widths();
+        heated.render_body(output);
+
+        if max == 0 {
+            return;
+        }
+        let noun = if upward { "ancestors" } else { "descendants" };
+        write!(output, "heat ({}):", noun).ok();
+        let mut first = true;
+        for bucket in 1..=HEAT_BUCKETS {
+            // Bucket b holds counts in ((b-1)·max/4, b·max/4]
+            let low = (bucket - 1) * max / HEAT_BUCKETS + 1;
+            let high = bucket * max / HEAT_BUCKETS;
+            if low > high {
+                continue;
+            }
+            output.push_str(if first { " " } else { ", " });
+            first = false;
+            for _ in 0..bucket {
+                output.push('*');
+            }
+            if low == high {
+                write!(output, " {}", low).ok();
+            } else {
+                write!(output, " {}-{}", low, high).ok();
+            }
+        }
+        writeln!(output).ok();
+    }
+
+    /// Number of distinct descendants (or ancestors when `upward`) per node.
+    ///
+    /// Exact counts need each node's full reachable set: these are kept as one
+    /// `n`-bit row per node, so memory is `n² / 8` bytes and time
+    /// `O(V·E / 64)`. No `O(V + E)` method is known, since distinct counts
+    /// amount to a transitive closure. Past [`EXACT_REACH_LIMIT`] nodes (2 MiB
+    /// of rows) this falls back to [`approx_reach_counts`](Self::approx_reach_counts).
+    /// Callers must have ruled out cycles.
+    fn reach_counts(&self, upward: bool) -> Vec<usize> {
+        let n = self.nodes.len();
+        if n > EXACT_REACH_LIMIT {
+            return self.approx_reach_counts(upward);
+        }
+        let next = if upward {
+            &self.parents
+        } else {
+            &self.children
+        };
+
+        let words = n.div_ceil(64);
+        let mut reach = vec![0u64; n * words];
+        let mut counts = vec![0; n];
+        for idx in self.reach_order(upward) {
+            for &other in &next[idx] {
+                reach[idx * words + other / 64] |= 1 << (other % 64);
+                for w in 0..words {
+                    reach[idx * words + w] |= reach[other * words + w];
+                }
+            }
+            counts[idx] = reach[idx * words..(idx + 1) * words]
+                .iter()
+                .map(|w| w.count_ones() as usize)
+                .sum();
+        }
+        counts
+    }
+
+    /// `O(V + E)` estimate of [`reach_counts`](Self::reach_counts) for large
+    /// graphs: each node adds up `1 + count` over its neighbours, capped at
+    /// `n - 1`. Exact on trees; a node reached along several paths is counted
+    /// once per path, so shared descendants push the estimate up.
+    fn approx_reach_counts(&self, upward: bool) -> Vec<usize> {
+        let n = self.nodes.len();
+        let next = if upward {
+            &self.parents
+        } else {
+            &self.children
+        };
+        let mut counts = vec![0usize; n];
+        for idx in self.reach_order(upward) {
+            let sum = next[idx]
+                .iter()
+                .fold(0usize, |acc, &other| acc.saturating_add(1 + counts[other]));
+            counts[idx] = sum.min(n.saturating_sub(1));
+        }
+        counts
+    }
+
+    /// Node indices ordered so every node comes after the neighbours it reaches
+    /// (children, or parents when `upward`), via Kahn's algorithm from the far end.
+    fn reach_order(&self, upward: bool) -> Vec<usize> {
+        let n = self.nodes.len();
+        let (next, prev) = if upward {
+            (&self.parents, &self.children)
+        } else {
+            (&self.children, &self.parents)
+        };
+
+        let mut remaining: Vec<usize> = next.iter().map(Vec::len).collect();
+        let mut queue: VecDeque<usize> = (0..n).filter(|&idx| remaining[idx] == 0).collect();
+        let mut order = Vec::with_capacity(n);
+        while let Some(idx) = queue.pop_front() {
+            order.push(idx);
+            for &other in &prev[idx] {
+                remaining[other] -= 1;
+                if remaining[other] == 0 {
+                    queue.push_back(other);
+                }
+            }
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{DAG, HeatMetric};
+
+    #[test]
+    fn test_reach_counts_diamond() {
+        let dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4), (2, 4)],
+        );
+        // Shared and parallel paths are counted once
+        assert_eq!(dag.reach_counts(false), vec![3, 1, 1, 0]);
+        assert_eq!(dag.reach_counts(true), vec![0, 1, 1, 3]);
+    }
+
+    #[test]
+    fn test_approx_reach_counts() {
+        // Exact on a tree
+        let tree = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 2), (1, 3), (2, 4)],
+        );
+        assert_eq!(tree.approx_reach_counts(false), tree.reach_counts(false));
+        assert_eq!(tree.approx_reach_counts(true), tree.reach_counts(true));
+
+        // A shared descendant is counted once per path, up to n - 1
+        let diamond = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D"), (5, "E"), (6, "F")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)],
+        );
+        assert_eq!(diamond.reach_counts(false), vec![4, 2, 2, 1, 0, 0]);
+        assert_eq!(diamond.approx_reach_counts(false), vec![5, 2, 2, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_heat_falls_back_past_exact_limit() {
+        // A diamond on top of a long chain, plus one isolated node
+        let n = super::EXACT_REACH_LIMIT;
+        let mut dag = DAG::from_edges(&[], &[(1, 2), (1, 3), (2, 4), (3, 4)]);
+        for id in 4..n {
+            dag.add_edge(id, id + 1);
+        }
+        dag.add_node(n + 1, "Alone");
+        // The root reaches n - 1 nodes, but the estimate counts the chain
+        // below the diamond twice and stops at the cap
+        let counts = dag.reach_counts(false);
+        assert_eq!(counts[0], n);
+        assert_eq!(counts[3], n - 4);
+    }
+
+    #[test]
+    fn test_heat_markers_and_legend() {
+        let mut dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        dag.set_heat(HeatMetric::Ancestors);
+        let output = dag.render();
+        assert!(
+            output.contains("[A] ") || output.contains("[A]\n"),
+            "{}",
+            output
+        );
+        assert!(output.contains("[B]**"));
+        assert!(output.contains("[D]****"));
+        assert!(
+            output.ends_with("heat (ancestors): ** 1, *** 2, **** 3\n"),
+            "{}",
+            output
+        );
+
+        // Markers widen nodes like any suffix, so the layout stays aligned
+        let (width, _) = dag.rendered_dimensions();
+        assert_eq!(
+            width,
+            output.lines().map(|l| l.chars().count()).max().unwrap()
+        );
+
+        dag.set_heat(HeatMetric::Off);
+        assert!(!dag.render().contains('*'));
+    }
+}
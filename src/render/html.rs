@@ -0,0 +1,182 @@
+//! HTML export: the text rendering with every node wrapped in a `<span>`.
+
+use crate::graph::DAG;
+use crate::render::ascii::RenderTarget;
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+/// Escapes everything written to it and wraps node text in spans.
+struct Html<'d, 'a> {
+    dag: &'d DAG<'a>,
+    output: String,
+}
+
+impl RenderTarget for Html<'_, '_> {
+    #[inline]
+    fn push(&mut self, ch: char) {
+        let mut buf = [0u8; 4];
+        push_escaped(&mut self.output, ch.encode_utf8(&mut buf));
+    }
+
+    #[inline]
+    fn push_str(&mut self, s: &str) {
+        push_escaped(&mut self.output, s);
+    }
+
+    fn begin_node(&mut self, id: usize) {
+        // Only the summary node of a capped render lies outside the graph;
+        // its ID is synthetic, so it gets neither `data-id` nor `title`
+        let Some(idx) = self.dag.node_index(id) else {
+            self.output
+                .push_str("<span class=\"dag-node dag-summary\">");
+            return;
+        };
+        let label = self.dag.nodes[idx].1;
+        if self.dag.is_auto_created(id) {
+            write!(
+                self.output,
+                "<span class=\"dag-node dag-placeholder\" data-id=\"{}\" title=\"{}\">",
+                id, id
+            )
+            .ok();
+        } else {
+            write!(
+                self.output,
+                "<span class=\"dag-node\" data-id=\"{}\" title=\"",
+                id
+            )
+            .ok();
+            push_escaped(&mut self.output, label);
+            self.output.push_str("\">");
+        }
+    }
+
+    fn end_node(&mut self) {
+        self.output.push_str("</span>");
+    }
+}
+
+impl Write for Html<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        RenderTarget::push_str(self, s);
+        Ok(())
+    }
+}
+
+/// Append `text` with XML special characters escaped.
+pub(crate) fn push_escaped(out: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+}
+
+impl<'a> DAG<'a> {
+    /// Render to an HTML `<pre>` block with every node in its own `<span>`.
+    ///
+    /// Each span covers exactly the node's text as drawn (the columns reported
+    /// by [`render_with_positions`](Self::render_with_positions)) and carries
+    /// `class="dag-node"`, `data-id`, and the full label as `title`, so CSS can
+    /// highlight nodes on hover and scripts can attach handlers. Placeholders
+    /// also get the `dag-placeholder` class, and the `⟨… +N hidden⟩` node of a
+    /// [capped](Self::set_max_render_nodes) render gets `dag-summary` instead
+    /// of an ID and title. Connectors stay plain text, and all text is
+    /// HTML-escaped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// assert_eq!(
+    ///     dag.to_html(),
+    ///     "<pre class=\"dag\">\
+    ///      <span class=\"dag-node\" data-id=\"1\" title=\"A\">[A]</span> → \
+    ///      <span class=\"dag-node\" data-id=\"2\" title=\"B\">[B]</span>\n\
+    ///      </pre>\n"
+    /// );
+    /// ```
+    pub fn to_html(&self) -> String {
+        let mut html = Html {
+            dag: self,
+            output: String::with_capacity(self.estimate_size() * 4),
+        };
+        html.output.push_str("<pre class=\"dag\">");
+        self.render_into(&mut html);
+        html.output.push_str("</pre>\n");
+        html.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{DAG, RenderMode};
+    use alloc::string::String;
+
+    /// Drop tags and undo escaping, checking that spans are balanced and flat.
+    fn strip_html(html: &str) -> String {
+        let mut text = String::new();
+        let mut depth = 0;
+        let mut rest = html;
+        while let Some(start) = rest.find('<') {
+            text.push_str(&rest[..start]);
+            let end = start + rest[start..].find('>').expect("unterminated tag");
+            let tag = &rest[start..=end];
+            if tag.starts_with("<span") {
+                depth += 1;
+                assert_eq!(depth, 1, "nested span");
+            } else if tag == "</span>" {
+                depth -= 1;
+                assert_eq!(depth, 0, "unbalanced span");
+            }
+            rest = &rest[end + 1..];
+        }
+        text.push_str(rest);
+        assert_eq!(depth, 0);
+        text.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&amp;", "&")
+    }
+
+    #[test]
+    fn test_html_matches_text_render() {
+        let mut dag = DAG::from_edges(
+            &[(1, "<script>"), (2, "a & b"), (3, "\"q\"")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        let html = dag.to_html();
+
+        assert!(html.contains("title=\"&lt;script&gt;\">[&lt;script&gt;]</span>"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("class=\"dag-node dag-placeholder\" data-id=\"4\""));
+        assert_eq!(html.matches("<span").count(), 4);
+
+        let inner = html
+            .strip_prefix("<pre class=\"dag\">")
+            .and_then(|s| s.strip_suffix("</pre>\n"))
+            .unwrap();
+        assert_eq!(strip_html(inner), dag.render());
+    }
+
+    #[test]
+    fn test_html_capped_summary_has_no_id() {
+        let mut dag = DAG::from_edges(&[(1, "Root"), (2, "Mid"), (3, "Leaf")], &[(1, 2), (2, 3)]);
+        dag.set_max_render_nodes(2);
+        let html = dag.to_html();
+
+        assert!(html.contains("<span class=\"dag-node dag-summary\">⟨… +1 hidden⟩</span>"));
+        assert!(!html.contains(&format!("data-id=\"{}\"", usize::MAX)));
+        assert!(!html.contains("title=\"\""));
+        assert_eq!(html.matches("<span").count(), 3);
+    }
+}
@@ -0,0 +1,200 @@
+//! Left-to-right layered rendering (`RenderMode::HorizontalLayered`).
+
+use crate::graph::DAG;
+use crate::render::ascii::RenderTarget;
+use alloc::{vec, vec::Vec};
+
+// Connector cell bits: which neighbours a line in this cell connects to
+const UP: u8 = 1;
+const DOWN: u8 = 2;
+const LEFT: u8 = 4;
+const RIGHT: u8 = 8;
+const ARROW: u8 = 16;
+
+impl<'a> DAG<'a> {
+    /// Render levels as columns, left to right, with `→` connectors between them.
+    ///
+    /// The transpose of the vertical layout: level 0 is the leftmost column and
+    /// nodes are stacked top to bottom within a column. Each node stays on the
+    /// row of its topmost parent where the column has room, so chains read as
+    /// one line. Between two columns every source with a child on another row
+    /// gets its own vertical trunk:
+    ///
+    /// ```text
+    /// [Fetch] ─→ [Parse] ─┬─→ [Test]
+    ///                     │
+    ///                     └─→ [Build]
+    /// ```
+    ///
+    /// As in the vertical layout, edges spanning several levels are not drawn.
+    pub(crate) fn render_horizontal_layered(&self, output: &mut impl RenderTarget) {
+        let (levels, _) = self.layout_levels();
+        self.note_undrawn_edges(output, &levels, levels.len() - 1);
+
+        let mut level_of = vec![0usize; self.nodes.len()];
+        for (level_idx, level_nodes) in levels.iter().enumerate() {
+            for &idx in level_nodes {
+                level_of[idx] = level_idx;
+            }
+        }
+
+        // Rows: follow the topmost parent in the previous column, keeping the
+        // crossing-reduced order and a blank row between stacked nodes
+        let mut row_of = vec![0usize; self.nodes.len()];
+        let mut height = 0;
+        for (level_idx, level_nodes) in levels.iter().enumerate() {
+            let mut next_free = 0;
+            for &idx in level_nodes {
+                let parent_row = self
+                    .get_parents_indices(idx)
+                    .iter()
+                    .filter(|&&p| level_of[p] + 1 == level_idx)
+                    .map(|&p| row_of[p])
+                    .min();
+                let row = parent_row.unwrap_or(0).max(next_free);
+                row_of[idx] = row;
+                next_free = row + 2;
+                height = height.max(row + 1);
+            }
+        }
+
+        // Columns: each as wide as its widest node, followed by a gutter with
+        // one trunk per source that needs to change rows
+        let mut column_x = Vec::with_capacity(levels.len());
+        let mut trunks: Vec<Vec<(usize, usize)>> = Vec::with_capacity(levels.len());
+        let mut x = 0;
+        for level_nodes in &levels {
+            column_x.push(x);
+            let column_width = level_nodes
+                .iter()
+                .map(|&idx| self.get_node_width(idx))
+                .max()
+                .unwrap_or(0);
+
+            let level_trunks: Vec<(usize, usize)> = level_nodes
+                .iter()
+                .filter(|&&idx| {
+                    self.get_children_indices(idx)
+                        .iter()
+                        .any(|&c| level_of[c] == level_of[idx] + 1 && row_of[c] != row_of[idx])
+                })
+                .enumerate()
+                .map(|(k, &idx)| (idx, 2 + 2 * k))
+                .collect();
+
+            x += column_width + 2 * level_trunks.len() + 4;
+            trunks.push(level_trunks);
+        }
+        let width = x;
+
+        let mut grid = vec![vec![0u8; width]; height];
+        for (level_idx, level_nodes) in levels.iter().enumerate().take(levels.len() - 1) {
+            let gutter = column_x[level_idx + 1] - 2 * trunks[level_idx].len() - 4;
+            let arrow_x = gutter + 2 + 2 * trunks[level_idx].len();
+
+            for &idx in level_nodes {
+                let start = column_x[level_idx] + self.get_node_width(idx) + 1;
+                let row = row_of[idx];
+                let trunk = trunks[level_idx]
+                    .iter()
+                    .find(|&&(source, _)| source == idx)
+                    .map(|&(_, offset)| gutter + offset);
+
+                let mut seen = Vec::new();
+                for &child in self.get_children_indices(idx) {
+                    if level_of[child] != level_idx + 1 || seen.contains(&child) {
+                        continue;
+                    }
+                    seen.push(child);
+
+                    let child_row = row_of[child];
+                    match trunk {
+                        Some(trunk_x) if child_row != row => {
+                            draw_horizontal(&mut grid[row], start, trunk_x);
+                            draw_vertical(&mut grid, trunk_x, row, child_row);
+                            draw_horizontal(&mut grid[child_row], trunk_x, arrow_x - 1);
+                        }
+                        _ => draw_horizontal(&mut grid[row], start, arrow_x - 1),
+                    }
+                    grid[child_row][arrow_x] = ARROW;
+                }
+            }
+        }
+
+        // Emit row by row, writing nodes over their cells
+        let mut row_nodes: Vec<Vec<usize>> = vec![Vec::new(); height];
+        for level_nodes in &levels {
+            for &idx in level_nodes {
+                row_nodes[row_of[idx]].push(idx);
+            }
+        }
+
+        for (row, cells) in grid.iter().enumerate() {
+            let line_end = cells.iter().rposition(|&c| c != 0).map_or(0, |i| i + 1);
+            let mut col = 0;
+            for &idx in &row_nodes[row] {
+                let node_x = column_x[level_of[idx]];
+                while col < node_x {
+                    output.push(self.connector_glyph(cells[col]));
+                    col += 1;
+                }
+                let (id, label) = self.nodes[idx];
+                self.write_node(output, id, label);
+                col += self.get_node_width(idx);
+            }
+            while col < line_end {
+                output.push(self.connector_glyph(cells[col]));
+                col += 1;
+            }
+            writeln!(output).ok();
+        }
+    }
+
+    /// Box-drawing character for a connector cell.
+    fn connector_glyph(&self, cell: u8) -> char {
+        match cell {
+            0 => ' ',
+            ARROW => self.theme.arrow_right,
+            c if c == UP | DOWN | LEFT | RIGHT => self.theme.cross,
+            c if c == UP | DOWN | RIGHT => self.theme.tee_right,
+            c if c == UP | DOWN | LEFT => self.theme.tee_left,
+            c if c == LEFT | RIGHT | DOWN => self.theme.tee_down,
+            c if c == LEFT | RIGHT | UP => self.theme.tee_up,
+            c if c == DOWN | RIGHT => self.theme.top_left,
+            c if c == DOWN | LEFT => self.theme.top_right,
+            c if c == UP | RIGHT => self.theme.bottom_left,
+            c if c == UP | LEFT => self.theme.bottom_right,
+            c if c & (UP | DOWN) != 0 => self.theme.vertical,
+            _ => self.theme.horizontal,
+        }
+    }
+}
+
+/// Connect cells `from..=to` of a row horizontally.
+fn draw_horizontal(cells: &mut [u8], from: usize, to: usize) {
+    for x in from..=to {
+        if x > from {
+            cells[x] |= LEFT;
+        }
+        if x < to {
+            cells[x] |= RIGHT;
+        }
+    }
+    // A lone cell still needs a stroke
+    if from == to {
+        cells[from] |= LEFT | RIGHT;
+    }
+}
+
+/// Connect column `x` vertically between rows `a` and `b`.
+fn draw_vertical(grid: &mut [Vec<u8>], x: usize, a: usize, b: usize) {
+    let (top, bottom) = if a < b { (a, b) } else { (b, a) };
+    for (row, cells) in grid.iter_mut().enumerate().take(bottom + 1).skip(top) {
+        if row > top {
+            cells[x] |= UP;
+        }
+        if row < bottom {
+            cells[x] |= DOWN;
+        }
+    }
+}
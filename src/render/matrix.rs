@@ -0,0 +1,109 @@
+//! Adjacency-matrix rendering for small, dense graphs.
+
+use crate::graph::DAG;
+use crate::render::ascii::RenderTarget;
+use alloc::string::String;
+use core::fmt::Write;
+
+impl<'a> DAG<'a> {
+    /// Render the graph as an adjacency matrix.
+    ///
+    /// Rows are edge sources and columns edge targets, both in insertion order
+    /// and labeled with node IDs. A cell shows the right arrow (see
+    /// [`set_arrows`](Self::set_arrows)) when the edge exists and `·` otherwise.
+    /// No layout passes run, which makes this a good fit for dense graphs where
+    /// the hierarchical drawing becomes unreadable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "C")], &[(1, 2), (2, 3)]);
+    ///
+    /// assert_eq!(
+    ///     dag.render_matrix(),
+    ///     "  1 2 3\n\
+    ///      1 · → ·\n\
+    ///      2 · · →\n\
+    ///      3 · · ·\n"
+    /// );
+    /// ```
+    pub fn render_matrix(&self) -> String {
+        let mut output = String::new();
+
+        if self.nodes.is_empty() {
+            output.push_str("Empty DAG");
+            return output;
+        }
+
+        // Every cell is as wide as the longest ID
+        let cell_width = self
+            .nodes
+            .iter()
+            .map(|&(id, _)| Self::count_digits(id))
+            .max()
+            .unwrap_or(1);
+
+        // Header row
+        Self::pad(&mut output, cell_width);
+        for &(id, _) in &self.nodes {
+            output.push(' ');
+            Self::write_usize_padded(&mut output, id, cell_width);
+        }
+        writeln!(output).ok();
+
+        for (from_idx, &(from_id, _)) in self.nodes.iter().enumerate() {
+            Self::write_usize_padded(&mut output, from_id, cell_width);
+            for to_idx in 0..self.nodes.len() {
+                output.push(' ');
+                Self::pad(&mut output, cell_width - 1);
+                output.push(if self.children[from_idx].contains(&to_idx) {
+                    self.theme.arrow_right
+                } else {
+                    '·'
+                });
+            }
+            writeln!(output).ok();
+        }
+
+        output
+    }
+
+    /// Write `n` right-aligned in a field of `width` columns.
+    fn write_usize_padded(output: &mut impl RenderTarget, n: usize, width: usize) {
+        Self::pad(output, width.saturating_sub(Self::count_digits(n)));
+        Self::write_usize(output, n);
+    }
+
+    fn pad(output: &mut impl RenderTarget, count: usize) {
+        for _ in 0..count {
+            output.push(' ');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::DAG;
+
+    #[test]
+    fn test_matrix_wide_ids() {
+        let mut dag = DAG::new();
+        dag.add_edge(7, 120);
+        dag.add_edge(120, 7);
+
+        // Cycles don't matter for the matrix view
+        assert_eq!(
+            dag.render_matrix(),
+            "      7 120\n\
+             \x20 7   ·   →\n\
+             120   →   ·\n"
+        );
+    }
+
+    #[test]
+    fn test_matrix_empty() {
+        assert_eq!(DAG::new().render_matrix(), "Empty DAG");
+    }
+}
@@ -1,6 +1,17 @@
 //! ASCII rendering for DAG visualization.
 //!
 //! This module provides ASCII-art rendering capabilities for directed acyclic graphs,
-//! including horizontal, vertical, and cycle visualization modes.
+//! including horizontal, vertical, layered, and cycle visualization modes, plus an
+//! adjacency-matrix view for dense graphs, plus HTML and (with the `svg` feature) SVG export.
 
 pub mod ascii;
+mod capped;
+mod describe;
+mod diff;
+mod heat;
+mod html;
+mod layered;
+mod matrix;
+mod report;
+#[cfg(feature = "svg")]
+pub mod svg;
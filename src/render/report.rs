@@ -0,0 +1,240 @@
+//! Recording what a rendering drew (see `DAG::render_with_report`).
+
+use crate::graph::DAG;
+use crate::render::ascii::{RenderEvent, RenderReport, RenderTarget, Theme};
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+/// Forwards to another target while accumulating a [`RenderReport`].
+struct Reported<'o, T: RenderTarget> {
+    inner: &'o mut T,
+    theme: Theme,
+    in_node: bool,
+    report: RenderReport,
+}
+
+impl<T: RenderTarget> Reported<'_, T> {
+    /// Record `ch` if it is one of the theme's glyphs drawn outside a node.
+    fn record_glyph(&mut self, ch: char) {
+        let t = &self.theme;
+        let glyphs = [
+            t.vertical,
+            t.horizontal,
+            t.top_left,
+            t.top_right,
+            t.bottom_left,
+            t.bottom_right,
+            t.tee_down,
+            t.tee_up,
+            t.tee_right,
+            t.tee_left,
+            t.cross,
+            t.arrow_down,
+            t.arrow_up,
+            t.arrow_right,
+            t.arrow_left,
+            t.arrow_cycle,
+            t.separator,
+        ];
+        if !self.in_node && glyphs.contains(&ch) && !self.report.glyphs.contains(&ch) {
+            self.report.glyphs.push(ch);
+        }
+    }
+}
+
+impl<T: RenderTarget> RenderTarget for Reported<'_, T> {
+    fn push(&mut self, ch: char) {
+        self.record_glyph(ch);
+        self.inner.push(ch);
+    }
+
+    fn push_str(&mut self, s: &str) {
+        for ch in s.chars() {
+            self.record_glyph(ch);
+        }
+        self.inner.push_str(s);
+    }
+
+    fn begin_node(&mut self, id: usize) {
+        self.in_node = true;
+        self.inner.begin_node(id);
+    }
+
+    fn end_node(&mut self) {
+        self.in_node = false;
+        self.inner.end_node();
+    }
+
+    fn note(&mut self, event: RenderEvent) {
+        let report = &mut self.report;
+        match event {
+            RenderEvent::Convergences(n) => report.convergences += n,
+            RenderEvent::Divergences(n) => report.divergences += n,
+            RenderEvent::HiddenLevels(n) => report.hidden_levels += n,
+            RenderEvent::HiddenNodes(n) => report.hidden_nodes += n,
+            RenderEvent::UndrawnEdges(n) => report.undrawn_edges += n,
+        }
+        self.inner.note(event);
+    }
+
+    fn begin_heading(&mut self) {
+        self.inner.begin_heading();
+    }
+}
+
+impl<T: RenderTarget> Write for Reported<'_, T> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        RenderTarget::push_str(self, s);
+        Ok(())
+    }
+}
+
+impl<'a> DAG<'a> {
+    /// Render the DAG and report what the drawing contains.
+    ///
+    /// The string is exactly what [`render`](Self::render) returns (including
+    /// the summary block if [`set_summary`](Self::set_summary) is on). The
+    /// report reflects the decisions made while drawing: which line glyphs
+    /// appear, how many junctions were drawn in vertical layouts, and what
+    /// was left out by the depth limit, the node cap, or the layout itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::{DAG, RenderMode};
+    ///
+    /// let mut dag = DAG::from_edges(
+    ///     &[(1, "A"), (2, "B"), (3, "C")],
+    ///     &[(1, 2), (1, 3), (2, 3)],
+    /// );
+    /// dag.set_render_mode(RenderMode::Vertical);
+    ///
+    /// let (output, report) = dag.render_with_report();
+    /// assert_eq!(output, dag.render());
+    /// assert_eq!(report.undrawn_edges, 1); // 1 → 3 skips a level
+    /// assert_eq!(report.placeholders, 0);
+    /// ```
+    pub fn render_with_report(&self) -> (String, RenderReport) {
+        let mut output = String::with_capacity(self.estimate_size());
+        self.write_title(&mut output);
+        let report = self.render_reported(&mut output);
+        if self.summary {
+            self.write_summary(&mut output, &report);
+        }
+        (output, report)
+    }
+
+    /// Render the graph into `output` and return what was drawn.
+    pub(crate) fn render_reported(&self, output: &mut impl RenderTarget) -> RenderReport {
+        let mut reported = Reported {
+            inner: output,
+            theme: self.theme,
+            in_node: false,
+            report: RenderReport::default(),
+        };
+        self.render_body(&mut reported);
+
+        let mut report = reported.report;
+        report.placeholders = self.auto_created.len();
+        report
+    }
+
+    /// Write the summary block for `report`.
+    pub(crate) fn write_summary(&self, output: &mut impl RenderTarget, report: &RenderReport) {
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+
+        writeln!(output).ok();
+        output.push_str("glyphs:");
+        if report.glyphs.is_empty() {
+            output.push_str(" none");
+        }
+        for &glyph in &report.glyphs {
+            output.push(' ');
+            output.push(glyph);
+        }
+        writeln!(output).ok();
+
+        writeln!(
+            output,
+            "junctions: {} convergence{}, {} divergence{}",
+            report.convergences,
+            plural(report.convergences),
+            report.divergences,
+            plural(report.divergences)
+        )
+        .ok();
+
+        output.push_str("hidden:");
+        let mut any = false;
+        for (count, what, why) in [
+            (report.hidden_levels, "level", "depth limit"),
+            (report.hidden_nodes, "node", "node cap"),
+            (report.undrawn_edges, "edge", "spans levels"),
+        ] {
+            if count > 0 {
+                output.push_str(if any { ", " } else { " " });
+                write!(output, "{} {}{} ({})", count, what, plural(count), why).ok();
+                any = true;
+            }
+        }
+        if !any {
+            output.push_str(" nothing");
+        }
+        writeln!(output).ok();
+
+        writeln!(output, "placeholders: {}", report.placeholders).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{DAG, RenderMode};
+
+    #[test]
+    fn test_report_junctions_and_glyphs() {
+        let mut dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "D")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        dag.set_render_mode(RenderMode::Vertical);
+        let (_, report) = dag.render_with_report();
+
+        assert_eq!((report.convergences, report.divergences), (1, 1));
+        assert!(report.glyphs.contains(&'│'));
+        assert!(report.glyphs.contains(&'↓'));
+        assert_eq!(report.undrawn_edges, 0);
+    }
+
+    #[test]
+    fn test_report_glyphs_ignore_labels() {
+        let dag = DAG::from_edges(&[(1, "a│b")], &[]);
+        let (_, report) = dag.render_with_report();
+        assert!(report.glyphs.is_empty());
+    }
+
+    #[test]
+    fn test_summary_block() {
+        let mut dag = DAG::new();
+        for i in 1..5 {
+            dag.add_edge(i, i + 1);
+        }
+        dag.add_edge(1, 3);
+        dag.set_render_mode(RenderMode::Vertical);
+        dag.set_max_render_depth(2);
+        dag.set_summary(true);
+
+        let output = dag.render();
+        assert!(
+            output.ends_with(
+                "… (3 more levels)\n\n\
+                 glyphs: │ ↓\n\
+                 junctions: 0 convergences, 0 divergences\n\
+                 hidden: 3 levels (depth limit)\n\
+                 placeholders: 5\n"
+            ),
+            "{}",
+            output
+        );
+        assert_eq!(dag.rendered_dimensions().1, output.lines().count());
+    }
+}
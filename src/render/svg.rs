@@ -0,0 +1,215 @@
+//! SVG export built on the vertical layout (see [`DAG::layout`]).
+//!
+//! Requires the `svg` feature.
+
+use crate::graph::DAG;
+use crate::render::html::push_escaped;
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Sizing for [`DAG::to_svg_with`]. All lengths are in SVG user units (px).
+///
+/// Layout columns are character cells, so `char_width` should roughly match
+/// the advance of `font_family` at `font_size` for labels to fit their boxes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SvgStyle {
+    /// Width of one layout column
+    pub char_width: usize,
+    /// Height of a node box
+    pub box_height: usize,
+    /// Vertical space between two levels
+    pub level_spacing: usize,
+    /// Label font size
+    pub font_size: usize,
+    /// Margin around the drawing
+    pub padding: usize,
+    /// Label font (a monospace family keeps the column math honest)
+    pub font_family: &'static str,
+}
+
+impl Default for SvgStyle {
+    fn default() -> Self {
+        Self {
+            char_width: 9,
+            box_height: 28,
+            level_spacing: 36,
+            font_size: 14,
+            padding: 16,
+            font_family: "monospace",
+        }
+    }
+}
+
+impl<'a> DAG<'a> {
+    /// Export the graph as a standalone SVG document with the default
+    /// [`SvgStyle`].
+    ///
+    /// Nodes are boxes with their label at the positions computed by
+    /// [`layout`](Self::layout) (levels top to bottom); auto-created nodes get
+    /// a dashed outline and show their ID. Edges are polylines with the same
+    /// elbows as the text connectors (down from the parent, across the gap
+    /// between levels, down into the child) ending in an arrowhead. Empty and
+    /// cyclic graphs produce an empty drawing.
+    ///
+    /// The canvas is sized from the layout: [`Layout::width`](crate::layout::Layout::width)
+    /// columns of `char_width` and one row per level, plus `padding` on every
+    /// side. The [`Alignment`](crate::graph::Alignment) setting only affects
+    /// the ASCII renderer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "Parse"), (2, "Compile")], &[(1, 2)]);
+    /// dag.add_edge(2, 3);
+    ///
+    /// let svg = dag.to_svg();
+    /// assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    /// assert_eq!(svg.matches("<rect").count(), 3);
+    /// assert_eq!(svg.matches("marker-end").count(), 2);
+    /// assert!(svg.contains("stroke-dasharray")); // placeholder 3
+    /// ```
+    pub fn to_svg(&self) -> String {
+        self.to_svg_with(&SvgStyle::default())
+    }
+
+    /// Export the graph as SVG with custom sizing (see [`to_svg`](Self::to_svg)).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    /// use ascii_dag::render::svg::SvgStyle;
+    ///
+    /// let dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// let style = SvgStyle { font_size: 20, char_width: 12, ..SvgStyle::default() };
+    /// assert!(dag.to_svg_with(&style).contains("font-size=\"20\""));
+    /// ```
+    pub fn to_svg_with(&self, style: &SvgStyle) -> String {
+        let layout = self.layout();
+        let level_y =
+            |level: usize| style.padding + level * (style.box_height + style.level_spacing);
+        let width = 2 * style.padding + layout.width * style.char_width;
+        let height = if layout.levels == 0 {
+            2 * style.padding
+        } else {
+            level_y(layout.levels - 1) + style.box_height + style.padding
+        };
+
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">",
+            w = width,
+            h = height
+        )
+        .ok();
+        svg.push_str(
+            "<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+             markerWidth=\"8\" markerHeight=\"8\" orient=\"auto-start-reverse\">\
+             <path d=\"M 0 0 L 10 5 L 0 10 z\"/></marker></defs>\n",
+        );
+
+        // Edges first, so boxes are drawn on top of them
+        for &(from, to) in &self.edges {
+            let (Some(src), Some(dst)) = (layout.node(from), layout.node(to)) else {
+                continue;
+            };
+            let x1 = style.padding + src.x * style.char_width + src.width * style.char_width / 2;
+            let y1 = level_y(src.level) + style.box_height;
+            let x2 = style.padding + dst.x * style.char_width + dst.width * style.char_width / 2;
+            let y2 = level_y(dst.level);
+            // Same elbow as the text connectors: drop from the parent, run along
+            // the gap between the levels, then drop onto the child's center
+            let mid = y1 + style.level_spacing / 2;
+            svg.push_str("<polyline points=\"");
+            if x1 == x2 {
+                write!(svg, "{},{} {},{}", x1, y1, x2, y2).ok();
+            } else {
+                write!(
+                    svg,
+                    "{},{} {},{} {},{} {},{}",
+                    x1, y1, x1, mid, x2, mid, x2, y2
+                )
+                .ok();
+            }
+            svg.push_str("\" fill=\"none\" stroke=\"black\" marker-end=\"url(#arrow)\"/>\n");
+        }
+
+        for node in &layout.nodes {
+            let Some(idx) = self.node_index(node.id) else {
+                continue;
+            };
+            let (id, label) = self.nodes[idx];
+            let placeholder = self.is_auto_created(id);
+
+            let x = style.padding + node.x * style.char_width;
+            let y = level_y(node.level);
+            let box_width = node.width * style.char_width;
+            write!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"4\" fill=\"white\" stroke=\"black\"",
+                x, y, box_width, style.box_height
+            )
+            .ok();
+            if placeholder {
+                svg.push_str(" stroke-dasharray=\"4 3\"");
+            }
+            svg.push_str("/>\n");
+
+            write!(
+                svg,
+                "<text x=\"{}\" y=\"{}\" font-family=\"{}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">",
+                x + box_width / 2,
+                y + style.box_height / 2,
+                style.font_family,
+                style.font_size
+            )
+            .ok();
+            if placeholder {
+                write!(svg, "{}", id).ok();
+            } else {
+                let mut text = String::new();
+                Self::write_label(&mut text, label);
+                push_escaped(&mut svg, &text);
+            }
+            svg.push_str("</text>\n");
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::DAG;
+
+    #[test]
+    fn test_labels_escaped() {
+        let dag = DAG::from_edges(&[(1, "<a & \"b\">")], &[]);
+        let svg = dag.to_svg();
+        assert!(svg.contains("&lt;a &amp; &quot;b&quot;&gt;"), "{}", svg);
+        assert!(!svg.contains("<a "));
+    }
+
+    #[test]
+    fn test_cyclic_graph_is_empty_drawing() {
+        let mut dag = DAG::new();
+        dag.add_edge(1, 2);
+        dag.add_edge(2, 1);
+        let svg = dag.to_svg();
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(!svg.contains("<rect"));
+    }
+
+    #[test]
+    fn test_diamond_golden() {
+        let dag = DAG::from_edges(
+            &[(1, "Top"), (2, "Left"), (3, "Right"), (4, "Bottom")],
+            &[(1, 2), (1, 3), (2, 4), (3, 4)],
+        );
+        assert_eq!(dag.to_svg(), include_str!("../../tests/golden/diamond.svg"));
+    }
+}
@@ -0,0 +1,257 @@
+//! Structural checks before rendering or shipping a graph.
+//!
+//! [`DAG::validate`](crate::graph::DAG::validate) lists everything questionable about a graph in
+//! one pass: cycles, self-loops, parallel edges, placeholders that never got a
+//! label, empty labels, and nodes without any edges.
+//!
+//! ```
+//! use ascii_dag::graph::DAG;
+//! use ascii_dag::validate::ValidationIssue;
+//!
+//! let mut dag = DAG::from_edges(&[(1, "App"), (2, "Log")], &[(1, 2)]);
+//! assert!(dag.is_valid());
+//!
+//! dag.add_edge(2, 3);
+//! assert_eq!(dag.validate(), vec![ValidationIssue::UnlabeledPlaceholder(3)]);
+//! ```
+
+use crate::graph::DAG;
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::{BTreeMap as HashMap, BTreeSet as HashSet};
+
+/// A problem found by [`DAG::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// Node IDs along one cycle, in edge order (self-loops are reported separately)
+    Cycle(Vec<usize>),
+    /// Node created by an edge but never given a label
+    UnlabeledPlaceholder(usize),
+    /// Edge `(from, to)` added more than once
+    DuplicateEdge(usize, usize),
+    /// Edge from a node to itself
+    SelfLoop(usize),
+    /// Node without any edges in a graph with more than one node
+    IsolatedNode(usize),
+    /// Node explicitly added with an empty label
+    EmptyLabel(usize),
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationIssue::Cycle(path) => {
+                write!(f, "cycle: ")?;
+                for id in path {
+                    write!(f, "{} → ", id)?;
+                }
+                match path.first() {
+                    Some(first) => write!(f, "{}", first),
+                    None => Ok(()),
+                }
+            }
+            ValidationIssue::UnlabeledPlaceholder(id) => {
+                write!(f, "node {} is referenced by an edge but has no label", id)
+            }
+            ValidationIssue::DuplicateEdge(from, to) => {
+                write!(f, "edge {} → {} is added more than once", from, to)
+            }
+            ValidationIssue::SelfLoop(id) => write!(f, "node {} has an edge to itself", id),
+            ValidationIssue::IsolatedNode(id) => write!(f, "node {} has no edges", id),
+            ValidationIssue::EmptyLabel(id) => write!(f, "node {} has an empty label", id),
+        }
+    }
+}
+
+impl<'a> DAG<'a> {
+    /// Report everything questionable about the graph.
+    ///
+    /// Issues are grouped by kind in the order of the [`ValidationIssue`]
+    /// variants, each kind in node/edge insertion order. At most one cycle is
+    /// reported. An empty result means the graph is ready to render.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    /// use ascii_dag::validate::ValidationIssue;
+    ///
+    /// let mut dag = DAG::from_edges(&[(1, "A"), (2, "B"), (3, "")], &[(1, 2)]);
+    /// dag.add_edge(2, 2);
+    ///
+    /// assert_eq!(
+    ///     dag.validate(),
+    ///     vec![
+    ///         ValidationIssue::SelfLoop(2),
+    ///         ValidationIssue::IsolatedNode(3),
+    ///         ValidationIssue::EmptyLabel(3),
+    ///     ]
+    /// );
+    /// assert_eq!(dag.validate()[0].to_string(), "node 2 has an edge to itself");
+    /// ```
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        if let Some(cycle) = self.find_cycle_ignoring_self_loops() {
+            issues.push(ValidationIssue::Cycle(cycle));
+        }
+
+        for &(id, _) in &self.nodes {
+            if self.is_auto_created(id) {
+                issues.push(ValidationIssue::UnlabeledPlaceholder(id));
+            }
+        }
+
+        // Occurrences of each edge; each issue is reported at its first occurrence
+        let mut edge_counts: HashMap<(usize, usize), usize> = HashMap::new();
+        for &edge in &self.edges {
+            *edge_counts.entry(edge).or_insert(0) += 1;
+        }
+        let mut reported: HashSet<(usize, usize)> = HashSet::new();
+        let first_occurrences: Vec<(usize, usize)> = self
+            .edges
+            .iter()
+            .copied()
+            .filter(|&edge| reported.insert(edge))
+            .collect();
+
+        for &(from, to) in &first_occurrences {
+            if edge_counts[&(from, to)] > 1 {
+                issues.push(ValidationIssue::DuplicateEdge(from, to));
+            }
+        }
+        for &(from, to) in &first_occurrences {
+            if from == to {
+                issues.push(ValidationIssue::SelfLoop(from));
+            }
+        }
+
+        if self.nodes.len() > 1 {
+            for (idx, &(id, _)) in self.nodes.iter().enumerate() {
+                if self.children[idx].is_empty() && self.parents[idx].is_empty() {
+                    issues.push(ValidationIssue::IsolatedNode(id));
+                }
+            }
+        }
+
+        for &(id, label) in &self.nodes {
+            if label.is_empty() && !self.is_auto_created(id) {
+                issues.push(ValidationIssue::EmptyLabel(id));
+            }
+        }
+
+        issues
+    }
+
+    /// Check that [`validate`](Self::validate) finds nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ascii_dag::graph::DAG;
+    ///
+    /// let dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    /// assert!(dag.is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_empty()
+    }
+
+    /// One cycle of length two or more, in edge order.
+    ///
+    /// Kahn's algorithm (with self-loops skipped) leaves exactly the nodes on or
+    /// downstream of a cycle. Each of them still has a parent among the
+    /// leftovers, so walking parents from any leftover node must revisit one.
+    fn find_cycle_ignoring_self_loops(&self) -> Option<Vec<usize>> {
+        let n = self.nodes.len();
+        let mut in_degree: Vec<usize> = (0..n)
+            .map(|idx| self.parents[idx].iter().filter(|&&p| p != idx).count())
+            .collect();
+        let mut queue: VecDeque<usize> = (0..n).filter(|&idx| in_degree[idx] == 0).collect();
+        let mut removed = vec![false; n];
+
+        while let Some(idx) = queue.pop_front() {
+            removed[idx] = true;
+            for &child in &self.children[idx] {
+                if child != idx {
+                    in_degree[child] -= 1;
+                    if in_degree[child] == 0 {
+                        queue.push_back(child);
+                    }
+                }
+            }
+        }
+
+        let start = (0..n).find(|&idx| !removed[idx])?;
+        let mut step = vec![usize::MAX; n]; // Position in the walk, MAX = unvisited
+        let mut walk = Vec::new();
+        let mut current = start;
+        while step[current] == usize::MAX {
+            step[current] = walk.len();
+            walk.push(current);
+            current = *self.parents[current]
+                .iter()
+                .find(|&&p| p != current && !removed[p])?;
+        }
+
+        // The walk followed edges backwards; start at the earliest-inserted node
+        let mut cycle: Vec<usize> = walk[step[current]..].to_vec();
+        cycle.reverse();
+        let first = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap_or(0);
+        cycle.rotate_left(first);
+        Some(cycle.into_iter().map(|idx| self.nodes[idx].0).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ValidationIssue;
+    use crate::graph::DAG;
+
+    #[test]
+    fn test_every_issue_kind() {
+        let mut dag = DAG::from_edges(
+            &[(1, "A"), (2, "B"), (3, "C"), (4, "Lone"), (5, "")],
+            &[(1, 2), (2, 3), (3, 1), (1, 2)],
+        );
+        dag.add_edge(5, 5);
+        dag.add_edge(3, 6);
+
+        assert_eq!(
+            dag.validate(),
+            vec![
+                ValidationIssue::Cycle(vec![1, 2, 3]),
+                ValidationIssue::UnlabeledPlaceholder(6),
+                ValidationIssue::DuplicateEdge(1, 2),
+                ValidationIssue::SelfLoop(5),
+                ValidationIssue::IsolatedNode(4),
+                ValidationIssue::EmptyLabel(5),
+            ]
+        );
+        assert!(!dag.is_valid());
+    }
+
+    #[test]
+    fn test_messages() {
+        assert_eq!(
+            ValidationIssue::Cycle(vec![1, 2, 3]).to_string(),
+            "cycle: 1 → 2 → 3 → 1"
+        );
+        assert_eq!(
+            ValidationIssue::DuplicateEdge(1, 2).to_string(),
+            "edge 1 → 2 is added more than once"
+        );
+    }
+
+    #[test]
+    fn test_single_node_is_valid() {
+        let mut dag = DAG::new();
+        dag.add_node(1, "Only");
+        assert!(dag.is_valid());
+    }
+}
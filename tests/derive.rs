@@ -0,0 +1,88 @@
+//! `#[derive(CycleDetectable)]`, run with `cargo test --features derive`.
+
+#![cfg(feature = "derive")]
+
+use ascii_dag::cycles::generic::{CycleDetectable, detect_cycle};
+use std::collections::BTreeSet;
+
+// Same shape as the hand-written impl in the crate's own cycle tests
+#[derive(CycleDetectable)]
+struct ErrorNode {
+    #[dag(id)]
+    id: usize,
+    #[allow(dead_code)]
+    message: &'static str,
+    #[dag(dependencies)]
+    caused_by: Vec<usize>,
+}
+
+// Same shape as `ChainableError` in the error_registry example
+#[derive(CycleDetectable)]
+struct ChainableError {
+    #[dag(id)]
+    code: &'static str,
+    #[allow(dead_code)]
+    message: String,
+    #[dag(dependencies)]
+    caused_by: Vec<&'static str>,
+}
+
+#[derive(CycleDetectable)]
+struct Task<'a>(#[dag(id)] &'a str, #[dag(dependencies)] BTreeSet<&'a str>);
+
+fn error(id: usize, caused_by: &[usize]) -> ErrorNode {
+    ErrorNode {
+        id,
+        message: "",
+        caused_by: caused_by.to_vec(),
+    }
+}
+
+#[test]
+fn derived_impl_reads_marked_fields() {
+    let node = error(7, &[1, 2]);
+    assert_eq!(node.id(), 7);
+    assert_eq!(node.dependencies().collect::<Vec<_>>(), vec![1, 2]);
+}
+
+#[test]
+fn derived_error_node_detects_cycles() {
+    let cyclic = [error(1, &[2]), error(2, &[3]), error(3, &[1])];
+    assert!(detect_cycle(&cyclic).is_some());
+
+    let acyclic = [error(1, &[2]), error(2, &[])];
+    assert!(detect_cycle(&acyclic).is_none());
+}
+
+#[test]
+fn derived_chainable_error_detects_cycles() {
+    let errors = [
+        ChainableError {
+            code: "E001",
+            message: "Disk full".to_string(),
+            caused_by: vec!["E002"],
+        },
+        ChainableError {
+            code: "E002",
+            message: "Write failed".to_string(),
+            caused_by: vec!["E001"],
+        },
+    ];
+    assert!(detect_cycle(&errors).is_some());
+    assert_eq!(errors[0].id(), "E001");
+}
+
+#[test]
+fn derived_tuple_struct_with_set() {
+    let tasks = [
+        Task("build", BTreeSet::from(["fetch"])),
+        Task("fetch", BTreeSet::new()),
+    ];
+    assert!(detect_cycle(&tasks).is_none());
+    assert_eq!(tasks[0].dependencies().collect::<Vec<_>>(), vec!["fetch"]);
+}
+
+#[test]
+fn missing_or_bad_attributes_fail_to_compile() {
+    trybuild::TestCases::new().compile_fail("tests/derive/*.rs");
+}
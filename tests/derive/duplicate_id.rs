@@ -0,0 +1,13 @@
+use ascii_dag::cycles::generic::CycleDetectable;
+
+#[derive(CycleDetectable)]
+struct Node {
+    #[dag(id)]
+    id: usize,
+    #[dag(id)]
+    key: usize,
+    #[dag(dependencies)]
+    deps: Vec<usize>,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use ascii_dag::cycles::generic::CycleDetectable;
+
+#[derive(CycleDetectable)]
+enum Node {
+    Leaf,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use ascii_dag::cycles::generic::CycleDetectable;
+
+#[derive(CycleDetectable)]
+struct Node {
+    #[dag(id)]
+    id: usize,
+    deps: Vec<usize>,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use ascii_dag::cycles::generic::CycleDetectable;
+
+#[derive(CycleDetectable)]
+struct Node {
+    id: usize,
+    #[dag(dependencies)]
+    deps: Vec<usize>,
+}
+
+fn main() {}
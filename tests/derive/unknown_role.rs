@@ -0,0 +1,11 @@
+use ascii_dag::cycles::generic::CycleDetectable;
+
+#[derive(CycleDetectable)]
+struct Node {
+    #[dag(id)]
+    id: usize,
+    #[dag(children)]
+    deps: Vec<usize>,
+}
+
+fn main() {}
@@ -0,0 +1,87 @@
+//! Smoke test for the `alloc`-only build.
+//!
+//! Run with `cargo test --no-default-features --test no_std`, and again with
+//! `--features generic` for the closure-based algorithms. The test crate
+//! itself is `no_std` too, so only `core`/`alloc` APIs of ascii-dag are usable
+//! here; `std` is linked solely for the test harness.
+
+#![no_std]
+
+extern crate alloc;
+extern crate std;
+
+use alloc::string::String;
+use ascii_dag::graph::{DAG, RenderMode};
+
+#[test]
+fn render_into_alloc_string() {
+    let mut dag = DAG::new();
+    dag.add_node(1, "Parse");
+    dag.add_node(2, "Compile");
+    dag.add_edge(1, 2);
+    dag.add_edge(2, 3); // auto-created placeholder
+
+    let mut output = String::new();
+    dag.render_to(&mut output);
+    assert_eq!(output, "[Parse] → [Compile] → ⟨3⟩\n");
+    assert_eq!(dag.render(), output);
+}
+
+#[test]
+fn vertical_layout_and_queries() {
+    let mut dag = DAG::from_edges(
+        &[(1, "Root"), (2, "Left"), (3, "Right"), (4, "Merge")],
+        &[(1, 2), (1, 3), (2, 4), (3, 4)],
+    );
+    dag.set_render_mode(RenderMode::Vertical);
+
+    let output = dag.render();
+    assert!(output.contains("[Root]"));
+    assert!(output.contains("[Merge]"));
+    assert_eq!(dag.rendered_dimensions().1, output.lines().count());
+    assert!(!dag.has_cycle());
+    assert_eq!(dag.topological_sort().unwrap().len(), 4);
+}
+
+#[test]
+fn cycle_rendering() {
+    let mut dag = DAG::new();
+    dag.add_edge(1, 2);
+    dag.add_edge(2, 1);
+    assert!(dag.has_cycle());
+    assert!(dag.render().contains("CYCLE DETECTED"));
+}
+
+// Without `std` the generic algorithms key their lookups by `Ord` (BTreeMap)
+#[cfg(feature = "generic")]
+#[test]
+fn generic_algorithms() {
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use ascii_dag::cycles::generic::detect_cycle_fn;
+    use ascii_dag::cycles::generic::roots::find_roots_fn;
+    use ascii_dag::layout::generic::impact::compute_descendants_fn;
+    use ascii_dag::layout::generic::metrics::GraphMetrics;
+    use ascii_dag::layout::generic::topological_sort_fn;
+
+    let get_deps = |&id: &usize| -> Vec<usize> {
+        match id {
+            2 | 3 => vec![1],
+            4 => vec![2, 3],
+            _ => vec![],
+        }
+    };
+    let items = [4, 3, 2, 1];
+
+    assert!(detect_cycle_fn(&items, get_deps).is_none());
+    assert_eq!(topological_sort_fn(&items, get_deps), Ok(vec![1, 3, 2, 4]));
+    assert_eq!(find_roots_fn(&items, get_deps), vec![1]);
+    assert_eq!(compute_descendants_fn(&items, &1, get_deps).len(), 3);
+    assert_eq!(
+        GraphMetrics::compute(&items, get_deps).path_count(),
+        Some(2)
+    );
+
+    let dag = DAG::from_edges(&[(1, "A"), (2, "B")], &[(1, 2)]);
+    assert_eq!(dag.impact_index().blast_radius(&1).1, vec![2]);
+}